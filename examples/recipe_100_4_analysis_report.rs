@@ -7,16 +7,17 @@
 //! ## Overview
 //!
 //! This recipe demonstrates how to generate comprehensive project analysis reports
-//! in multiple formats (JSON, Markdown, HTML). Reports include TDG scores, metrics,
+//! in multiple formats (JSON, Markdown, HTML, CSV). Reports include TDG scores, metrics,
 //! language distribution, and actionable recommendations.
 //!
 //! ## Features
 //!
-//! - **Multiple Formats:** JSON, Markdown, and HTML output
+//! - **Multiple Formats:** JSON, Markdown, HTML, and CSV output
 //! - **Rich Metrics:** Lines of code, file counts, language distribution
 //! - **TDG Scoring:** Technical debt grade with detailed breakdown
-//! - **Recommendations:** Actionable improvement suggestions
+//! - **Recommendations:** Prioritized, deduplicated improvement suggestions
 //! - **Customization:** Configurable report sections and styling
+//! - **Report Diffing:** Compare two reports to track quality over time
 //!
 //! ## Examples
 //!
@@ -32,9 +33,11 @@
 //! cargo test --example recipe_100_4_analysis_report
 //! ```
 
-use batuta_cookbook::types::{Grade, Result, TdgScore};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use batuta_cookbook::types::{Error, Grade, Result};
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
 use std::fs;
 use std::path::Path;
 
@@ -98,18 +101,175 @@ impl Default for ProjectMetrics {
 pub struct AnalysisReport {
     /// Project name
     pub project_name: String,
-    /// Analysis timestamp
+    /// Analysis timestamp (RFC 3339, validated on deserialization)
+    #[serde(deserialize_with = "deserialize_rfc3339")]
     pub timestamp: String,
     /// Project metrics
     pub metrics: ProjectMetrics,
     /// TDG score
     pub tdg_score: TdgScoreData,
-    /// Recommendations for improvement
-    pub recommendations: Vec<String>,
+    /// Recommendations for improvement (plain strings from older reports load too)
+    #[serde(deserialize_with = "deserialize_recommendations")]
+    pub recommendations: Vec<Recommendation>,
     /// Warnings and issues found
     pub warnings: Vec<String>,
 }
 
+impl AnalysisReport {
+    /// Stamp the report with the current UTC time in RFC 3339 format
+    #[must_use]
+    pub fn with_now(mut self) -> Self {
+        self.timestamp = Utc::now().to_rfc3339();
+        self
+    }
+
+    /// Parse the timestamp as an RFC 3339 date-time
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the timestamp is not valid RFC 3339.
+    pub fn parsed_timestamp(&self) -> Result<DateTime<FixedOffset>> {
+        parse_rfc3339(&self.timestamp)
+    }
+
+    /// Recommendations sorted by priority (most urgent first)
+    ///
+    /// Recommendations sharing a title are collapsed into the most urgent
+    /// one; ties keep their original order.
+    #[must_use]
+    pub fn prioritized_recommendations(&self) -> Vec<&Recommendation> {
+        let mut sorted: Vec<&Recommendation> = self.recommendations.iter().collect();
+        sorted.sort_by_key(|rec| rec.priority);
+
+        let mut seen = HashSet::new();
+        sorted.retain(|rec| seen.insert(rec.title.as_str()));
+        sorted
+    }
+}
+
+/// Parse an RFC 3339 timestamp, naming the offending value on failure
+fn parse_rfc3339(timestamp: &str) -> Result<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(timestamp).map_err(|e| {
+        Error::ValidationError(format!(
+            "invalid report timestamp '{timestamp}': expected RFC 3339 ({e})"
+        ))
+    })
+}
+
+/// Serde hook rejecting report timestamps that are not RFC 3339
+fn deserialize_rfc3339<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let timestamp = String::deserialize(deserializer)?;
+    parse_rfc3339(&timestamp).map_err(serde::de::Error::custom)?;
+    Ok(timestamp)
+}
+
+/// Serde hook accepting recommendations as structs or, as written by older
+/// reports, plain strings (loaded like `Recommendation::from`)
+fn deserialize_recommendations<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Vec<Recommendation>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StoredRecommendation {
+        Legacy(String),
+        Current(Recommendation),
+    }
+
+    Ok(Vec::<StoredRecommendation>::deserialize(deserializer)?
+        .into_iter()
+        .map(|stored| match stored {
+            StoredRecommendation::Legacy(title) => title.into(),
+            StoredRecommendation::Current(recommendation) => recommendation,
+        })
+        .collect())
+}
+
+/// Urgency of a recommendation, most urgent first
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Priority {
+    /// Must be addressed before the next release
+    Critical,
+    /// Should be addressed soon
+    High,
+    /// Worth scheduling (default)
+    #[default]
+    Medium,
+    /// Nice to have
+    Low,
+}
+
+impl Priority {
+    /// Short label used for badges
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Critical => "Critical",
+            Self::High => "High",
+            Self::Medium => "Medium",
+            Self::Low => "Low",
+        }
+    }
+}
+
+/// A single improvement suggestion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recommendation {
+    /// One-line summary, also used to detect duplicates
+    pub title: String,
+    /// Optional longer explanation (may be empty)
+    pub detail: String,
+    /// How urgent the recommendation is
+    pub priority: Priority,
+    /// Area the recommendation belongs to, e.g. "Testing"
+    pub category: String,
+}
+
+impl Recommendation {
+    /// Create a recommendation with no detail in the "General" category
+    pub fn new(title: impl Into<String>, priority: Priority) -> Self {
+        Self {
+            title: title.into(),
+            detail: String::new(),
+            priority,
+            category: "General".to_string(),
+        }
+    }
+
+    /// Set the longer explanation
+    #[must_use]
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    /// Set the category
+    #[must_use]
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = category.into();
+        self
+    }
+}
+
+impl From<String> for Recommendation {
+    fn from(title: String) -> Self {
+        Self::new(title, Priority::default())
+    }
+}
+
+impl From<&str> for Recommendation {
+    fn from(title: &str) -> Self {
+        Self::new(title, Priority::default())
+    }
+}
+
 /// Serializable TDG score data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TdgScoreData {
@@ -121,17 +281,41 @@ pub struct TdgScoreData {
     pub breakdown: HashMap<String, f64>,
 }
 
-impl From<TdgScore> for TdgScoreData {
-    fn from(tdg: TdgScore) -> Self {
+/// Weight of test coverage in the composite score
+const COVERAGE_WEIGHT: f64 = 0.35;
+/// Weight of documentation in the composite score
+const DOCUMENTATION_WEIGHT: f64 = 0.15;
+/// Weight of code complexity in the composite score
+const COMPLEXITY_WEIGHT: f64 = 0.30;
+/// Weight of security in the composite score
+const SECURITY_WEIGHT: f64 = 0.20;
+
+impl TdgScoreData {
+    /// Build a score from measured components (each 0-100)
+    ///
+    /// The composite score is the weighted mean
+    /// `0.35 * coverage + 0.15 * docs + 0.30 * complexity + 0.20 * security`,
+    /// with each component clamped to 0-100. The inputs are kept in `breakdown`.
+    #[must_use]
+    pub fn from_components(coverage: f64, docs: f64, complexity: f64, security: f64) -> Self {
+        let components = [
+            ("Test Coverage", coverage, COVERAGE_WEIGHT),
+            ("Documentation", docs, DOCUMENTATION_WEIGHT),
+            ("Code Complexity", complexity, COMPLEXITY_WEIGHT),
+            ("Security", security, SECURITY_WEIGHT),
+        ];
+
         let mut breakdown = HashMap::new();
-        breakdown.insert("Test Coverage".to_string(), 85.0);
-        breakdown.insert("Documentation".to_string(), 90.0);
-        breakdown.insert("Code Complexity".to_string(), tdg.score);
-        breakdown.insert("Security".to_string(), 95.0);
+        let mut score = 0.0;
+        for (category, value, weight) in components {
+            let value = value.clamp(0.0, 100.0);
+            breakdown.insert(category.to_string(), value);
+            score += value * weight;
+        }
 
         Self {
-            score: tdg.score,
-            grade: tdg.grade.to_string(),
+            score,
+            grade: Grade::from_score(score).to_string(),
             breakdown,
         }
     }
@@ -146,6 +330,8 @@ pub enum ReportFormat {
     Markdown,
     /// HTML format
     Html,
+    /// CSV format
+    Csv,
 }
 
 impl ReportFormat {
@@ -155,6 +341,34 @@ impl ReportFormat {
             Self::Json => "json",
             Self::Markdown => "md",
             Self::Html => "html",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Stylesheet used by HTML reports
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ReportTheme {
+    /// Light background (default)
+    #[default]
+    Light,
+    /// Dark background, for dashboards embedded in dark UIs
+    Dark,
+    /// A complete custom stylesheet
+    Custom(String),
+}
+
+impl ReportTheme {
+    /// CSS emitted inside the report's `<style>` element
+    ///
+    /// A `</` in a custom stylesheet is written as `<\/` so it cannot close
+    /// the `<style>` element early.
+    #[must_use]
+    pub fn css(&self) -> String {
+        match self {
+            Self::Light => REPORT_CSS.to_string(),
+            Self::Dark => REPORT_CSS_DARK.to_string(),
+            Self::Custom(css) => format!("{}\n", css.replace("</", "<\\/")),
         }
     }
 }
@@ -163,6 +377,8 @@ impl ReportFormat {
 pub struct ReportGenerator {
     /// Report format
     format: ReportFormat,
+    /// Stylesheet for HTML output
+    theme: ReportTheme,
     /// Whether to include recommendations
     include_recommendations: bool,
     /// Whether to include detailed metrics
@@ -174,6 +390,7 @@ impl ReportGenerator {
     pub fn new(format: ReportFormat) -> Self {
         Self {
             format,
+            theme: ReportTheme::default(),
             include_recommendations: true,
             include_detailed_metrics: true,
         }
@@ -185,6 +402,13 @@ impl ReportGenerator {
         self
     }
 
+    /// Set the stylesheet used for HTML output
+    #[must_use]
+    pub fn with_theme(mut self, theme: ReportTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     /// Set whether to include detailed metrics
     pub fn with_detailed_metrics(mut self, include: bool) -> Self {
         self.include_detailed_metrics = include;
@@ -197,6 +421,7 @@ impl ReportGenerator {
             ReportFormat::Json => self.generate_json(report),
             ReportFormat::Markdown => self.generate_markdown(report),
             ReportFormat::Html => self.generate_html(report),
+            ReportFormat::Csv => Ok(Self::generate_csv(report)),
         }
     }
 
@@ -257,7 +482,7 @@ impl ReportGenerator {
             let mut langs: Vec<_> = report.metrics.language_distribution.iter().collect();
             langs.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
             for (lang, lines) in langs {
-                let percentage = (*lines as f64 / report.metrics.total_lines as f64) * 100.0;
+                let percentage = percentage_of(*lines, report.metrics.total_lines);
                 md.push_str(&format!(
                     "- **{}:** {} lines ({:.1}%)\n",
                     lang,
@@ -280,8 +505,18 @@ impl ReportGenerator {
         // Recommendations
         if self.include_recommendations && !report.recommendations.is_empty() {
             md.push_str("## 💡 Recommendations\n\n");
-            for (i, rec) in report.recommendations.iter().enumerate() {
-                md.push_str(&format!("{}. {}\n", i + 1, rec));
+            for (i, rec) in report.prioritized_recommendations().iter().enumerate() {
+                let _ = write!(
+                    md,
+                    "{}. **[{}]** {}",
+                    i + 1,
+                    rec.priority.label(),
+                    rec.title
+                );
+                if !rec.detail.is_empty() {
+                    let _ = write!(md, " — {}", rec.detail);
+                }
+                md.push('\n');
             }
             md.push_str("\n");
         }
@@ -300,21 +535,21 @@ impl ReportGenerator {
         );
         html.push_str(&format!(
             "    <title>Analysis Report - {}</title>\n",
-            report.project_name
+            html_escape(&report.project_name)
         ));
         html.push_str("    <style>\n");
-        html.push_str(REPORT_CSS);
+        html.push_str(&self.theme.css());
         html.push_str("    </style>\n");
         html.push_str("</head>\n<body>\n");
 
         // Header
         html.push_str(&format!(
             "    <div class=\"container\">\n        <h1>📊 Analysis Report: {}</h1>\n",
-            report.project_name
+            html_escape(&report.project_name)
         ));
         html.push_str(&format!(
             "        <p class=\"timestamp\">Generated: {}</p>\n\n",
-            report.timestamp
+            html_escape(&report.timestamp)
         ));
 
         // TDG Score Card
@@ -327,7 +562,8 @@ impl ReportGenerator {
         html.push_str("            <h2>Technical Debt Grade</h2>\n");
         html.push_str(&format!(
             "            <div class=\"score {}\">{}</div>\n",
-            grade_class, report.tdg_score.grade
+            grade_class,
+            html_escape(&report.tdg_score.grade)
         ));
         html.push_str(&format!(
             "            <p class=\"score-value\">{:.1}/100</p>\n",
@@ -363,8 +599,18 @@ impl ReportGenerator {
             html.push_str("        <div class=\"recommendations\">\n");
             html.push_str("            <h2>💡 Recommendations</h2>\n");
             html.push_str("            <ol>\n");
-            for rec in &report.recommendations {
-                html.push_str(&format!("                <li>{}</li>\n", rec));
+            for rec in report.prioritized_recommendations() {
+                let _ = write!(
+                    html,
+                    "                <li><span class=\"badge badge-{}\">{}</span> {}",
+                    rec.priority.label().to_lowercase(),
+                    rec.priority.label(),
+                    html_escape(&rec.title)
+                );
+                if !rec.detail.is_empty() {
+                    let _ = write!(html, "<p class=\"detail\">{}</p>", html_escape(&rec.detail));
+                }
+                html.push_str("</li>\n");
             }
             html.push_str("            </ol>\n");
             html.push_str("        </div>\n");
@@ -376,15 +622,253 @@ impl ReportGenerator {
         Ok(html)
     }
 
+    /// Generate CSV report
+    ///
+    /// The language table (`language,lines,percentage`) is followed by a blank
+    /// line and a `metric,value` summary table.
+    fn generate_csv(report: &AnalysisReport) -> String {
+        let mut csv = String::from("language,lines,percentage\n");
+
+        let mut langs: Vec<_> = report.metrics.language_distribution.iter().collect();
+        langs.sort_by(|(a_lang, a_lines), (b_lang, b_lines)| {
+            b_lines.cmp(a_lines).then_with(|| a_lang.cmp(b_lang))
+        });
+        for (lang, lines) in langs {
+            let percentage = percentage_of(*lines, report.metrics.total_lines);
+            let _ = writeln!(csv, "{},{lines},{percentage:.1}", csv_field(lang));
+        }
+
+        csv.push_str("\nmetric,value\n");
+        let _ = writeln!(csv, "total_lines,{}", report.metrics.total_lines);
+        let _ = writeln!(csv, "file_count,{}", report.metrics.file_count);
+        let _ = writeln!(csv, "tdg_score,{:.1}", report.tdg_score.score);
+
+        csv
+    }
+
     /// Write report to file
     pub fn write_to_file(&self, report: &AnalysisReport, output_path: &Path) -> Result<()> {
         let content = self.generate(report)?;
-        fs::write(output_path, content)
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write report: {}", e)))?;
+        fs::write(output_path, content)?;
         Ok(())
     }
 }
 
+/// Line count change for a single language between two reports
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageChange {
+    /// Language name
+    pub language: String,
+    /// Lines in the old report (`None` if the language was absent)
+    pub old_lines: Option<usize>,
+    /// Lines in the new report (`None` if the language was removed)
+    pub new_lines: Option<usize>,
+}
+
+impl LanguageChange {
+    /// Signed change in lines
+    #[must_use]
+    pub fn delta(&self) -> i64 {
+        signed_delta(self.old_lines.unwrap_or(0), self.new_lines.unwrap_or(0))
+    }
+}
+
+/// Differences between two analysis reports
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportDiff {
+    /// Project name of the old report
+    pub old_project: String,
+    /// Project name of the new report
+    pub new_project: String,
+    /// TDG score in the old report
+    pub old_score: f64,
+    /// TDG score in the new report
+    pub new_score: f64,
+    /// Change in TDG score
+    pub score_delta: f64,
+    /// Change in total lines of code
+    pub total_lines_delta: i64,
+    /// Change in file count
+    pub file_count_delta: i64,
+    /// Per-language line changes, sorted by language name
+    pub language_changes: Vec<LanguageChange>,
+    /// Titles of recommendations only present in the new report
+    pub recommendations_added: Vec<String>,
+    /// Titles of recommendations only present in the old report
+    pub recommendations_removed: Vec<String>,
+}
+
+impl ReportDiff {
+    /// Compute the differences from `old` to `new`
+    #[must_use]
+    pub fn between(old: &AnalysisReport, new: &AnalysisReport) -> Self {
+        let old_langs = &old.metrics.language_distribution;
+        let new_langs = &new.metrics.language_distribution;
+
+        let mut languages: Vec<&String> = old_langs.keys().chain(new_langs.keys()).collect();
+        languages.sort();
+        languages.dedup();
+
+        let language_changes = languages
+            .into_iter()
+            .map(|language| LanguageChange {
+                language: language.clone(),
+                old_lines: old_langs.get(language).copied(),
+                new_lines: new_langs.get(language).copied(),
+            })
+            .collect();
+
+        let recommendations_added = recommendation_titles_missing(new, old);
+        let recommendations_removed = recommendation_titles_missing(old, new);
+
+        Self {
+            old_project: old.project_name.clone(),
+            new_project: new.project_name.clone(),
+            old_score: old.tdg_score.score,
+            new_score: new.tdg_score.score,
+            score_delta: new.tdg_score.score - old.tdg_score.score,
+            total_lines_delta: signed_delta(old.metrics.total_lines, new.metrics.total_lines),
+            file_count_delta: signed_delta(old.metrics.file_count, new.metrics.file_count),
+            language_changes,
+            recommendations_added,
+            recommendations_removed,
+        }
+    }
+
+    /// Render the diff as Markdown with ▲/▼ indicators
+    #[must_use]
+    pub fn render_markdown(&self) -> String {
+        let mut md = String::new();
+
+        let _ = writeln!(
+            md,
+            "# Report Diff: {} → {}\n",
+            self.old_project, self.new_project
+        );
+
+        md.push_str("## 📊 Summary\n\n");
+        let _ = writeln!(
+            md,
+            "- **TDG Score:** {:.1} → {:.1} ({} {:+.1})",
+            self.old_score,
+            self.new_score,
+            indicator(self.score_delta),
+            self.score_delta
+        );
+        let _ = writeln!(
+            md,
+            "- **Total Lines:** {} {:+}",
+            indicator(self.total_lines_delta),
+            self.total_lines_delta
+        );
+        let _ = writeln!(
+            md,
+            "- **Files:** {} {:+}\n",
+            indicator(self.file_count_delta),
+            self.file_count_delta
+        );
+
+        if !self.language_changes.is_empty() {
+            md.push_str("## 📈 Language Changes\n\n");
+            for change in &self.language_changes {
+                let delta = change.delta();
+                let detail = match (change.old_lines, change.new_lines) {
+                    (None, Some(lines)) => format!("new, {} lines", format_number(lines)),
+                    (Some(_), None) => "removed".to_string(),
+                    (old, new) => format!(
+                        "{} → {} lines",
+                        format_number(old.unwrap_or(0)),
+                        format_number(new.unwrap_or(0))
+                    ),
+                };
+                let _ = writeln!(
+                    md,
+                    "- **{}:** {detail} ({} {delta:+})",
+                    change.language,
+                    indicator(delta)
+                );
+            }
+            md.push('\n');
+        }
+
+        if !self.recommendations_added.is_empty() || !self.recommendations_removed.is_empty() {
+            md.push_str("## 💡 Recommendations\n\n");
+            for rec in &self.recommendations_added {
+                let _ = writeln!(md, "- ➕ {rec}");
+            }
+            for rec in &self.recommendations_removed {
+                let _ = writeln!(md, "- ➖ {rec}");
+            }
+            md.push('\n');
+        }
+
+        md
+    }
+}
+
+/// Signed difference `new - old`
+#[allow(clippy::cast_possible_wrap)]
+fn signed_delta(old: usize, new: usize) -> i64 {
+    new as i64 - old as i64
+}
+
+/// Direction indicator for a delta: ▲ up, ▼ down, = unchanged
+fn indicator<T: PartialOrd + Default + Copy>(delta: T) -> &'static str {
+    let zero = T::default();
+    if delta > zero {
+        "▲"
+    } else if delta < zero {
+        "▼"
+    } else {
+        "="
+    }
+}
+
+/// Percentage of `total` made up by `lines` (0.0 when `total` is zero)
+#[allow(clippy::cast_precision_loss)]
+fn percentage_of(lines: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (lines as f64 / total as f64) * 100.0
+    }
+}
+
+/// Escape a string for safe interpolation into HTML text or attributes
+fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Titles of recommendations in `report` that `other` does not mention
+fn recommendation_titles_missing(report: &AnalysisReport, other: &AnalysisReport) -> Vec<String> {
+    report
+        .recommendations
+        .iter()
+        .filter(|rec| !other.recommendations.iter().any(|o| o.title == rec.title))
+        .map(|rec| rec.title.clone())
+        .collect()
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 /// Simple CSS for HTML reports (embedded)
 const REPORT_CSS: &str = r#"
 body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif; margin: 0; padding: 20px; background: #f5f5f5; }
@@ -405,6 +889,40 @@ h2 { color: #555; margin-top: 30px; }
 .recommendations { background: #E8F5E9; padding: 20px; border-radius: 8px; margin-top: 20px; }
 .recommendations ol { margin: 0; padding-left: 20px; }
 .recommendations li { margin: 10px 0; color: #2E7D32; }
+.badge { display: inline-block; padding: 2px 8px; border-radius: 4px; font-size: 0.8em; font-weight: bold; color: white; margin-right: 6px; }
+.badge-critical { background: #D32F2F; }
+.badge-high { background: #F57C00; }
+.badge-medium { background: #1976D2; }
+.badge-low { background: #757575; }
+.recommendations .detail { margin: 4px 0 0; color: #666; font-size: 0.9em; }
+"#;
+
+/// Dark-theme counterpart of `REPORT_CSS`
+const REPORT_CSS_DARK: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif; margin: 0; padding: 20px; background: #121212; color: #e0e0e0; }
+.container { max-width: 900px; margin: 0 auto; background: #1e1e1e; padding: 40px; border-radius: 8px; box-shadow: 0 2px 8px rgba(0,0,0,0.6); }
+h1 { color: #f5f5f5; border-bottom: 3px solid #66BB6A; padding-bottom: 10px; }
+h2 { color: #cfcfcf; margin-top: 30px; }
+.timestamp { color: #9e9e9e; font-size: 0.9em; }
+.score-card { text-align: center; padding: 30px; background: linear-gradient(135deg, #3949ab 0%, #4a148c 100%); color: white; border-radius: 8px; margin: 20px 0; }
+.score { font-size: 4em; font-weight: bold; margin: 20px 0; }
+.score-value { font-size: 1.2em; opacity: 0.9; }
+.grade-a { color: #81C784; }
+.grade-b { color: #FFD54F; }
+.grade-c { color: #E57373; }
+.metrics table { width: 100%; border-collapse: collapse; }
+.metrics td { padding: 12px; border-bottom: 1px solid #333; }
+.metrics td:first-child { font-weight: bold; color: #cfcfcf; }
+.metrics td:last-child { text-align: right; color: #e0e0e0; }
+.recommendations { background: #1b3320; padding: 20px; border-radius: 8px; margin-top: 20px; }
+.recommendations ol { margin: 0; padding-left: 20px; }
+.recommendations li { margin: 10px 0; color: #A5D6A7; }
+.badge { display: inline-block; padding: 2px 8px; border-radius: 4px; font-size: 0.8em; font-weight: bold; color: white; margin-right: 6px; }
+.badge-critical { background: #EF5350; }
+.badge-high { background: #FFA726; }
+.badge-medium { background: #42A5F5; }
+.badge-low { background: #9E9E9E; }
+.recommendations .detail { margin: 4px 0 0; color: #BDBDBD; font-size: 0.9em; }
 "#;
 
 // ============================================================================
@@ -430,20 +948,24 @@ fn example_1_json_report() -> Result<()> {
     metrics.complexity_score = 72.5;
     metrics.calculate_averages();
 
-    let tdg_score = TdgScore {
-        score: 87.3,
-        grade: Grade::from_score(87.3),
-    };
+    let tdg_score = TdgScoreData::from_components(82.0, 88.0, 90.0, 91.0);
 
     let report = AnalysisReport {
         project_name: "sample-project".to_string(),
         timestamp: "2025-11-21T10:30:00Z".to_string(),
         metrics,
-        tdg_score: tdg_score.into(),
+        tdg_score,
         recommendations: vec![
-            "Consider increasing test coverage to 90%".to_string(),
-            "Reduce cyclomatic complexity in module 'core'".to_string(),
-            "Add API documentation for public functions".to_string(),
+            Recommendation::new("Consider increasing test coverage to 90%", Priority::High)
+                .with_category("Testing"),
+            Recommendation::new(
+                "Reduce cyclomatic complexity in module 'core'",
+                Priority::Medium,
+            )
+            .with_detail("3 functions exceed a complexity of 15")
+            .with_category("Complexity"),
+            Recommendation::new("Add API documentation for public functions", Priority::Low)
+                .with_category("Documentation"),
         ],
         warnings: vec!["Found 3 TODO comments in codebase".to_string()],
     };
@@ -479,19 +1001,22 @@ fn example_2_markdown_report() -> Result<()> {
     metrics.complexity_score = 65.8;
     metrics.calculate_averages();
 
-    let tdg_score = TdgScore {
-        score: 92.1,
-        grade: Grade::from_score(92.1),
-    };
+    let tdg_score = TdgScoreData::from_components(94.0, 90.0, 91.0, 93.0);
 
     let report = AnalysisReport {
         project_name: "batuta-cookbook".to_string(),
         timestamp: "2025-11-21T10:35:00Z".to_string(),
         metrics,
-        tdg_score: tdg_score.into(),
+        tdg_score,
         recommendations: vec![
-            "Excellent code quality! Maintain current standards".to_string(),
-            "Consider adding performance benchmarks".to_string(),
+            Recommendation::new("Consider adding performance benchmarks", Priority::Medium)
+                .with_category("Performance"),
+            Recommendation::new(
+                "Excellent code quality! Maintain current standards",
+                Priority::Low,
+            ),
+            // Reported twice by different analyzers; rendered once
+            Recommendation::new("Consider adding performance benchmarks", Priority::Low),
         ],
         warnings: vec![],
     };
@@ -527,28 +1052,27 @@ fn example_3_save_reports() -> Result<()> {
     metrics.complexity_score = 78.2;
     metrics.calculate_averages();
 
-    let tdg_score = TdgScore {
-        score: 85.5,
-        grade: Grade::from_score(85.5),
-    };
+    let tdg_score = TdgScoreData::from_components(80.0, 85.0, 88.0, 90.0);
 
     let report = AnalysisReport {
         project_name: "multi-format-demo".to_string(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
+        timestamp: String::new(),
         metrics,
-        tdg_score: tdg_score.into(),
+        tdg_score,
         recommendations: vec![
-            "Add integration tests for API endpoints".to_string(),
-            "Document deployment procedures".to_string(),
+            "Add integration tests for API endpoints".into(),
+            "Document deployment procedures".into(),
         ],
         warnings: vec!["High complexity in module 'parser'".to_string()],
-    };
+    }
+    .with_now();
 
     // Generate all formats
     let formats = vec![
         (ReportFormat::Json, "report.json"),
         (ReportFormat::Markdown, "report.md"),
         (ReportFormat::Html, "report.html"),
+        (ReportFormat::Csv, "report.csv"),
     ];
 
     for (format, filename) in formats {
@@ -568,6 +1092,55 @@ fn example_3_save_reports() -> Result<()> {
     Ok(())
 }
 
+// ============================================================================
+// EXAMPLE 4: Compare Two Reports
+// ============================================================================
+
+fn example_4_compare_reports() {
+    println!("=== Example 4: Compare Two Reports ===\n");
+
+    let mut old_metrics = ProjectMetrics::new();
+    old_metrics.total_lines = 4200;
+    old_metrics.file_count = 35;
+    old_metrics
+        .language_distribution
+        .insert("Rust".to_string(), 3600);
+    old_metrics
+        .language_distribution
+        .insert("Shell".to_string(), 600);
+
+    let mut new_metrics = ProjectMetrics::new();
+    new_metrics.total_lines = 5100;
+    new_metrics.file_count = 41;
+    new_metrics
+        .language_distribution
+        .insert("Rust".to_string(), 4300);
+    new_metrics
+        .language_distribution
+        .insert("Python".to_string(), 800);
+
+    let old_report = AnalysisReport {
+        project_name: "sample-project@v1".to_string(),
+        timestamp: "2025-11-01T10:00:00Z".to_string(),
+        metrics: old_metrics,
+        tdg_score: TdgScoreData::from_components(70.0, 75.0, 82.0, 90.0),
+        recommendations: vec!["Increase test coverage".into()],
+        warnings: vec![],
+    };
+
+    let new_report = AnalysisReport {
+        project_name: "sample-project@v2".to_string(),
+        timestamp: "2025-11-21T10:00:00Z".to_string(),
+        metrics: new_metrics,
+        tdg_score: TdgScoreData::from_components(84.0, 80.0, 85.0, 92.0),
+        recommendations: vec!["Document the Python tooling".into()],
+        warnings: vec![],
+    };
+
+    let diff = ReportDiff::between(&old_report, &new_report);
+    println!("{}", diff.render_markdown());
+}
+
 // ============================================================================
 // MAIN FUNCTION - Run all examples
 // ============================================================================
@@ -580,6 +1153,9 @@ fn main() -> Result<()> {
     println!("{}\n", "=".repeat(70));
 
     example_3_save_reports()?;
+    println!("{}\n", "=".repeat(70));
+
+    example_4_compare_reports();
 
     Ok(())
 }
@@ -612,16 +1188,23 @@ mod tests {
     }
 
     #[test]
-    fn test_tdg_score_conversion() {
-        let tdg = TdgScore {
-            score: 92.5,
-            grade: Grade::A,
-        };
+    fn test_tdg_score_from_components() {
+        let data = TdgScoreData::from_components(80.0, 60.0, 90.0, 100.0);
+
+        let expected = (80.0 * 0.35) + (60.0 * 0.15) + (90.0 * 0.30) + (100.0 * 0.20);
+        assert!((data.score - expected).abs() < 1e-9);
+        assert_eq!(data.grade, Grade::from_score(expected).to_string());
+        assert_eq!(data.breakdown["Test Coverage"], 80.0);
+        assert_eq!(data.breakdown["Documentation"], 60.0);
+        assert_eq!(data.breakdown["Code Complexity"], 90.0);
+        assert_eq!(data.breakdown["Security"], 100.0);
+    }
 
-        let data: TdgScoreData = tdg.into();
-        assert_eq!(data.score, 92.5);
+    #[test]
+    fn test_tdg_score_uniform_components() {
+        let data = TdgScoreData::from_components(92.5, 92.5, 92.5, 92.5);
+        assert!((data.score - 92.5).abs() < 1e-9);
         assert_eq!(data.grade, "A");
-        assert!(!data.breakdown.is_empty());
     }
 
     #[test]
@@ -629,22 +1212,20 @@ mod tests {
         assert_eq!(ReportFormat::Json.extension(), "json");
         assert_eq!(ReportFormat::Markdown.extension(), "md");
         assert_eq!(ReportFormat::Html.extension(), "html");
+        assert_eq!(ReportFormat::Csv.extension(), "csv");
     }
 
     #[test]
     fn test_generate_json_report() {
         let metrics = ProjectMetrics::default();
-        let tdg = TdgScore {
-            score: 85.0,
-            grade: Grade::AMinus,
-        };
+        let tdg = TdgScoreData::from_components(85.0, 85.0, 85.0, 85.0);
 
         let report = AnalysisReport {
             project_name: "test-project".to_string(),
             timestamp: "2025-11-21T00:00:00Z".to_string(),
             metrics,
-            tdg_score: tdg.into(),
-            recommendations: vec!["Test recommendation".to_string()],
+            tdg_score: tdg,
+            recommendations: vec!["Test recommendation".into()],
             warnings: vec![],
         };
 
@@ -663,17 +1244,14 @@ mod tests {
         metrics.file_count = 10;
         metrics.calculate_averages();
 
-        let tdg = TdgScore {
-            score: 90.0,
-            grade: Grade::A,
-        };
+        let tdg = TdgScoreData::from_components(90.0, 90.0, 90.0, 90.0);
 
         let report = AnalysisReport {
             project_name: "markdown-test".to_string(),
             timestamp: "2025-11-21T00:00:00Z".to_string(),
             metrics,
-            tdg_score: tdg.into(),
-            recommendations: vec!["Improve tests".to_string()],
+            tdg_score: tdg,
+            recommendations: vec!["Improve tests".into()],
             warnings: vec!["Warning 1".to_string()],
         };
 
@@ -691,16 +1269,13 @@ mod tests {
     #[test]
     fn test_generate_html_report() {
         let metrics = ProjectMetrics::default();
-        let tdg = TdgScore {
-            score: 75.0,
-            grade: Grade::B,
-        };
+        let tdg = TdgScoreData::from_components(75.0, 75.0, 75.0, 75.0);
 
         let report = AnalysisReport {
             project_name: "html-test".to_string(),
             timestamp: "2025-11-21T00:00:00Z".to_string(),
             metrics,
-            tdg_score: tdg.into(),
+            tdg_score: tdg,
             recommendations: vec![],
             warnings: vec![],
         };
@@ -720,16 +1295,13 @@ mod tests {
         let output_path = temp_dir.path().join("test_report.json");
 
         let metrics = ProjectMetrics::default();
-        let tdg = TdgScore {
-            score: 80.0,
-            grade: Grade::BPlus,
-        };
+        let tdg = TdgScoreData::from_components(80.0, 80.0, 80.0, 80.0);
 
         let report = AnalysisReport {
             project_name: "file-test".to_string(),
             timestamp: "2025-11-21T00:00:00Z".to_string(),
             metrics,
-            tdg_score: tdg.into(),
+            tdg_score: tdg,
             recommendations: vec![],
             warnings: vec![],
         };
@@ -764,16 +1336,13 @@ mod tests {
             .language_distribution
             .insert("Python".to_string(), 300);
 
-        let tdg = TdgScore {
-            score: 85.0,
-            grade: Grade::AMinus,
-        };
+        let tdg = TdgScoreData::from_components(85.0, 85.0, 85.0, 85.0);
 
         let report = AnalysisReport {
             project_name: "lang-dist-test".to_string(),
             timestamp: "2025-11-21T00:00:00Z".to_string(),
             metrics,
-            tdg_score: tdg.into(),
+            tdg_score: tdg,
             recommendations: vec![],
             warnings: vec![],
         };
@@ -784,4 +1353,439 @@ mod tests {
         assert!(md.contains("70.0%")); // Rust percentage
         assert!(md.contains("30.0%")); // Python percentage
     }
+
+    /// Split a generated CSV report into its language and summary tables
+    fn parse_csv_sections(csv: &str) -> (Vec<Vec<String>>, Vec<Vec<String>>) {
+        let parse = |section: &str| -> Vec<Vec<String>> {
+            section
+                .lines()
+                .map(|line| line.split(',').map(str::to_string).collect())
+                .collect()
+        };
+        let (languages, summary) = csv.split_once("\n\n").unwrap();
+        (parse(languages), parse(summary))
+    }
+
+    #[test]
+    fn test_generate_csv_report() {
+        let mut metrics = ProjectMetrics::new();
+        metrics.total_lines = 1000;
+        metrics.file_count = 12;
+        metrics
+            .language_distribution
+            .insert("Rust".to_string(), 700);
+        metrics
+            .language_distribution
+            .insert("Python".to_string(), 200);
+        metrics
+            .language_distribution
+            .insert("Shell".to_string(), 100);
+
+        let report = AnalysisReport {
+            project_name: "csv-test".to_string(),
+            timestamp: "2025-11-21T00:00:00Z".to_string(),
+            metrics,
+            tdg_score: TdgScoreData::from_components(88.0, 88.0, 88.0, 88.0),
+            recommendations: vec![],
+            warnings: vec![],
+        };
+
+        let generator = ReportGenerator::new(ReportFormat::Csv);
+        let csv = generator.generate(&report).unwrap();
+        let (languages, summary) = parse_csv_sections(&csv);
+
+        assert_eq!(languages[0], ["language", "lines", "percentage"]);
+        assert_eq!(languages.len() - 1, 3);
+        assert_eq!(languages[1], ["Rust", "700", "70.0"]);
+        assert_eq!(summary[0], ["metric", "value"]);
+        assert_eq!(summary.len() - 1, 3);
+        assert!(summary.contains(&vec!["file_count".to_string(), "12".to_string()]));
+        assert!(summary.contains(&vec!["tdg_score".to_string(), "88.0".to_string()]));
+    }
+
+    #[test]
+    fn test_generate_csv_report_empty_distribution() {
+        let report = AnalysisReport {
+            project_name: "empty-csv".to_string(),
+            timestamp: "2025-11-21T00:00:00Z".to_string(),
+            metrics: ProjectMetrics::default(),
+            tdg_score: TdgScoreData::from_components(0.0, 0.0, 0.0, 0.0),
+            recommendations: vec![],
+            warnings: vec![],
+        };
+
+        let csv = ReportGenerator::new(ReportFormat::Csv)
+            .generate(&report)
+            .unwrap();
+        let (languages, summary) = parse_csv_sections(&csv);
+
+        assert_eq!(languages, vec![vec!["language", "lines", "percentage"]]);
+        assert_eq!(summary.len(), 4);
+        assert!(summary.iter().all(|row| row.len() == 2));
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("Rust"), "Rust");
+        assert_eq!(csv_field("C, C++"), "\"C, C++\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_markdown_report_empty_metrics_has_no_nan() {
+        let mut metrics = ProjectMetrics::default();
+        metrics.language_distribution.insert("Rust".to_string(), 0);
+
+        let report = AnalysisReport {
+            project_name: "empty-project".to_string(),
+            timestamp: "2025-11-21T00:00:00Z".to_string(),
+            metrics,
+            tdg_score: TdgScoreData::from_components(0.0, 0.0, 0.0, 0.0),
+            recommendations: vec![],
+            warnings: vec![],
+        };
+
+        for format in [
+            ReportFormat::Markdown,
+            ReportFormat::Html,
+            ReportFormat::Csv,
+        ] {
+            let output = ReportGenerator::new(format).generate(&report).unwrap();
+            assert!(!output.contains("NaN"), "{format:?} output contains NaN");
+        }
+
+        let md = ReportGenerator::new(ReportFormat::Markdown)
+            .generate(&report)
+            .unwrap();
+        assert!(md.contains("0.0%"));
+    }
+
+    #[test]
+    fn test_html_report_escapes_user_strings() {
+        let report = AnalysisReport {
+            project_name: "<script>alert('x')</script> & co".to_string(),
+            timestamp: "2025-11-21T00:00:00Z".to_string(),
+            metrics: ProjectMetrics::default(),
+            tdg_score: TdgScoreData::from_components(80.0, 80.0, 80.0, 80.0),
+            recommendations: vec!["Use <b>bold</b> \"quotes\"".into()],
+            warnings: vec![],
+        };
+
+        let html = ReportGenerator::new(ReportFormat::Html)
+            .generate(&report)
+            .unwrap();
+
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; co"));
+        assert!(html.contains("Use &lt;b&gt;bold&lt;/b&gt; &quot;quotes&quot;"));
+
+        let md = ReportGenerator::new(ReportFormat::Markdown)
+            .generate(&report)
+            .unwrap();
+        assert!(md.contains("<script>alert('x')</script> & co"));
+    }
+
+    #[test]
+    fn test_html_report_themes() {
+        let report = AnalysisReport {
+            project_name: "themed".to_string(),
+            timestamp: "2025-11-21T00:00:00Z".to_string(),
+            metrics: ProjectMetrics::default(),
+            tdg_score: TdgScoreData::from_components(80.0, 80.0, 80.0, 80.0),
+            recommendations: vec![],
+            warnings: vec![],
+        };
+        let render = |theme| {
+            ReportGenerator::new(ReportFormat::Html)
+                .with_theme(theme)
+                .generate(&report)
+                .unwrap()
+        };
+
+        let light = render(ReportTheme::Light);
+        assert!(light.contains("background: #f5f5f5"));
+        assert!(!light.contains("background: #121212"));
+        assert_eq!(
+            light,
+            ReportGenerator::new(ReportFormat::Html)
+                .generate(&report)
+                .unwrap()
+        );
+
+        let dark = render(ReportTheme::Dark);
+        assert!(dark.contains("background: #121212"));
+        assert!(!dark.contains("background: #f5f5f5"));
+
+        let custom = render(ReportTheme::Custom(
+            "body { color: red; } </style><script>".to_string(),
+        ));
+        assert!(custom.contains("body { color: red; }"));
+        assert!(!custom.contains("</style><script>"));
+        assert!(!custom.contains("background: #f5f5f5"));
+    }
+
+    #[test]
+    fn test_custom_theme_cannot_close_style_element() {
+        for css in [
+            "a { color: red; }</STYLE><script>alert(1)</script>",
+            "a { color: red; }</StYlE ><img src=x>",
+        ] {
+            let css = ReportTheme::Custom(css.to_string()).css();
+            assert!(!css.to_ascii_lowercase().contains("</style"));
+            assert!(!css.contains("</"));
+        }
+    }
+
+    #[test]
+    fn test_html_escape() {
+        assert_eq!(html_escape("plain"), "plain");
+        assert_eq!(
+            html_escape(r#"<a href="x">'&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_report_diff_score_rise_and_new_language() {
+        let mut old_metrics = ProjectMetrics::new();
+        old_metrics.total_lines = 1000;
+        old_metrics.file_count = 10;
+        old_metrics
+            .language_distribution
+            .insert("Rust".to_string(), 800);
+        old_metrics
+            .language_distribution
+            .insert("Shell".to_string(), 200);
+
+        let mut new_metrics = ProjectMetrics::new();
+        new_metrics.total_lines = 1300;
+        new_metrics.file_count = 12;
+        new_metrics
+            .language_distribution
+            .insert("Rust".to_string(), 900);
+        new_metrics
+            .language_distribution
+            .insert("Shell".to_string(), 100);
+        new_metrics
+            .language_distribution
+            .insert("Go".to_string(), 300);
+
+        let old = AnalysisReport {
+            project_name: "diff-test".to_string(),
+            timestamp: "2025-11-01T00:00:00Z".to_string(),
+            metrics: old_metrics,
+            tdg_score: TdgScoreData::from_components(70.0, 70.0, 70.0, 70.0),
+            recommendations: vec!["Add tests".into(), "Keep going".into()],
+            warnings: vec![],
+        };
+        let new = AnalysisReport {
+            project_name: "diff-test".to_string(),
+            timestamp: "2025-11-21T00:00:00Z".to_string(),
+            metrics: new_metrics,
+            tdg_score: TdgScoreData::from_components(85.0, 85.0, 85.0, 85.0),
+            recommendations: vec!["Keep going".into(), "Document Go API".into()],
+            warnings: vec![],
+        };
+
+        let diff = ReportDiff::between(&old, &new);
+
+        assert!((diff.score_delta - 15.0).abs() < 1e-9);
+        assert_eq!(diff.total_lines_delta, 300);
+        assert_eq!(diff.file_count_delta, 2);
+
+        let go = diff
+            .language_changes
+            .iter()
+            .find(|c| c.language == "Go")
+            .unwrap();
+        assert_eq!(go.old_lines, None);
+        assert_eq!(go.new_lines, Some(300));
+        assert_eq!(go.delta(), 300);
+
+        let shell = diff
+            .language_changes
+            .iter()
+            .find(|c| c.language == "Shell")
+            .unwrap();
+        assert_eq!(shell.delta(), -100);
+
+        assert_eq!(diff.recommendations_added, vec!["Document Go API"]);
+        assert_eq!(diff.recommendations_removed, vec!["Add tests"]);
+
+        let md = diff.render_markdown();
+        assert!(md.contains("70.0 → 85.0 (▲ +15.0)"));
+        assert!(md.contains("**Go:** new, 300 lines (▲ +300)"));
+        assert!(md.contains("**Shell:** 200 → 100 lines (▼ -100)"));
+    }
+
+    #[test]
+    fn test_report_diff_removed_language() {
+        let mut old_metrics = ProjectMetrics::new();
+        old_metrics
+            .language_distribution
+            .insert("Perl".to_string(), 50);
+
+        let old = AnalysisReport {
+            project_name: "old".to_string(),
+            timestamp: "2025-11-01T00:00:00Z".to_string(),
+            metrics: old_metrics,
+            tdg_score: TdgScoreData::from_components(80.0, 80.0, 80.0, 80.0),
+            recommendations: vec![],
+            warnings: vec![],
+        };
+        let new = AnalysisReport {
+            project_name: "new".to_string(),
+            timestamp: "2025-11-21T00:00:00Z".to_string(),
+            metrics: ProjectMetrics::default(),
+            tdg_score: TdgScoreData::from_components(80.0, 80.0, 80.0, 80.0),
+            recommendations: vec![],
+            warnings: vec![],
+        };
+
+        let diff = ReportDiff::between(&old, &new);
+        assert_eq!(diff.language_changes.len(), 1);
+        assert_eq!(diff.language_changes[0].new_lines, None);
+        assert_eq!(diff.language_changes[0].delta(), -50);
+
+        let md = diff.render_markdown();
+        assert!(md.contains("**Perl:** removed (▼ -50)"));
+        assert!(md.contains("(= +0.0)"));
+    }
+
+    fn report_with(recommendations: Vec<Recommendation>) -> AnalysisReport {
+        AnalysisReport {
+            project_name: "recs".to_string(),
+            timestamp: "2025-11-21T00:00:00Z".to_string(),
+            metrics: ProjectMetrics::default(),
+            tdg_score: TdgScoreData::from_components(80.0, 80.0, 80.0, 80.0),
+            recommendations,
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_prioritized_recommendations_order() {
+        let report = report_with(vec![
+            Recommendation::new("Polish docs", Priority::Low),
+            "Split module".into(),
+            Recommendation::new("Fix unsafe block", Priority::Critical),
+            Recommendation::new("Add tests", Priority::High),
+            Recommendation::new("Rename types", Priority::Medium),
+        ]);
+
+        let titles: Vec<&str> = report
+            .prioritized_recommendations()
+            .iter()
+            .map(|rec| rec.title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Fix unsafe block",
+                "Add tests",
+                "Split module",
+                "Rename types",
+                "Polish docs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prioritized_recommendations_dedup_keeps_most_urgent() {
+        let report = report_with(vec![
+            Recommendation::new("Add tests", Priority::Low).with_detail("from linter"),
+            Recommendation::new("Add tests", Priority::High).with_detail("from coverage"),
+            "Add tests".into(),
+        ]);
+
+        let recs = report.prioritized_recommendations();
+        assert_eq!(recs.len(), 1);
+        assert_eq!(recs[0].priority, Priority::High);
+        assert_eq!(recs[0].detail, "from coverage");
+    }
+
+    #[test]
+    fn test_recommendation_from_string() {
+        let rec = Recommendation::from("Add tests".to_string());
+        assert_eq!(rec.title, "Add tests");
+        assert_eq!(rec.priority, Priority::Medium);
+        assert_eq!(rec.category, "General");
+        assert!(rec.detail.is_empty());
+    }
+
+    #[test]
+    fn test_recommendation_badges_rendered() {
+        let report = report_with(vec![
+            Recommendation::new("Add tests", Priority::High).with_detail("Coverage is 40%"),
+            Recommendation::new("Add tests", Priority::Low),
+        ]);
+
+        let md = ReportGenerator::new(ReportFormat::Markdown)
+            .generate(&report)
+            .unwrap();
+        assert!(md.contains("1. **[High]** Add tests — Coverage is 40%\n"));
+        assert!(!md.contains("[Low]"));
+
+        let html = ReportGenerator::new(ReportFormat::Html)
+            .generate(&report)
+            .unwrap();
+        assert!(html.contains(
+            "<li><span class=\"badge badge-high\">High</span> Add tests\
+             <p class=\"detail\">Coverage is 40%</p></li>"
+        ));
+        assert!(!html.contains("badge-low\">"));
+    }
+
+    #[test]
+    fn test_legacy_string_recommendations_load() {
+        let report = report_with(vec![]);
+        let mut json = serde_json::to_value(&report).unwrap();
+        json["recommendations"] = serde_json::json!([
+            "Increase test coverage",
+            {
+                "title": "Add benchmarks",
+                "detail": "",
+                "priority": "High",
+                "category": "Performance"
+            }
+        ]);
+
+        let loaded: AnalysisReport = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            loaded.recommendations,
+            vec![
+                Recommendation::from("Increase test coverage"),
+                Recommendation::new("Add benchmarks", Priority::High).with_category("Performance"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        let report = report_with(vec![]).with_now();
+        let stamped = report.parsed_timestamp().unwrap();
+
+        let json = ReportGenerator::new(ReportFormat::Json)
+            .generate(&report)
+            .unwrap();
+        let loaded: AnalysisReport = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.timestamp, report.timestamp);
+        assert_eq!(loaded.parsed_timestamp().unwrap(), stamped);
+    }
+
+    #[test]
+    fn test_malformed_timestamp_rejected() {
+        let mut report = report_with(vec![]);
+        report.timestamp = "21/11/2025 10:30".to_string();
+
+        let err = report.parsed_timestamp().unwrap_err();
+        assert!(matches!(err, Error::ValidationError(_)));
+
+        let json = serde_json::to_string(&report).unwrap();
+        let err = serde_json::from_str::<AnalysisReport>(&json).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid report timestamp '21/11/2025 10:30'"));
+    }
 }
@@ -18,13 +18,15 @@
 //! - **Aggregate Statistics:** Project-wide metrics across all languages
 //! - **Language Hotspots:** Identify which languages dominate the codebase
 //! - **Quality Scoring:** TDG scores per language and overall
+//! - **Large Trees:** Progress callbacks and parallel file analysis
 //! - **Common Patterns:** Recognize microservices, full-stack, and hybrid architectures
 //!
 //! ## Supported Languages
 //!
 //! - **Rust** (.rs)
 //! - **Python** (.py)
-//! - **JavaScript/TypeScript** (.js, .ts, .jsx, .tsx)
+//! - **JavaScript** (.js, .jsx)
+//! - **TypeScript** (.ts, .tsx)
 //! - **C/C++** (.c, .cpp, .h, .hpp)
 //! - **Go** (.go)
 //! - **Java** (.java)
@@ -46,10 +48,25 @@
 //! ```
 
 use batuta_cookbook::types::{Grade, Language, Result, TdgScore};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use rayon::prelude::*;
 use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
+/// A single analyzed file and its line counts
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileEntry {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    /// Total lines in the file
+    pub lines: usize,
+    /// Comment lines in the file
+    pub comment_lines: usize,
+}
+
 /// Statistics for a single language in the project
 #[derive(Debug, Clone, PartialEq)]
 pub struct LanguageStats {
@@ -70,9 +87,11 @@ pub struct LanguageStats {
     /// Percentage of total project
     pub percentage_of_project: f64,
     /// Files analyzed
-    pub files: Vec<PathBuf>,
+    pub files: Vec<FileEntry>,
     /// TDG score for this language
     pub tdg_score: Option<TdgScore>,
+    /// Files containing interop markers, keyed by the language they call into
+    pub interop_files: HashMap<Language, Vec<PathBuf>>,
 }
 
 impl LanguageStats {
@@ -89,6 +108,31 @@ impl LanguageStats {
             percentage_of_project: 0.0,
             files: Vec::new(),
             tdg_score: None,
+            interop_files: HashMap::new(),
+        }
+    }
+
+    /// Paths of the analyzed files
+    #[must_use]
+    pub fn files_paths(&self) -> Vec<&Path> {
+        self.files.iter().map(|f| f.path.as_path()).collect()
+    }
+
+    /// Add a file, keeping only the `limit` largest files if a limit is set
+    fn retain_file(&mut self, entry: FileEntry, limit: Option<usize>) {
+        self.files.push(entry);
+        if let Some(limit) = limit {
+            if self.files.len() > limit {
+                if let Some(smallest) = self
+                    .files
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, f)| f.lines)
+                    .map(|(i, _)| i)
+                {
+                    self.files.swap_remove(smallest);
+                }
+            }
         }
     }
 
@@ -135,12 +179,163 @@ pub struct MultiLanguageAnalysis {
     pub total_files: usize,
     /// Primary language (most lines of code)
     pub primary_language: Option<Language>,
-    /// Secondary languages
+    /// Secondary languages (above the secondary threshold)
     pub secondary_languages: Vec<Language>,
+    /// Non-primary languages at or below the secondary threshold
+    pub minor_languages: Vec<Language>,
     /// Overall project TDG score
     pub overall_tdg: TdgScore,
     /// Architecture pattern detected
     pub architecture_pattern: ArchitecturePattern,
+    /// Architecture pattern with confidence and reasons
+    pub architecture_classification: ArchitectureClassification,
+    /// Detected cross-language calls, sorted by source then target language
+    pub language_edges: Vec<LanguageEdge>,
+}
+
+/// A detected call from one language into another (FFI, subprocess, ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageEdge {
+    /// Language making the call
+    pub from: Language,
+    /// Language being called
+    pub to: Language,
+    /// Files (relative to the project root) containing the interop markers
+    pub files: Vec<PathBuf>,
+}
+
+impl std::fmt::Display for LanguageEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.from, self.to)
+    }
+}
+
+/// Source-code markers of a call into another language
+const INTEROP_MARKERS: &[(Language, &str, Language)] = &[
+    (Language::Rust, "extern \"C\"", Language::C),
+    (Language::Rust, "process::Command", Language::Shell),
+    (Language::Python, "ctypes", Language::C),
+    (Language::Python, "cffi", Language::C),
+    (Language::Python, "subprocess", Language::Shell),
+    (Language::Python, "os.system(", Language::Shell),
+    (Language::JavaScript, "child_process", Language::Shell),
+    (Language::TypeScript, "child_process", Language::Shell),
+    (Language::Go, "import \"C\"", Language::C),
+    (Language::Go, "os/exec", Language::Shell),
+    (Language::Java, "System.loadLibrary", Language::C),
+    (Language::Java, "ProcessBuilder", Language::Shell),
+    (Language::Ruby, "require 'ffi'", Language::C),
+];
+
+/// Interpreters and toolchains whose invocation from a shell script runs
+/// code in another language
+const SHELL_INVOCATIONS: &[(&str, Language)] = &[
+    ("python", Language::Python),
+    ("python3", Language::Python),
+    ("node", Language::JavaScript),
+    ("npx", Language::JavaScript),
+    ("ts-node", Language::TypeScript),
+    ("cargo", Language::Rust),
+    ("go", Language::Go),
+    ("java", Language::Java),
+    ("ruby", Language::Ruby),
+];
+
+/// Languages a line of `language` code calls into
+fn interop_targets(language: Language, line: &str) -> Vec<Language> {
+    if language == Language::Shell {
+        // First word of each command: `python3 x.py`, `a && node b.js`, `$(cargo run)`
+        return line
+            .split(['|', ';', '&', '(', '`'])
+            .filter_map(|command| command.split_whitespace().next())
+            .filter_map(|program| {
+                SHELL_INVOCATIONS
+                    .iter()
+                    .find(|(name, _)| *name == program)
+                    .map(|(_, target)| *target)
+            })
+            .collect();
+    }
+
+    INTEROP_MARKERS
+        .iter()
+        .filter(|(from, marker, _)| *from == language && line.contains(marker))
+        .map(|(_, _, target)| *target)
+        .collect()
+}
+
+impl MultiLanguageAnalysis {
+    /// Largest files across all languages, sorted by line count (descending)
+    #[must_use]
+    pub fn top_files(&self, n: usize) -> Vec<&FileEntry> {
+        let mut files: Vec<&FileEntry> = self
+            .language_stats
+            .values()
+            .flat_map(|s| s.files.iter())
+            .collect();
+        files.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.path.cmp(&b.path)));
+        files.truncate(n);
+        files
+    }
+
+    /// Render the language breakdown as a Markdown table
+    ///
+    /// Rows are sorted by lines (descending) and followed by a totals row.
+    /// Percentages are 0.0 when the project has no lines.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_markdown_table(&self) -> String {
+        let percent = |lines: usize| {
+            if self.total_lines == 0 {
+                0.0
+            } else {
+                lines as f64 / self.total_lines as f64 * 100.0
+            }
+        };
+        let avg = |lines: usize, files: usize| {
+            if files == 0 {
+                0.0
+            } else {
+                lines as f64 / files as f64
+            }
+        };
+
+        let mut rows: Vec<&LanguageStats> = self.language_stats.values().collect();
+        rows.sort_by(|a, b| {
+            b.lines_of_code
+                .cmp(&a.lines_of_code)
+                .then_with(|| a.language.to_string().cmp(&b.language.to_string()))
+        });
+
+        let mut table = String::from("| Language | Lines | % | Files | Avg/File | TDG Grade |\n");
+        table.push_str("|----------|------:|--:|------:|---------:|-----------|\n");
+        for stats in rows {
+            let grade = stats
+                .tdg_score
+                .map_or_else(|| "-".to_string(), |tdg| tdg.grade.to_string());
+            let _ = writeln!(
+                table,
+                "| {} | {} | {:.1}% | {} | {:.1} | {} |",
+                stats.language,
+                stats.lines_of_code,
+                percent(stats.lines_of_code),
+                stats.file_count,
+                avg(stats.lines_of_code, stats.file_count),
+                grade
+            );
+        }
+        let _ = writeln!(
+            table,
+            "| **Total** | {} | {:.1}% | {} | {:.1} | {} |",
+            self.total_lines,
+            percent(self.total_lines),
+            self.total_files,
+            avg(self.total_lines, self.total_files),
+            self.overall_tdg.grade
+        );
+
+        table
+    }
 }
 
 /// Common architecture patterns in multi-language projects
@@ -170,12 +365,66 @@ impl std::fmt::Display for ArchitecturePattern {
     }
 }
 
+/// Architecture pattern with the evidence behind it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchitectureClassification {
+    /// Detected pattern
+    pub pattern: ArchitecturePattern,
+    /// Confidence in the classification (0.0 - 1.0)
+    pub confidence: f64,
+    /// Human-readable reasons for the classification
+    pub reasons: Vec<String>,
+}
+
+/// Comma-separated language names
+fn join_languages(languages: &[Language]) -> String {
+    languages
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Progress reported by [`MultiLanguageAnalyzer::analyze_with_progress`]
+///
+/// Paths are relative to the project root.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent {
+    /// A directory is being scanned for source files
+    DirectoryScanned {
+        /// Directory path (empty for the project root)
+        path: PathBuf,
+    },
+    /// A source file was read and its lines counted
+    FileAnalyzed {
+        /// File path
+        path: PathBuf,
+        /// Detected language
+        language: Language,
+        /// Total lines in the file
+        lines: usize,
+    },
+}
+
+/// `path` relative to `root`, or `path` itself when it lies outside `root`
+fn relative_to<'a>(path: &'a Path, root: &Path) -> &'a Path {
+    path.strip_prefix(root).unwrap_or(path)
+}
+
 /// Multi-language analyzer
 pub struct MultiLanguageAnalyzer {
     /// Directories to exclude
     exclude_dirs: Vec<String>,
+    /// Glob patterns matched against paths relative to the project root
+    exclude_globs: GlobSet,
     /// Maximum depth to scan
     max_depth: usize,
+    /// Stream files line by line instead of reading them into memory
+    line_counting_only: bool,
+    /// Maximum number of largest files retained per language
+    max_retained_files: Option<usize>,
+    /// Share of the project (percent) a language needs to count as secondary
+    secondary_threshold: f64,
 }
 
 impl MultiLanguageAnalyzer {
@@ -193,7 +442,11 @@ impl MultiLanguageAnalyzer {
                 ".venv".to_string(),
                 "__pycache__".to_string(),
             ],
+            exclude_globs: GlobSet::empty(),
             max_depth: 10,
+            line_counting_only: false,
+            max_retained_files: None,
+            secondary_threshold: 5.0,
         }
     }
 
@@ -203,27 +456,151 @@ impl MultiLanguageAnalyzer {
         self
     }
 
+    /// Set glob patterns (e.g. `**/fixtures/**`) for paths to exclude
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any pattern is not a valid glob.
+    pub fn with_exclude_globs(mut self, patterns: Vec<String>) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(&pattern).map_err(|e| {
+                batuta_cookbook::Error::Analysis(format!("Invalid exclude glob '{pattern}': {e}"))
+            })?;
+            builder.add(glob);
+        }
+        self.exclude_globs = builder.build().map_err(|e| {
+            batuta_cookbook::Error::Analysis(format!("Failed to build exclude globs: {e}"))
+        })?;
+        Ok(self)
+    }
+
     /// Set maximum scan depth
     pub fn with_max_depth(mut self, depth: usize) -> Self {
         self.max_depth = depth;
         self
     }
 
+    /// Stream files with a buffered reader, counting lines without retaining content
+    #[must_use]
+    pub fn with_line_counting_only(mut self, enabled: bool) -> Self {
+        self.line_counting_only = enabled;
+        self
+    }
+
+    /// Retain only the `limit` largest files per language in `LanguageStats.files`
+    #[must_use]
+    pub fn with_max_retained_files(mut self, limit: usize) -> Self {
+        self.max_retained_files = Some(limit);
+        self
+    }
+
+    /// Share of the project (percent, default 5.0) a non-primary language
+    /// must exceed to be listed as secondary rather than minor
+    #[must_use]
+    pub fn with_secondary_threshold(mut self, percent: f64) -> Self {
+        self.secondary_threshold = percent;
+        self
+    }
+
     /// Analyze a multi-language project
     pub fn analyze(&self, project_path: &Path) -> Result<MultiLanguageAnalysis> {
+        self.analyze_with_progress(project_path, |_| {})
+    }
+
+    /// Analyze a project, reporting each scanned directory and analyzed file
+    ///
+    /// `on_progress` is called once per directory as it is scanned and once
+    /// per source file after it is analyzed, in scan order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory or file cannot be read.
+    pub fn analyze_with_progress(
+        &self,
+        project_path: &Path,
+        on_progress: impl Fn(ProgressEvent),
+    ) -> Result<MultiLanguageAnalysis> {
+        let mut sources = Vec::new();
+        self.collect_sources(project_path, project_path, 0, &mut sources, &on_progress)?;
+
+        let file_stats = sources
+            .iter()
+            .map(|(path, language)| {
+                let stats = self.analyze_source(path, *language)?;
+                on_progress(ProgressEvent::FileAnalyzed {
+                    path: relative_to(path, project_path).to_path_buf(),
+                    language: *language,
+                    lines: stats.lines,
+                });
+                Ok(stats)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(self.aggregate(project_path, &sources, file_stats))
+    }
+
+    /// Analyze a project, reading and counting files on rayon's thread pool
+    ///
+    /// Files are aggregated in scan order, so the result is identical to
+    /// [`MultiLanguageAnalyzer::analyze`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory or file cannot be read.
+    pub fn analyze_parallel(&self, project_path: &Path) -> Result<MultiLanguageAnalysis> {
+        let mut sources = Vec::new();
+        self.collect_sources(project_path, project_path, 0, &mut sources, &|_| {})?;
+
+        let file_stats = sources
+            .par_iter()
+            .map(|(path, language)| self.analyze_source(path, *language))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(self.aggregate(project_path, &sources, file_stats))
+    }
+
+    /// Combine per-file statistics (in the same order as `sources`) into the
+    /// project analysis
+    fn aggregate(
+        &self,
+        project_path: &Path,
+        sources: &[(PathBuf, Language)],
+        file_stats: Vec<FileStats>,
+    ) -> MultiLanguageAnalysis {
         let mut language_stats: HashMap<Language, LanguageStats> = HashMap::new();
         let mut total_lines = 0;
         let mut total_files = 0;
 
-        // Scan directory and collect stats
-        self.scan_directory(
-            project_path,
-            project_path,
-            0,
-            &mut language_stats,
-            &mut total_lines,
-            &mut total_files,
-        )?;
+        for ((path, language), file_stats) in sources.iter().zip(file_stats) {
+            let relative_path = relative_to(path, project_path);
+            let stats = language_stats
+                .entry(*language)
+                .or_insert_with(|| LanguageStats::new(*language));
+
+            stats.lines_of_code += file_stats.lines;
+            stats.blank_lines += file_stats.blank_lines;
+            stats.comment_lines += file_stats.comment_lines;
+            stats.file_count += 1;
+            for target in &file_stats.interop_targets {
+                stats
+                    .interop_files
+                    .entry(*target)
+                    .or_default()
+                    .push(relative_path.to_path_buf());
+            }
+            stats.retain_file(
+                FileEntry {
+                    path: relative_path.to_path_buf(),
+                    lines: file_stats.lines,
+                    comment_lines: file_stats.comment_lines,
+                },
+                self.max_retained_files,
+            );
+
+            total_lines += file_stats.lines;
+            total_files += 1;
+        }
 
         // Calculate metrics for each language
         for stats in language_stats.values_mut() {
@@ -235,40 +612,59 @@ impl MultiLanguageAnalyzer {
         sorted_langs.sort_by(|a, b| b.lines_of_code.cmp(&a.lines_of_code));
 
         let primary_language = sorted_langs.first().map(|s| s.language);
-        let secondary_languages: Vec<Language> = sorted_langs
+        let (secondary, minor): (Vec<&LanguageStats>, Vec<&LanguageStats>) = sorted_langs
             .iter()
             .skip(1)
-            .filter(|s| s.percentage_of_project > 5.0) // Only significant languages
-            .map(|s| s.language)
-            .collect();
+            .partition(|s| s.percentage_of_project > self.secondary_threshold);
+        let secondary_languages: Vec<Language> = secondary.iter().map(|s| s.language).collect();
+        let minor_languages: Vec<Language> = minor.iter().map(|s| s.language).collect();
 
         // Detect architecture pattern
-        let architecture_pattern = Self::detect_architecture_pattern(&language_stats);
+        let architecture_classification = Self::classify_architecture(&language_stats);
+        let architecture_pattern = architecture_classification.pattern.clone();
 
         // Calculate overall TDG score
         let overall_tdg = Self::calculate_overall_tdg(&language_stats);
 
-        Ok(MultiLanguageAnalysis {
+        let mut language_edges: Vec<LanguageEdge> = language_stats
+            .values()
+            .flat_map(|stats| {
+                stats.interop_files.iter().map(|(to, files)| {
+                    let mut files = files.clone();
+                    files.sort();
+                    LanguageEdge {
+                        from: stats.language,
+                        to: *to,
+                        files,
+                    }
+                })
+            })
+            .collect();
+        language_edges.sort_by_key(|edge| (edge.from.to_string(), edge.to.to_string()));
+
+        MultiLanguageAnalysis {
             project_path: project_path.to_path_buf(),
             language_stats,
             total_lines,
             total_files,
             primary_language,
             secondary_languages,
+            minor_languages,
             overall_tdg,
             architecture_pattern,
-        })
+            architecture_classification,
+            language_edges,
+        }
     }
 
-    /// Scan directory recursively
-    fn scan_directory(
+    /// Scan directory recursively, collecting source files and their language
+    fn collect_sources(
         &self,
         current_path: &Path,
         root_path: &Path,
         depth: usize,
-        language_stats: &mut HashMap<Language, LanguageStats>,
-        total_lines: &mut usize,
-        total_files: &mut usize,
+        sources: &mut Vec<(PathBuf, Language)>,
+        on_progress: &dyn Fn(ProgressEvent),
     ) -> Result<()> {
         if depth > self.max_depth {
             return Ok(());
@@ -281,12 +677,20 @@ impl MultiLanguageAnalyzer {
                 e
             ))
         })?;
+        on_progress(ProgressEvent::DirectoryScanned {
+            path: relative_to(current_path, root_path).to_path_buf(),
+        });
 
         for entry in entries {
             let entry = entry.map_err(|e| {
                 batuta_cookbook::Error::Analysis(format!("Failed to read entry: {}", e))
             })?;
             let path = entry.path();
+            let relative_path = relative_to(&path, root_path);
+
+            if self.exclude_globs.is_match(relative_path) {
+                continue;
+            }
 
             if path.is_dir() {
                 // Check if directory should be excluded
@@ -300,33 +704,11 @@ impl MultiLanguageAnalyzer {
                 }
 
                 // Recurse into subdirectory
-                self.scan_directory(
-                    &path,
-                    root_path,
-                    depth + 1,
-                    language_stats,
-                    total_lines,
-                    total_files,
-                )?;
+                self.collect_sources(&path, root_path, depth + 1, sources, on_progress)?;
             } else if path.is_file() {
-                // Analyze file
                 if let Some(language) = Self::detect_language(&path) {
                     if language != Language::Unknown {
-                        let file_stats = self.analyze_file(&path)?;
-                        let relative_path = path.strip_prefix(root_path).unwrap_or(&path);
-
-                        let stats = language_stats
-                            .entry(language)
-                            .or_insert_with(|| LanguageStats::new(language));
-
-                        stats.lines_of_code += file_stats.lines;
-                        stats.blank_lines += file_stats.blank_lines;
-                        stats.comment_lines += file_stats.comment_lines;
-                        stats.file_count += 1;
-                        stats.files.push(relative_path.to_path_buf());
-
-                        *total_lines += file_stats.lines;
-                        *total_files += 1;
+                        sources.push((path, language));
                     }
                 }
             }
@@ -335,24 +717,23 @@ impl MultiLanguageAnalyzer {
         Ok(())
     }
 
+    /// Count the lines of one source file, honoring `line_counting_only`
+    fn analyze_source(&self, path: &Path, language: Language) -> Result<FileStats> {
+        if self.line_counting_only {
+            Self::analyze_file_streaming(path, language)
+        } else {
+            self.analyze_file(path, language)
+        }
+    }
+
     /// Detect language from file extension
     fn detect_language(path: &Path) -> Option<Language> {
-        path.extension().and_then(|ext| {
-            let ext = ext.to_str()?;
-            match ext {
-                "rs" => Some(Language::Rust),
-                "py" | "pyw" => Some(Language::Python),
-                "js" | "jsx" | "ts" | "tsx" => Some(Language::JavaScript),
-                "c" | "h" => Some(Language::C),
-                "cpp" | "cc" | "cxx" | "hpp" | "hxx" => Some(Language::Cpp),
-                "sh" | "bash" => Some(Language::Shell),
-                _ => Some(Language::Unknown),
-            }
-        })
+        let ext = path.extension()?.to_str()?;
+        Some(Language::from_extension(ext).unwrap_or(Language::Unknown))
     }
 
-    /// Analyze a single file
-    fn analyze_file(&self, path: &Path) -> Result<FileStats> {
+    /// Analyze a single file using the comment syntax of `language`
+    fn analyze_file(&self, path: &Path, language: Language) -> Result<FileStats> {
         let content = fs::read_to_string(path).map_err(|e| {
             batuta_cookbook::Error::Analysis(format!(
                 "Failed to read file {}: {}",
@@ -361,36 +742,51 @@ impl MultiLanguageAnalyzer {
             ))
         })?;
 
-        let lines: Vec<&str> = content.lines().collect();
-        let total_lines = lines.len();
-        let mut blank_lines = 0;
-        let mut comment_lines = 0;
-
-        for line in &lines {
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                blank_lines += 1;
-            } else if trimmed.starts_with("//")
-                || trimmed.starts_with('#')
-                || trimmed.starts_with("/*")
-            {
-                comment_lines += 1;
-            }
+        let mut counter = LineCounter::new(language);
+        for line in content.lines() {
+            counter.count(line);
+        }
+
+        Ok(counter.stats)
+    }
+
+    /// Analyze a single file line by line, holding at most one line in memory
+    fn analyze_file_streaming(path: &Path, language: Language) -> Result<FileStats> {
+        let read_error = |e: std::io::Error| {
+            batuta_cookbook::Error::Analysis(format!("Failed to read file {}: {e}", path.display()))
+        };
+
+        let mut reader = BufReader::new(fs::File::open(path).map_err(read_error)?);
+        let mut counter = LineCounter::new(language);
+        let mut buffer = String::new();
+
+        while reader.read_line(&mut buffer).map_err(read_error)? > 0 {
+            // Match `str::lines`: strip `\n` and a preceding `\r`
+            let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            counter.count(line);
+            buffer.clear();
         }
 
-        Ok(FileStats {
-            lines: total_lines,
-            blank_lines,
-            comment_lines,
-        })
+        Ok(counter.stats)
     }
 
-    /// Detect architecture pattern
-    fn detect_architecture_pattern(
+    /// Classify the architecture pattern with a confidence score
+    ///
+    /// Confidence is the normalized distance of the project from the
+    /// thresholds that decided the classification.
+    fn classify_architecture(
         language_stats: &HashMap<Language, LanguageStats>,
-    ) -> ArchitecturePattern {
+    ) -> ArchitectureClassification {
+        const MONOLINGUAL_THRESHOLD: f64 = 80.0;
+        const SCRIPTING_THRESHOLD: f64 = 20.0;
+
         if language_stats.is_empty() {
-            return ArchitecturePattern::Monolingual;
+            return ArchitectureClassification {
+                pattern: ArchitecturePattern::Monolingual,
+                confidence: 1.0,
+                reasons: vec!["No source files found".to_string()],
+            };
         }
 
         let mut sorted: Vec<_> = language_stats.values().collect();
@@ -400,38 +796,98 @@ impl MultiLanguageAnalyzer {
                 .unwrap()
         });
 
+        let primary = sorted[0];
+        let primary_reason = format!(
+            "{} accounts for {:.1}% of lines (monolingual threshold {MONOLINGUAL_THRESHOLD:.0}%)",
+            primary.language, primary.percentage_of_project
+        );
+
         // Single language dominates
-        if let Some(primary) = sorted.first() {
-            if primary.percentage_of_project > 80.0 {
-                return ArchitecturePattern::Monolingual;
-            }
+        if primary.percentage_of_project > MONOLINGUAL_THRESHOLD {
+            return ArchitectureClassification {
+                pattern: ArchitecturePattern::Monolingual,
+                confidence: (primary.percentage_of_project - MONOLINGUAL_THRESHOLD)
+                    / (100.0 - MONOLINGUAL_THRESHOLD),
+                reasons: vec![primary_reason],
+            };
         }
 
-        // Check for full-stack pattern (JavaScript + backend language)
-        let has_javascript = language_stats.contains_key(&Language::JavaScript);
-        let has_backend = language_stats.contains_key(&Language::Rust)
-            || language_stats.contains_key(&Language::Python);
+        // How far the primary language is from making the project monolingual
+        let spread_margin =
+            (MONOLINGUAL_THRESHOLD - primary.percentage_of_project) / MONOLINGUAL_THRESHOLD;
 
-        if has_javascript && has_backend && language_stats.len() <= 3 {
-            return ArchitecturePattern::FullStack;
+        // Check for full-stack pattern (JavaScript/TypeScript + backend language)
+        let frontend: Vec<_> = [Language::JavaScript, Language::TypeScript]
+            .into_iter()
+            .filter(|lang| language_stats.contains_key(lang))
+            .collect();
+        let backend: Vec<_> = [
+            Language::Rust,
+            Language::Python,
+            Language::Go,
+            Language::Java,
+            Language::Ruby,
+        ]
+        .into_iter()
+        .filter(|lang| language_stats.contains_key(lang))
+        .collect();
+
+        if !frontend.is_empty() && !backend.is_empty() && language_stats.len() <= 3 {
+            return ArchitectureClassification {
+                pattern: ArchitecturePattern::FullStack,
+                confidence: spread_margin,
+                reasons: vec![
+                    format!(
+                        "Frontend ({}) and backend ({}) languages present",
+                        join_languages(&frontend),
+                        join_languages(&backend)
+                    ),
+                    primary_reason,
+                ],
+            };
         }
 
         // Check for scripting support (shell + primary language)
-        let has_shell = language_stats.contains_key(&Language::Shell);
-        if has_shell {
-            if let Some(shell_stats) = language_stats.get(&Language::Shell) {
-                if shell_stats.percentage_of_project < 20.0 && language_stats.len() <= 3 {
-                    return ArchitecturePattern::ScriptingSupport;
-                }
+        if let Some(shell_stats) = language_stats.get(&Language::Shell) {
+            if shell_stats.percentage_of_project < SCRIPTING_THRESHOLD && language_stats.len() <= 3
+            {
+                let shell_margin =
+                    (SCRIPTING_THRESHOLD - shell_stats.percentage_of_project) / SCRIPTING_THRESHOLD;
+                return ArchitectureClassification {
+                    pattern: ArchitecturePattern::ScriptingSupport,
+                    confidence: spread_margin.min(shell_margin),
+                    reasons: vec![
+                        format!(
+                            "Shell accounts for {:.1}% of lines (scripting threshold {SCRIPTING_THRESHOLD:.0}%)",
+                            shell_stats.percentage_of_project
+                        ),
+                        primary_reason,
+                    ],
+                };
             }
         }
 
         // Many languages with significant contributions
         if language_stats.len() >= 4 {
-            return ArchitecturePattern::Microservices;
+            return ArchitectureClassification {
+                pattern: ArchitecturePattern::Microservices,
+                confidence: spread_margin,
+                reasons: vec![
+                    format!("{} languages detected", language_stats.len()),
+                    primary_reason,
+                ],
+            };
         }
 
-        ArchitecturePattern::Polyglot
+        // Fallback: no positive signal, so confidence is capped at one half
+        ArchitectureClassification {
+            pattern: ArchitecturePattern::Polyglot,
+            confidence: spread_margin * 0.5,
+            reasons: vec![
+                "No frontend/backend, scripting, or service split detected".to_string(),
+                primary_reason,
+            ],
+        }
     }
 
     /// Calculate overall TDG score
@@ -443,21 +899,23 @@ impl MultiLanguageAnalyzer {
             };
         }
 
-        // Weighted average based on lines of code
-        let total_lines: usize = language_stats.values().map(|s| s.lines_of_code).sum();
-        let mut weighted_score = 0.0;
-
-        for stats in language_stats.values() {
-            if let Some(tdg) = &stats.tdg_score {
-                let weight = stats.lines_of_code as f64 / total_lines as f64;
-                weighted_score += tdg.score * weight;
-            }
-        }
+        // Weighted average based on lines of code; languages without a
+        // score count as zero so they still dilute the overall score
+        let unscored = TdgScore {
+            score: 0.0,
+            grade: Grade::F,
+        };
+        let weighted: Vec<(TdgScore, f64)> = language_stats
+            .values()
+            .map(|stats| {
+                (
+                    stats.tdg_score.unwrap_or(unscored),
+                    stats.lines_of_code as f64,
+                )
+            })
+            .collect();
 
-        TdgScore {
-            score: weighted_score,
-            grade: Grade::from_score(weighted_score),
-        }
+        TdgScore::weighted_average(&weighted)
     }
 }
 
@@ -473,6 +931,145 @@ struct FileStats {
     lines: usize,
     blank_lines: usize,
     comment_lines: usize,
+    /// Other languages this file calls into (deduplicated)
+    interop_targets: Vec<Language>,
+}
+
+/// Accumulates file statistics one line at a time
+struct LineCounter {
+    stats: FileStats,
+    language: Language,
+    syntax: CommentSyntax,
+    in_block_comment: bool,
+}
+
+impl LineCounter {
+    /// Create a counter using the comment syntax of `language`
+    fn new(language: Language) -> Self {
+        Self {
+            stats: FileStats {
+                lines: 0,
+                blank_lines: 0,
+                comment_lines: 0,
+                interop_targets: Vec::new(),
+            },
+            language,
+            syntax: CommentSyntax::for_language(language),
+            in_block_comment: false,
+        }
+    }
+
+    /// Record a single line (without its line terminator)
+    fn count(&mut self, line: &str) {
+        self.stats.lines += 1;
+        if line.trim().is_empty() {
+            self.stats.blank_lines += 1;
+        } else if self
+            .syntax
+            .is_comment_line(line, &mut self.in_block_comment)
+        {
+            self.stats.comment_lines += 1;
+        } else {
+            for target in interop_targets(self.language, line) {
+                if target != self.language && !self.stats.interop_targets.contains(&target) {
+                    self.stats.interop_targets.push(target);
+                }
+            }
+        }
+    }
+}
+
+/// Comment markers used by a language
+#[derive(Debug, Clone, Copy)]
+struct CommentSyntax {
+    /// Line comment marker (e.g. `//` or `#`)
+    line: Option<&'static str>,
+    /// Block comment delimiters (e.g. `/*` and `*/`)
+    block: Option<(&'static str, &'static str)>,
+    /// Characters that open and close string literals
+    quotes: &'static [char],
+}
+
+impl CommentSyntax {
+    /// Comment syntax for a language
+    fn for_language(language: Language) -> Self {
+        match language {
+            Language::Rust
+            | Language::C
+            | Language::Cpp
+            | Language::JavaScript
+            | Language::TypeScript
+            | Language::Go
+            | Language::Java => Self {
+                line: Some("//"),
+                block: Some(("/*", "*/")),
+                quotes: &['"'],
+            },
+            Language::Python | Language::Shell | Language::Ruby => Self {
+                line: Some("#"),
+                block: None,
+                quotes: &['"', '\''],
+            },
+            Language::Unknown => Self {
+                line: None,
+                block: None,
+                quotes: &[],
+            },
+        }
+    }
+
+    /// Whether a line holds only comments, carrying block comment state across lines
+    fn is_comment_line(&self, line: &str, in_block_comment: &mut bool) -> bool {
+        let mut has_code = false;
+        let mut has_comment = false;
+        let mut open_quote: Option<char> = None;
+        let mut rest = line.trim();
+
+        while let Some(c) = rest.chars().next() {
+            if *in_block_comment {
+                has_comment = true;
+                let close = self.block.map_or("", |(_, close)| close);
+                match rest.find(close) {
+                    Some(index) => {
+                        *in_block_comment = false;
+                        rest = &rest[index + close.len()..];
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
+            if let Some(quote) = open_quote {
+                if c == '\\' {
+                    rest = &rest[c.len_utf8()..];
+                    if let Some(escaped) = rest.chars().next() {
+                        rest = &rest[escaped.len_utf8()..];
+                    }
+                    continue;
+                }
+                if c == quote {
+                    open_quote = None;
+                }
+            } else if self.line.is_some_and(|marker| rest.starts_with(marker)) {
+                has_comment = true;
+                break;
+            } else if let Some((open, _)) = self.block.filter(|(open, _)| rest.starts_with(open)) {
+                has_comment = true;
+                *in_block_comment = true;
+                rest = &rest[open.len()..];
+                continue;
+            } else if !c.is_whitespace() {
+                has_code = true;
+                if self.quotes.contains(&c) {
+                    open_quote = Some(c);
+                }
+            }
+
+            rest = &rest[c.len_utf8()..];
+        }
+
+        has_comment && !has_code
+    }
 }
 
 // ============================================================================
@@ -483,12 +1080,25 @@ fn example_1_analyze_current_project() -> Result<()> {
     println!("=== Example 1: Analyze Current Project (Polyglot) ===\n");
 
     let analyzer = MultiLanguageAnalyzer::new();
-    let analysis = analyzer.analyze(Path::new("."))?;
+    let directories = std::cell::Cell::new(0);
+    let analysis = analyzer.analyze_with_progress(Path::new("."), |event| {
+        if let ProgressEvent::DirectoryScanned { .. } = event {
+            directories.set(directories.get() + 1);
+        }
+    })?;
 
     println!("📊 Project Analysis: {}", analysis.project_path.display());
+    println!("Directories Scanned: {}", directories.get());
     println!("Total Lines: {}", analysis.total_lines);
     println!("Total Files: {}", analysis.total_files);
-    println!("Architecture: {}", analysis.architecture_pattern);
+    println!(
+        "Architecture: {} (confidence {:.0}%)",
+        analysis.architecture_pattern,
+        analysis.architecture_classification.confidence * 100.0
+    );
+    for reason in &analysis.architecture_classification.reasons {
+        println!("  - {reason}");
+    }
     println!(
         "Overall TDG: {} ({})",
         analysis.overall_tdg.score, analysis.overall_tdg.grade
@@ -505,20 +1115,23 @@ fn example_1_analyze_current_project() -> Result<()> {
         }
     }
 
-    println!("\n📈 Language Breakdown:");
-    let mut sorted: Vec<_> = analysis.language_stats.values().collect();
-    sorted.sort_by(|a, b| b.lines_of_code.cmp(&a.lines_of_code));
-
-    for stats in sorted {
+    if !analysis.minor_languages.is_empty() {
         println!(
-            "  {} - {} lines ({:.1}%) in {} files",
-            stats.language, stats.lines_of_code, stats.percentage_of_project, stats.file_count
+            "\n🔹 Minor Languages: {}",
+            join_languages(&analysis.minor_languages)
         );
-        if let Some(tdg) = &stats.tdg_score {
-            println!("    TDG: {} ({})", tdg.score, tdg.grade);
+    }
+
+    if !analysis.language_edges.is_empty() {
+        println!("\n🔗 Cross-Language Calls:");
+        for edge in &analysis.language_edges {
+            println!("  - {edge} ({} files)", edge.files.len());
         }
     }
 
+    println!("\n📈 Language Breakdown:\n");
+    print!("{}", analysis.to_markdown_table());
+
     Ok(())
 }
 
@@ -588,18 +1201,24 @@ fn example_3_identify_hotspots() -> Result<()> {
             );
             println!("  Avg Lines per File: {:.0}", stats.avg_lines_per_file);
 
-            // Show top 3 files if available
-            let file_count = stats.files.len().min(3);
-            if file_count > 0 {
-                println!("  Sample Files:");
-                for file in stats.files.iter().take(file_count) {
-                    println!("    - {}", file.display());
+            // Show the 3 largest files
+            let mut largest: Vec<_> = stats.files.iter().collect();
+            largest.sort_by_key(|f| std::cmp::Reverse(f.lines));
+            if !largest.is_empty() {
+                println!("  Largest Files:");
+                for file in largest.iter().take(3) {
+                    println!("    - {} ({} lines)", file.path.display(), file.lines);
                 }
             }
             println!();
         }
     }
 
+    println!("📏 Largest Files Overall:");
+    for file in analysis.top_files(5) {
+        println!("  - {} ({} lines)", file.path.display(), file.lines);
+    }
+
     Ok(())
 }
 
@@ -658,6 +1277,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_language_detection_extended() {
+        let cases = [
+            ("main.go", Language::Go),
+            ("App.java", Language::Java),
+            ("server.rb", Language::Ruby),
+            ("index.ts", Language::TypeScript),
+            ("view.tsx", Language::TypeScript),
+            ("view.jsx", Language::JavaScript),
+        ];
+
+        for (file, expected) in cases {
+            assert_eq!(
+                MultiLanguageAnalyzer::detect_language(Path::new(file)),
+                Some(expected),
+                "{file}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_go_and_java_project() {
+        let temp_dir = create_test_project(vec![
+            (
+                "gateway/main.go",
+                "package main
+
+func main() {}
+",
+            ),
+            (
+                "billing/App.java",
+                "public class App {
+    public static void main(String[] args) {}
+}
+",
+            ),
+        ]);
+
+        let analyzer = MultiLanguageAnalyzer::new();
+        let analysis = analyzer.analyze(temp_dir.path()).unwrap();
+
+        assert_eq!(analysis.language_stats.len(), 2);
+        assert!(analysis.language_stats.contains_key(&Language::Go));
+        assert!(analysis.language_stats.contains_key(&Language::Java));
+    }
+
+    #[test]
+    fn test_architecture_detection_typescript_fullstack() {
+        let mut stats_map = HashMap::new();
+
+        let mut go_stats = LanguageStats::new(Language::Go);
+        go_stats.percentage_of_project = 55.0;
+        stats_map.insert(Language::Go, go_stats);
+
+        let mut ts_stats = LanguageStats::new(Language::TypeScript);
+        ts_stats.percentage_of_project = 45.0;
+        stats_map.insert(Language::TypeScript, ts_stats);
+
+        let pattern = MultiLanguageAnalyzer::classify_architecture(&stats_map).pattern;
+        assert_eq!(pattern, ArchitecturePattern::FullStack);
+    }
+
     #[test]
     fn test_single_language_project() {
         let temp_dir = create_test_project(vec![
@@ -677,6 +1359,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_markdown_table() {
+        let temp_dir = create_test_project(vec![
+            ("src/main.rs", "fn main() {}\n"),
+            ("app/a.py", "import os\nx = 1\ny = 2\n"),
+            ("app/b.py", "print(1)\nprint(2)\n"),
+        ]);
+        let analysis = MultiLanguageAnalyzer::new()
+            .analyze(temp_dir.path())
+            .unwrap();
+
+        let table = analysis.to_markdown_table();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "| Language | Lines | % | Files | Avg/File | TDG Grade |"
+        );
+        assert!(lines[1].starts_with("|---"));
+        assert_eq!(lines.len(), 2 + analysis.language_stats.len() + 1);
+        assert!(lines[2].starts_with("| Python | "));
+        assert!(lines[3].starts_with("| Rust | "));
+        assert!(lines[4].starts_with(&format!(
+            "| **Total** | {} | 100.0% | 3 |",
+            analysis.total_lines
+        )));
+    }
+
+    #[test]
+    fn test_markdown_table_empty_project() {
+        let temp_dir = create_test_project(vec![("src/main.rs", "fn main() {}\n")]);
+        let mut analysis = MultiLanguageAnalyzer::new()
+            .analyze(temp_dir.path())
+            .unwrap();
+        analysis.language_stats.clear();
+        analysis.total_lines = 0;
+        analysis.total_files = 0;
+
+        let table = analysis.to_markdown_table();
+        assert!(table.contains("| **Total** | 0 | 0.0% | 0 | 0.0 |"));
+        assert!(!table.contains("NaN"));
+    }
+
+    #[test]
+    fn test_cross_language_edges() {
+        let temp_dir = create_test_project(vec![
+            (
+                "src/ffi.rs",
+                "// extern \"C\" in a comment does not count\nextern \"C\" {\n    fn add(a: i32, b: i32) -> i32;\n}\n",
+            ),
+            ("src/comment_only.rs", "// uses extern \"C\" elsewhere\n"),
+            ("csrc/add.c", "int add(int a, int b) { return a + b; }\n"),
+            ("tools/run.py", "import subprocess\nsubprocess.run(['make'])\n"),
+            ("scripts/build.sh", "#!/bin/bash\ncargo build && python3 tools/run.py\n"),
+        ]);
+
+        let analysis = MultiLanguageAnalyzer::new()
+            .analyze(temp_dir.path())
+            .unwrap();
+        let edges: Vec<String> = analysis
+            .language_edges
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            edges,
+            vec![
+                "Python -> Shell",
+                "Rust -> C",
+                "Shell -> Python",
+                "Shell -> Rust"
+            ]
+        );
+        let rust_to_c = &analysis.language_edges[1];
+        assert_eq!(rust_to_c.files, vec![PathBuf::from("src/ffi.rs")]);
+    }
+
+    #[test]
+    fn test_no_edges_without_interop() {
+        let temp_dir = create_test_project(vec![
+            ("src/main.rs", "fn main() {}\n"),
+            ("app.py", "print('hi')\n"),
+        ]);
+
+        let analysis = MultiLanguageAnalyzer::new()
+            .analyze(temp_dir.path())
+            .unwrap();
+        assert!(analysis.language_edges.is_empty());
+    }
+
     #[test]
     fn test_multi_language_project() {
         let temp_dir = create_test_project(vec![
@@ -715,7 +1488,7 @@ mod tests {
         stats.percentage_of_project = 95.0;
         stats_map.insert(Language::Rust, stats);
 
-        let pattern = MultiLanguageAnalyzer::detect_architecture_pattern(&stats_map);
+        let pattern = MultiLanguageAnalyzer::classify_architecture(&stats_map).pattern;
         assert_eq!(pattern, ArchitecturePattern::Monolingual);
     }
 
@@ -731,10 +1504,47 @@ mod tests {
         js_stats.percentage_of_project = 40.0;
         stats_map.insert(Language::JavaScript, js_stats);
 
-        let pattern = MultiLanguageAnalyzer::detect_architecture_pattern(&stats_map);
+        let pattern = MultiLanguageAnalyzer::classify_architecture(&stats_map).pattern;
         assert_eq!(pattern, ArchitecturePattern::FullStack);
     }
 
+    #[test]
+    fn test_classification_confidence_monolingual() {
+        let mut stats_map = HashMap::new();
+
+        let mut rust_stats = LanguageStats::new(Language::Rust);
+        rust_stats.percentage_of_project = 95.0;
+        stats_map.insert(Language::Rust, rust_stats);
+
+        let mut shell_stats = LanguageStats::new(Language::Shell);
+        shell_stats.percentage_of_project = 5.0;
+        stats_map.insert(Language::Shell, shell_stats);
+
+        let classification = MultiLanguageAnalyzer::classify_architecture(&stats_map);
+        assert_eq!(classification.pattern, ArchitecturePattern::Monolingual);
+        assert!(classification.confidence >= 0.7);
+        assert!(!classification.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_classification_confidence_borderline_fullstack() {
+        let mut stats_map = HashMap::new();
+
+        let mut rust_stats = LanguageStats::new(Language::Rust);
+        rust_stats.percentage_of_project = 55.0;
+        stats_map.insert(Language::Rust, rust_stats);
+
+        let mut js_stats = LanguageStats::new(Language::JavaScript);
+        js_stats.percentage_of_project = 45.0;
+        stats_map.insert(Language::JavaScript, js_stats);
+
+        let classification = MultiLanguageAnalyzer::classify_architecture(&stats_map);
+        assert_eq!(classification.pattern, ArchitecturePattern::FullStack);
+        assert!(classification.confidence < 0.5);
+        assert!(classification.reasons[0].contains("JavaScript"));
+        assert!(classification.reasons[0].contains("Rust"));
+    }
+
     #[test]
     fn test_exclude_directories() {
         let temp_dir = create_test_project(vec![
@@ -807,6 +1617,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_exclude_globs_nested_fixtures() {
+        let temp_dir = create_test_project(vec![
+            ("src/main.rs", "fn main() {}\n"),
+            ("tests/integration.rs", "fn it_works() {}\n"),
+            ("tests/fixtures/sample.rs", "fn fixture() {}\n"),
+            ("tests/fixtures/deep/data.py", "x = 1\n"),
+        ]);
+
+        let analyzer = MultiLanguageAnalyzer::new()
+            .with_exclude_globs(vec!["**/fixtures/**".to_string()])
+            .unwrap();
+        let analysis = analyzer.analyze(temp_dir.path()).unwrap();
+
+        let rust_stats = &analysis.language_stats[&Language::Rust];
+        let mut paths = rust_stats.files_paths();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![Path::new("src/main.rs"), Path::new("tests/integration.rs")]
+        );
+        assert!(!analysis.language_stats.contains_key(&Language::Python));
+    }
+
+    #[test]
+    fn test_exclude_globs_invalid_pattern() {
+        let result = MultiLanguageAnalyzer::new().with_exclude_globs(vec!["src/[".to_string()]);
+        assert!(result.is_err());
+    }
+
+    /// Fixture with blank lines, block comments, CRLF endings, and no trailing newline
+    fn streaming_fixture() -> TempDir {
+        create_test_project(vec![
+            (
+                "src/main.rs",
+                "/* header\n * docs\n */\n\nfn main() {\n    // hi\n}\n",
+            ),
+            (
+                "src/lib.rs",
+                "pub fn a() {}\r\n\r\n// note\r\npub fn b() {}",
+            ),
+            (
+                "scripts/run.py",
+                "# setup\nimport os\n\nprint(os.name)  # inline\n",
+            ),
+            ("web/app.ts", "export const x = 1;\n"),
+            ("tools/build.sh", "#!/bin/bash\necho hi\n\n"),
+        ])
+    }
+
+    #[test]
+    fn test_streaming_matches_buffered_aggregates() {
+        let temp_dir = streaming_fixture();
+
+        let buffered = MultiLanguageAnalyzer::new()
+            .analyze(temp_dir.path())
+            .unwrap();
+        let streaming = MultiLanguageAnalyzer::new()
+            .with_line_counting_only(true)
+            .analyze(temp_dir.path())
+            .unwrap();
+
+        assert_eq!(streaming.total_lines, buffered.total_lines);
+        assert_eq!(streaming.total_files, buffered.total_files);
+        assert_eq!(
+            streaming.language_stats.len(),
+            buffered.language_stats.len()
+        );
+        for (language, expected) in &buffered.language_stats {
+            let actual = &streaming.language_stats[language];
+            assert_eq!(actual.lines_of_code, expected.lines_of_code, "{language}");
+            assert_eq!(actual.blank_lines, expected.blank_lines, "{language}");
+            assert_eq!(actual.comment_lines, expected.comment_lines, "{language}");
+            assert_eq!(actual.file_count, expected.file_count, "{language}");
+        }
+    }
+
+    #[test]
+    fn test_max_retained_files_keeps_largest() {
+        let temp_dir = create_test_project(vec![
+            ("a.rs", &"fn a() {}\n".repeat(3)),
+            ("b.rs", &"fn b() {}\n".repeat(10)),
+            ("c.rs", &"fn c() {}\n".repeat(1)),
+            ("d.rs", &"fn d() {}\n".repeat(7)),
+        ]);
+
+        let analysis = MultiLanguageAnalyzer::new()
+            .with_line_counting_only(true)
+            .with_max_retained_files(2)
+            .analyze(temp_dir.path())
+            .unwrap();
+
+        let rust_stats = &analysis.language_stats[&Language::Rust];
+        assert_eq!(rust_stats.file_count, 4);
+        assert_eq!(rust_stats.lines_of_code, 21);
+
+        let mut lines: Vec<_> = rust_stats.files.iter().map(|f| f.lines).collect();
+        lines.sort_unstable();
+        assert_eq!(lines, vec![7, 10]);
+    }
+
     #[test]
     fn test_max_depth_limit() {
         let temp_dir = create_test_project(vec![
@@ -834,13 +1745,89 @@ mod tests {
 
         let analyzer = MultiLanguageAnalyzer::new();
         let file_path = temp_dir.path().join("test.rs");
-        let stats = analyzer.analyze_file(&file_path).unwrap();
+        let stats = analyzer.analyze_file(&file_path, Language::Rust).unwrap();
 
         assert_eq!(stats.lines, 4);
         assert_eq!(stats.comment_lines, 2);
         assert_eq!(stats.blank_lines, 1);
     }
 
+    #[test]
+    fn test_file_analysis_multiline_block_comment() {
+        let temp_dir = create_test_project(vec![(
+            "lib.rs",
+            "/*\n * Module docs\n\n * continued\n */\nfn main() {} /* trailing */\nlet s = \"/* not a comment */\";\n",
+        )]);
+
+        let analyzer = MultiLanguageAnalyzer::new();
+        let file_path = temp_dir.path().join("lib.rs");
+        let stats = analyzer.analyze_file(&file_path, Language::Rust).unwrap();
+
+        assert_eq!(stats.lines, 7);
+        assert_eq!(stats.blank_lines, 1);
+        assert_eq!(stats.comment_lines, 4);
+    }
+
+    #[test]
+    fn test_file_analysis_python_hash_in_code() {
+        let temp_dir = create_test_project(vec![(
+            "app.py",
+            "# Real comment\ncolor = \"#ff0000\"\nlabel = f\"#{count}\"  # trailing\n    # indented comment\n",
+        )]);
+
+        let analyzer = MultiLanguageAnalyzer::new();
+        let file_path = temp_dir.path().join("app.py");
+        let stats = analyzer.analyze_file(&file_path, Language::Python).unwrap();
+
+        assert_eq!(stats.lines, 4);
+        assert_eq!(stats.comment_lines, 2);
+    }
+
+    #[test]
+    fn test_file_analysis_c_preprocessor_is_code() {
+        let temp_dir = create_test_project(vec![(
+            "main.c",
+            "#include <stdio.h>\n// entry point\nint main() { return 0; }\n",
+        )]);
+
+        let analyzer = MultiLanguageAnalyzer::new();
+        let file_path = temp_dir.path().join("main.c");
+        let stats = analyzer.analyze_file(&file_path, Language::C).unwrap();
+
+        assert_eq!(stats.comment_lines, 1);
+    }
+
+    #[test]
+    fn test_top_files_ranking() {
+        let temp_dir = create_test_project(vec![
+            ("small.rs", &"fn a() {}\n".repeat(5)),
+            ("large.py", &"x = 1\n".repeat(40)),
+            ("medium.js", &"// note\nlet a = 1;\n".repeat(10)),
+        ]);
+
+        let analyzer = MultiLanguageAnalyzer::new();
+        let analysis = analyzer.analyze(temp_dir.path()).unwrap();
+
+        let top = analysis.top_files(3);
+        let ranking: Vec<_> = top.iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(
+            ranking,
+            vec![
+                Path::new("large.py"),
+                Path::new("medium.js"),
+                Path::new("small.rs")
+            ]
+        );
+        assert_eq!(top[0].lines, 40);
+        assert_eq!(top[1].comment_lines, 10);
+
+        assert_eq!(analysis.top_files(1).len(), 1);
+        assert_eq!(
+            analysis.language_stats[&Language::Rust].files_paths(),
+            vec![Path::new("small.rs")]
+        );
+    }
+
     #[test]
     fn test_primary_and_secondary_languages() {
         let temp_dir = create_test_project(vec![
@@ -855,4 +1842,91 @@ mod tests {
         assert_eq!(analysis.primary_language, Some(Language::Rust));
         assert!(analysis.secondary_languages.contains(&Language::JavaScript));
     }
+
+    #[test]
+    fn test_secondary_threshold() {
+        // Rust 90 lines, JavaScript 3 lines (3%), Go 7 lines (7%)
+        let temp_dir = create_test_project(vec![
+            ("main.rs", &"fn main() {}\n".repeat(90)),
+            ("app.js", &"console.log();\n".repeat(3)),
+            ("main.go", &"package main\n".repeat(7)),
+        ]);
+
+        let default = MultiLanguageAnalyzer::new()
+            .analyze(temp_dir.path())
+            .unwrap();
+        assert_eq!(default.secondary_languages, vec![Language::Go]);
+        assert_eq!(default.minor_languages, vec![Language::JavaScript]);
+
+        let lowered = MultiLanguageAnalyzer::new()
+            .with_secondary_threshold(1.0)
+            .analyze(temp_dir.path())
+            .unwrap();
+        assert_eq!(
+            lowered.secondary_languages,
+            vec![Language::Go, Language::JavaScript]
+        );
+        assert!(lowered.minor_languages.is_empty());
+    }
+
+    #[test]
+    fn test_progress_fires_for_every_file() {
+        let temp_dir = create_test_project(vec![
+            ("src/main.rs", "fn main() {}\n"),
+            ("src/util/helpers.py", "def f():\n    pass\n"),
+            ("web/app.js", "console.log(1);\n"),
+            ("README.md", "# not source\n"),
+        ]);
+
+        let events = std::cell::RefCell::new(Vec::new());
+        let analysis = MultiLanguageAnalyzer::new()
+            .analyze_with_progress(temp_dir.path(), |event| events.borrow_mut().push(event))
+            .unwrap();
+        let events = events.into_inner();
+
+        let mut analyzed: Vec<PathBuf> = events
+            .iter()
+            .filter_map(|event| match event {
+                ProgressEvent::FileAnalyzed { path, .. } => Some(path.clone()),
+                ProgressEvent::DirectoryScanned { .. } => None,
+            })
+            .collect();
+        analyzed.sort();
+        assert_eq!(analyzed.len(), analysis.total_files);
+        assert_eq!(
+            analyzed,
+            vec![
+                PathBuf::from("src/main.rs"),
+                PathBuf::from("src/util/helpers.py"),
+                PathBuf::from("web/app.js"),
+            ]
+        );
+
+        assert!(events.contains(&ProgressEvent::DirectoryScanned {
+            path: PathBuf::new()
+        }));
+        assert!(events.contains(&ProgressEvent::DirectoryScanned {
+            path: PathBuf::from("src/util")
+        }));
+        assert!(events.contains(&ProgressEvent::FileAnalyzed {
+            path: PathBuf::from("src/util/helpers.py"),
+            language: Language::Python,
+            lines: 2,
+        }));
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let temp_dir = streaming_fixture();
+        let analyzer = MultiLanguageAnalyzer::new().with_max_retained_files(1);
+
+        let sequential = analyzer.analyze(temp_dir.path()).unwrap();
+        let parallel = analyzer.analyze_parallel(temp_dir.path()).unwrap();
+
+        assert_eq!(parallel.total_lines, sequential.total_lines);
+        assert_eq!(parallel.total_files, sequential.total_files);
+        assert_eq!(parallel.language_stats, sequential.language_stats);
+        assert_eq!(parallel.primary_language, sequential.primary_language);
+        assert_eq!(parallel.language_edges, sequential.language_edges);
+    }
 }
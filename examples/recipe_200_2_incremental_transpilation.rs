@@ -14,6 +14,7 @@
 //!
 //! - **Change Detection:** File hash-based change tracking
 //! - **Smart Caching:** Cache transpiled outputs and reuse when possible
+//! - **Content Deduplication:** Identical outputs are stored once, in memory and on disk
 //! - **Dependency Tracking:** Invalidate dependent files when dependencies change
 //! - **Performance Metrics:** Track cache hits, misses, and time savings
 //! - **Cache Management:** Configurable cache size and TTL
@@ -40,12 +41,28 @@
 //! ```bash
 //! cargo test --example recipe_200_2_incremental_transpilation
 //! ```
+//!
+//! The async cache load/save variants are behind the `async` feature:
+//! ```bash
+//! cargo test --features async --example recipe_200_2_incremental_transpilation
+//! ```
+//!
+//! Watch mode (`IncrementalTranspiler::watch`) is behind the `watch` feature:
+//! ```bash
+//! cargo test --features watch --example recipe_200_2_incremental_transpilation
+//! ```
 
+use batuta_cookbook::transpiler::TranspilerRegistry;
+use batuta_cookbook::types::Language;
 use batuta_cookbook::{Error, Result};
+use globset::{Glob, GlobMatcher};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::{Duration, SystemTime};
 
 /// Cache entry for a transpiled file
@@ -57,16 +74,27 @@ pub struct CacheEntry {
     pub output_path: PathBuf,
     /// Hash of source content
     pub source_hash: String,
-    /// Transpiled content
-    pub transpiled_content: String,
+    /// Transpiled content (shared between entries with identical output)
+    pub transpiled_content: Arc<str>,
     /// Timestamp of transpilation
     pub timestamp: SystemTime,
+    /// Timestamp of the most recent cache hit (used for LRU eviction)
+    #[serde(default = "never_accessed")]
+    pub last_accessed: SystemTime,
     /// Source language
     pub source_language: String,
     /// Target language
     pub target_language: String,
     /// Dependencies (other files this depends on)
     pub dependencies: Vec<PathBuf>,
+    /// Hash of each dependency at transpilation time
+    #[serde(default)]
+    pub dependency_hashes: HashMap<PathBuf, String>,
+}
+
+/// Default access time for entries loaded from caches that predate LRU tracking
+fn never_accessed() -> SystemTime {
+    SystemTime::UNIX_EPOCH
 }
 
 impl CacheEntry {
@@ -88,24 +116,78 @@ impl CacheEntry {
     }
 }
 
+/// Current on-disk cache format version
+///
+/// Version 1 caches predate the `format_version` field; version 2 stores
+/// each entry's output inline rather than in a shared `contents` table.
+const CACHE_FORMAT_VERSION: u32 = 3;
+
+/// The shared copy of `content` in `contents`, adding it if it's new
+///
+/// Counts one more entry referring to it; pair with `release`.
+fn intern(contents: &mut HashMap<Arc<str>, usize>, content: &Arc<str>) -> Arc<str> {
+    if let Some((existing, refs)) = contents.get_key_value(&**content) {
+        let existing = Arc::clone(existing);
+        contents.insert(Arc::clone(&existing), refs + 1);
+        return existing;
+    }
+    contents.insert(Arc::clone(content), 1);
+    Arc::clone(content)
+}
+
+/// Drop one entry's reference to `content`, freeing it when none remain
+fn release(contents: &mut HashMap<Arc<str>, usize>, content: &str) {
+    if let Some(refs) = contents.get_mut(content) {
+        *refs -= 1;
+        if *refs == 0 {
+            contents.remove(content);
+        }
+    }
+}
+
 /// Transpilation cache
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranspilationCache {
+    /// On-disk format version (see `CACHE_FORMAT_VERSION`)
+    format_version: u32,
     /// Cache entries by source path
     entries: HashMap<PathBuf, CacheEntry>,
     /// Maximum cache age in seconds
     max_age_secs: u64,
     /// Maximum number of entries
     max_entries: usize,
+    /// Number of entries evicted to make room for new ones
+    #[serde(default)]
+    evictions: usize,
+    /// Distinct transpiled outputs, shared by every entry that produced
+    /// them, with the number of entries referring to each
+    #[serde(skip)]
+    contents: HashMap<Arc<str>, usize>,
+}
+
+/// Snapshot of cache occupancy and churn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Current number of entries
+    pub len: usize,
+    /// Maximum number of entries
+    pub max_entries: usize,
+    /// Total entries evicted over the cache's lifetime
+    pub evictions: usize,
+    /// Timestamp of the oldest entry, if any
+    pub oldest_timestamp: Option<SystemTime>,
 }
 
 impl TranspilationCache {
     /// Create a new cache
     pub fn new() -> Self {
         Self {
+            format_version: CACHE_FORMAT_VERSION,
             entries: HashMap::new(),
             max_age_secs: 86400, // 24 hours
             max_entries: 10000,
+            evictions: 0,
+            contents: HashMap::new(),
         }
     }
 
@@ -121,8 +203,21 @@ impl TranspilationCache {
         self
     }
 
-    /// Get a cache entry if valid
-    pub fn get(&self, source_path: &Path, current_hash: &str) -> Option<&CacheEntry> {
+    /// Get a cache entry if valid, marking it as recently used
+    pub fn get(&mut self, source_path: &Path, current_hash: &str) -> Option<&CacheEntry> {
+        let max_age = Duration::from_secs(self.max_age_secs);
+        let entry = self.entries.get_mut(source_path)?;
+
+        if entry.is_valid(current_hash, max_age) {
+            entry.last_accessed = SystemTime::now();
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    /// Look up a valid cache entry without updating its access time
+    fn peek(&self, source_path: &Path, current_hash: &str) -> Option<&CacheEntry> {
         let entry = self.entries.get(source_path)?;
         let max_age = Duration::from_secs(self.max_age_secs);
 
@@ -133,24 +228,53 @@ impl TranspilationCache {
         }
     }
 
+    /// Check that an entry is valid and none of its dependencies changed
+    ///
+    /// `dep_hashes` holds the current hash of every dependency; the entry is
+    /// stale if its recorded dependency set or any dependency hash differs.
+    #[must_use]
+    pub fn is_valid_with_deps(
+        &self,
+        source_path: &Path,
+        current_hash: &str,
+        dep_hashes: &HashMap<PathBuf, String>,
+    ) -> bool {
+        self.peek(source_path, current_hash)
+            .is_some_and(|entry| entry.dependency_hashes == *dep_hashes)
+    }
+
     /// Insert a cache entry
-    pub fn insert(&mut self, entry: CacheEntry) {
+    ///
+    /// Output identical to an already cached entry's is stored only once.
+    pub fn insert(&mut self, mut entry: CacheEntry) {
         // Check if we need to evict old entries
         if self.entries.len() >= self.max_entries {
             self.evict_oldest();
         }
 
-        self.entries.insert(entry.source_path.clone(), entry);
+        entry.transpiled_content = intern(&mut self.contents, &entry.transpiled_content);
+        if let Some(replaced) = self.entries.insert(entry.source_path.clone(), entry) {
+            release(&mut self.contents, &replaced.transpiled_content);
+        }
     }
 
     /// Remove a cache entry
     pub fn remove(&mut self, source_path: &Path) {
-        self.entries.remove(source_path);
+        if let Some(removed) = self.entries.remove(source_path) {
+            release(&mut self.contents, &removed.transpiled_content);
+        }
     }
 
     /// Clear all cache entries
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.contents.clear();
+    }
+
+    /// Number of distinct transpiled outputs held by the cache
+    #[must_use]
+    pub fn content_blob_count(&self) -> usize {
+        self.contents.len()
     }
 
     /// Get cache size
@@ -163,38 +287,172 @@ impl TranspilationCache {
         self.entries.is_empty()
     }
 
-    /// Evict the oldest cache entry
+    /// Get the number of entries evicted so far
+    #[must_use]
+    pub fn eviction_count(&self) -> usize {
+        self.evictions
+    }
+
+    /// Get cache statistics
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            len: self.entries.len(),
+            max_entries: self.max_entries,
+            evictions: self.evictions,
+            oldest_timestamp: self.entries.values().map(|entry| entry.timestamp).min(),
+        }
+    }
+
+    /// Evict the least recently accessed cache entry
     fn evict_oldest(&mut self) {
         if let Some(oldest_path) = self
             .entries
             .iter()
-            .min_by_key(|(_, entry)| entry.timestamp)
+            .min_by_key(|(_, entry)| entry.last_accessed)
             .map(|(path, _)| path.clone())
         {
-            self.entries.remove(&oldest_path);
+            if let Some(evicted) = self.entries.remove(&oldest_path) {
+                release(&mut self.contents, &evicted.transpiled_content);
+            }
+            self.evictions += 1;
+        }
+    }
+
+    /// Serialize the cache to pretty-printed JSON
+    fn to_json(&self) -> Result<String> {
+        let mut value = serde_json::to_value(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize cache: {e}")))?;
+        if let Some(fields) = value.as_object_mut() {
+            Self::deduplicate_contents(fields);
+        }
+
+        serde_json::to_string_pretty(&value)
+            .map_err(|e| Error::Other(format!("Failed to serialize cache: {e}")))
+    }
+
+    /// Move each entry's inline `transpiled_content` into a `contents` table
+    /// keyed by content hash, leaving a `content_hash` reference behind
+    fn deduplicate_contents(fields: &mut Map<String, Value>) {
+        use sha2::{Digest, Sha256};
+
+        let mut contents = Map::new();
+        if let Some(Value::Object(entries)) = fields.get_mut("entries") {
+            for entry in entries.values_mut().filter_map(Value::as_object_mut) {
+                if let Some(Value::String(content)) = entry.remove("transpiled_content") {
+                    let hash = format!("{:x}", Sha256::digest(content.as_bytes()));
+                    entry.insert("content_hash".to_string(), hash.clone().into());
+                    contents.entry(hash).or_insert(content.into());
+                }
+            }
+        }
+        fields.insert("contents".to_string(), Value::Object(contents));
+    }
+
+    /// Inverse of `deduplicate_contents`, run before deserializing
+    fn inline_contents(fields: &mut Map<String, Value>) -> Result<()> {
+        let Some(Value::Object(contents)) = fields.remove("contents") else {
+            return Ok(());
+        };
+        if let Some(Value::Object(entries)) = fields.get_mut("entries") {
+            for entry in entries.values_mut().filter_map(Value::as_object_mut) {
+                if let Some(Value::String(hash)) = entry.remove("content_hash") {
+                    let content = contents.get(&hash).cloned().ok_or_else(|| {
+                        Error::Other(format!("Cache entry refers to missing content {hash}"))
+                    })?;
+                    entry.insert("transpiled_content".to_string(), content);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse a cache from JSON written by `to_json`, migrating older formats
+    fn from_json(content: &str) -> Result<Self> {
+        let mut value: Value = serde_json::from_str(content)
+            .map_err(|e| Error::Other(format!("Failed to deserialize cache: {e}")))?;
+
+        let version = match value.get("format_version") {
+            None => 1,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| Error::Other(format!("Invalid cache format_version: {version}")))?,
+        };
+        if version == 0 || version > CACHE_FORMAT_VERSION {
+            return Err(Error::Other(format!(
+                "Unsupported cache format version {version} (this build reads versions 1 to \
+                 {CACHE_FORMAT_VERSION}); delete the cache file to rebuild it"
+            )));
+        }
+        if let Some(fields) = value.as_object_mut() {
+            Self::migrate(fields, version);
+            Self::inline_contents(fields)?;
+        }
+
+        let mut cache: Self = serde_json::from_value(value)
+            .map_err(|e| Error::Other(format!("Failed to deserialize cache: {e}")))?;
+        for entry in cache.entries.values_mut() {
+            entry.transpiled_content = intern(&mut cache.contents, &entry.transpiled_content);
+        }
+        Ok(cache)
+    }
+
+    /// Upgrade a parsed cache from `version` to `CACHE_FORMAT_VERSION` in place
+    fn migrate(fields: &mut Map<String, Value>, version: u32) {
+        if version < 2 {
+            // Version 2 only added the version field itself
+            fields.insert("format_version".to_string(), 2.into());
+        }
+        if version < 3 {
+            Self::deduplicate_contents(fields);
+            fields.insert("format_version".to_string(), 3.into());
         }
     }
 
     /// Save cache to file
     pub fn save_to_file(&self, path: &Path) -> Result<()> {
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| Error::Other(format!("Failed to serialize cache: {}", e)))?;
-
-        fs::write(path, json)
-            .map_err(|e| Error::Other(format!("Failed to write cache file: {}", e)))?;
+        fs::write(path, self.to_json()?)?;
 
         Ok(())
     }
 
     /// Load cache from file
+    ///
+    /// Caches written in an older format are migrated in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or if it was
+    /// written in a newer or unknown format (delete it to start fresh).
     pub fn load_from_file(path: &Path) -> Result<Self> {
-        let content = fs::read_to_string(path)
-            .map_err(|e| Error::Other(format!("Failed to read cache file: {}", e)))?;
+        let content = fs::read_to_string(path)?;
 
-        let cache: Self = serde_json::from_str(&content)
-            .map_err(|e| Error::Other(format!("Failed to deserialize cache: {}", e)))?;
+        Self::from_json(&content)
+    }
 
-        Ok(cache)
+    /// Save cache to file without blocking the async runtime
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be serialized or written.
+    #[cfg(feature = "async")]
+    pub async fn save_to_file_async(&self, path: &Path) -> Result<()> {
+        tokio::fs::write(path, self.to_json()?).await?;
+
+        Ok(())
+    }
+
+    /// Load cache from file without blocking the async runtime
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    #[cfg(feature = "async")]
+    pub async fn load_from_file_async(path: &Path) -> Result<Self> {
+        let content = tokio::fs::read_to_string(path).await?;
+
+        Self::from_json(&content)
     }
 }
 
@@ -204,6 +462,164 @@ impl Default for TranspilationCache {
     }
 }
 
+/// Import edges between source files
+///
+/// Used to transpile dependencies before the files that import them, so a
+/// dependent is never checked against a stale dependency hash.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    /// Direct dependencies of every file in the graph
+    edges: BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl DependencyGraph {
+    /// Create an empty graph
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a graph over `files` from the imports found in each one
+    ///
+    /// Python `import a.b` / `from a.b import c` and Rust `use a::b` /
+    /// `mod b;` lines are recognised. A file depends on another in `files`
+    /// when any imported name (`a`, `b` or `c` above) equals that file's
+    /// stem; imports of anything else are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read.
+    pub fn from_sources(files: &[PathBuf]) -> Result<Self> {
+        let mut by_stem: HashMap<&str, Vec<&PathBuf>> = HashMap::new();
+        for file in files {
+            if let Some(stem) = file.file_stem().and_then(|stem| stem.to_str()) {
+                by_stem.entry(stem).or_default().push(file);
+            }
+        }
+
+        let mut graph = Self::new();
+        for file in files {
+            graph.add_file(file);
+            let content = fs::read_to_string(file).map_err(|e| {
+                Error::TranspilationError(format!("Failed to read {}: {e}", file.display()))
+            })?;
+            for name in imported_names(&content) {
+                for &dependency in by_stem.get(name.as_str()).into_iter().flatten() {
+                    if dependency != file {
+                        graph.add_dependency(file, dependency);
+                    }
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Add a file with no dependencies (if it isn't in the graph yet)
+    pub fn add_file(&mut self, file: &Path) {
+        self.edges.entry(file.to_path_buf()).or_default();
+    }
+
+    /// Record that `dependent` imports `dependency`
+    pub fn add_dependency(&mut self, dependent: &Path, dependency: &Path) {
+        self.add_file(dependency);
+        self.edges
+            .entry(dependent.to_path_buf())
+            .or_default()
+            .insert(dependency.to_path_buf());
+    }
+
+    /// Direct dependencies of `file`, in path order
+    #[must_use]
+    pub fn dependencies(&self, file: &Path) -> Vec<PathBuf> {
+        self.edges
+            .get(file)
+            .map(|deps| deps.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Every file, each listed after all of its dependencies
+    ///
+    /// Ties are broken by path, so the order is deterministic.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TranspilationError` naming the files involved if the
+    /// dependencies form a cycle.
+    pub fn topological_order(&self) -> Result<Vec<PathBuf>> {
+        let mut order = Vec::with_capacity(self.edges.len());
+        let mut done = HashSet::new();
+        let mut path = Vec::new();
+        for file in self.edges.keys() {
+            self.visit(file, &mut path, &mut done, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    /// Depth-first post-order visit; `path` holds the files being visited
+    fn visit<'a>(
+        &'a self,
+        file: &'a PathBuf,
+        path: &mut Vec<&'a PathBuf>,
+        done: &mut HashSet<&'a PathBuf>,
+        order: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        if done.contains(file) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|&visiting| visiting == file) {
+            let cycle: Vec<String> = path[start..]
+                .iter()
+                .chain([&file])
+                .map(|file| file.display().to_string())
+                .collect();
+            return Err(Error::TranspilationError(format!(
+                "Dependency cycle: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        path.push(file);
+        for dependency in self.edges.get(file).into_iter().flatten() {
+            self.visit(dependency, path, done, order)?;
+        }
+        path.pop();
+
+        done.insert(file);
+        order.push(file.clone());
+        Ok(())
+    }
+}
+
+/// Every name mentioned by the import lines of `content`
+fn imported_names(content: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for line in content.lines().map(str::trim) {
+        let modules = if let Some(rest) = line.strip_prefix("from ") {
+            // `from a.b import c, d`: the module and what is imported from it
+            let (module, imported) = rest.split_once(" import ").unwrap_or((rest, ""));
+            format!("{module},{imported}")
+        } else if let Some(rest) = line
+            .strip_prefix("import ")
+            .or_else(|| line.strip_prefix("use "))
+            .or_else(|| line.strip_prefix("pub use "))
+            .or_else(|| line.strip_prefix("mod "))
+            .or_else(|| line.strip_prefix("pub mod "))
+        {
+            rest.to_string()
+        } else {
+            continue;
+        };
+
+        names.extend(
+            modules
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .filter(|name| !name.is_empty() && *name != "as")
+                .map(str::to_string),
+        );
+    }
+    names
+}
+
 /// Performance metrics for incremental transpilation
 #[derive(Debug, Clone, Default)]
 pub struct IncrementalMetrics {
@@ -224,6 +640,17 @@ pub struct IncrementalMetrics {
 }
 
 impl IncrementalMetrics {
+    /// Add `other`'s counts and times to these
+    fn merge(&mut self, other: &IncrementalMetrics) {
+        self.total_files += other.total_files;
+        self.cache_hits += other.cache_hits;
+        self.cache_misses += other.cache_misses;
+        self.files_transpiled += other.files_transpiled;
+        self.files_skipped += other.files_skipped;
+        self.total_time_ms += other.total_time_ms;
+        self.time_saved_ms += other.time_saved_ms;
+    }
+
     /// Calculate cache hit rate
     pub fn hit_rate(&self) -> f64 {
         if self.total_files == 0 {
@@ -242,6 +669,11 @@ impl IncrementalMetrics {
     }
 }
 
+/// How long `IncrementalTranspiler::watch` waits for further changes before
+/// transpiling a batch
+#[cfg(feature = "watch")]
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
 /// Incremental transpiler with caching
 pub struct IncrementalTranspiler {
     /// Transpilation cache
@@ -252,6 +684,14 @@ pub struct IncrementalTranspiler {
     metrics: IncrementalMetrics,
     /// Enable verbose logging
     verbose: bool,
+    /// Backends used to transpile source files
+    registry: TranspilerRegistry,
+    /// Language of the source files
+    source_language: Language,
+    /// Language to emit
+    target_language: Language,
+    /// Compute outputs and metrics without writing any files
+    dry_run: bool,
 }
 
 impl IncrementalTranspiler {
@@ -262,6 +702,10 @@ impl IncrementalTranspiler {
             cache_path: None,
             metrics: IncrementalMetrics::default(),
             verbose: false,
+            registry: TranspilerRegistry::with_defaults(),
+            source_language: Language::Python,
+            target_language: Language::Rust,
+            dry_run: false,
         }
     }
 
@@ -277,6 +721,28 @@ impl IncrementalTranspiler {
         self
     }
 
+    /// Skip every file write (outputs and cache file); metrics still update
+    #[must_use]
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Use a custom backend registry
+    #[must_use]
+    pub fn with_registry(mut self, registry: TranspilerRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Set the (source, target) language pair used to pick a backend
+    #[must_use]
+    pub fn with_languages(mut self, source: Language, target: Language) -> Self {
+        self.source_language = source;
+        self.target_language = target;
+        self
+    }
+
     /// Set cache configuration
     pub fn with_cache(mut self, cache: TranspilationCache) -> Self {
         self.cache = cache;
@@ -298,6 +764,9 @@ impl IncrementalTranspiler {
 
     /// Save cache to file if configured
     pub fn save_cache(&self) -> Result<()> {
+        if self.dry_run {
+            return Ok(());
+        }
         if let Some(ref path) = self.cache_path {
             self.cache.save_to_file(path)?;
             if self.verbose {
@@ -309,70 +778,231 @@ impl IncrementalTranspiler {
 
     /// Transpile a single file incrementally
     pub fn transpile_file(&mut self, source_path: &Path, output_path: &Path) -> Result<()> {
+        self.transpile_file_with_deps(source_path, output_path, &[])
+    }
+
+    /// Transpile a single file incrementally, invalidating the cached output
+    /// when any of `dependencies` has changed since it was transpiled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source or a dependency cannot be read, or the
+    /// output cannot be written.
+    pub fn transpile_file_with_deps(
+        &mut self,
+        source_path: &Path,
+        output_path: &Path,
+        dependencies: &[PathBuf],
+    ) -> Result<()> {
+        let cache = Mutex::new(std::mem::take(&mut self.cache));
+        let result = self.transpile_with_cache(&cache, source_path, output_path, dependencies);
+        self.cache = cache.into_inner().unwrap_or_else(PoisonError::into_inner);
+
+        self.metrics.merge(&result?);
+        Ok(())
+    }
+
+    /// Transpile `files` so every dependency is transpiled before the files
+    /// importing it
+    ///
+    /// Dependencies come from `DependencyGraph::from_sources` over the source
+    /// paths, and each file's cache entry is tied to their hashes (see
+    /// `transpile_file_with_deps`). Returns the source paths in the order
+    /// they were transpiled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the imports form a cycle (before anything is
+    /// transpiled) or any file fails to transpile.
+    pub fn transpile_in_dependency_order(
+        &mut self,
+        files: Vec<(PathBuf, PathBuf)>,
+    ) -> Result<Vec<PathBuf>> {
+        let sources: Vec<PathBuf> = files.iter().map(|(source, _)| source.clone()).collect();
+        let graph = DependencyGraph::from_sources(&sources)?;
+        let order = graph.topological_order()?;
+
+        let mut outputs: HashMap<PathBuf, PathBuf> = files.into_iter().collect();
+        for source in &order {
+            if let Some(output) = outputs.remove(source) {
+                self.transpile_file_with_deps(source, &output, &graph.dependencies(source))?;
+            }
+        }
+        Ok(order)
+    }
+
+    /// Transpile `files` concurrently on a pool of `threads` workers (0 uses
+    /// one per CPU)
+    ///
+    /// Workers lock the cache only to look up or insert an entry, so cache
+    /// hits are detected exactly as in `transpile_batch`. Every file is
+    /// attempted; metrics are merged in input order and the first error in
+    /// input order is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the thread pool cannot be started or any file
+    /// fails to transpile.
+    pub fn transpile_batch_parallel(
+        &mut self,
+        files: Vec<(PathBuf, PathBuf)>,
+        threads: usize,
+    ) -> Result<()> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| Error::Other(format!("Failed to start thread pool: {e}")))?;
+
+        let cache = Mutex::new(std::mem::take(&mut self.cache));
+        let outcomes: Vec<Result<IncrementalMetrics>> = pool.install(|| {
+            files
+                .into_par_iter()
+                .map(|(source, output)| self.transpile_with_cache(&cache, &source, &output, &[]))
+                .collect()
+        });
+        self.cache = cache.into_inner().unwrap_or_else(PoisonError::into_inner);
+
+        let mut first_error = None;
+        for outcome in outcomes {
+            match outcome {
+                Ok(metrics) => self.metrics.merge(&metrics),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Transpile one file against a shared cache, returning the metrics it
+    /// adds
+    ///
+    /// The cache is locked only to look up or insert the entry, never while
+    /// transpiling, so several files can be processed at once.
+    fn transpile_with_cache(
+        &self,
+        cache: &Mutex<TranspilationCache>,
+        source_path: &Path,
+        output_path: &Path,
+        dependencies: &[PathBuf],
+    ) -> Result<IncrementalMetrics> {
         let start = std::time::Instant::now();
+        let mut metrics = IncrementalMetrics {
+            total_files: 1,
+            ..IncrementalMetrics::default()
+        };
 
         // Read source file
         let source_content = fs::read_to_string(source_path)
-            .map_err(|e| Error::TranspilationError(format!("Failed to read source: {}", e)))?;
+            .map_err(|e| Error::TranspilationError(format!("Failed to read source: {e}")))?;
 
-        // Calculate hash
+        // Calculate hashes
         let source_hash = Self::calculate_hash(&source_content);
+        let dependency_hashes = Self::hash_dependencies(dependencies)?;
 
         // Check cache
-        if let Some(entry) = self.cache.get(source_path, &source_hash) {
+        let cached = {
+            let mut cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+            if cache.is_valid_with_deps(source_path, &source_hash, &dependency_hashes) {
+                cache
+                    .get(source_path, &source_hash)
+                    .map(|entry| Arc::clone(&entry.transpiled_content))
+            } else {
+                None
+            }
+        };
+
+        if let Some(content) = cached {
             // Cache hit!
-            self.metrics.cache_hits += 1;
-            self.metrics.files_skipped += 1;
-            self.metrics.total_files += 1;
+            metrics.cache_hits = 1;
+            metrics.files_skipped = 1;
 
             // Estimate time saved (assume transpilation takes 10ms per file)
-            self.metrics.time_saved_ms += 10;
+            metrics.time_saved_ms = 10;
 
             if self.verbose {
                 println!("✓ Cache hit: {}", source_path.display());
             }
 
             // Write cached output
-            fs::write(output_path, &entry.transpiled_content)
-                .map_err(|e| Error::TranspilationError(format!("Failed to write output: {}", e)))?;
+            if !self.dry_run {
+                fs::write(output_path, content.as_bytes()).map_err(|e| {
+                    Error::TranspilationError(format!("Failed to write output: {e}"))
+                })?;
+            }
 
-            return Ok(());
+            return Ok(metrics);
         }
 
         // Cache miss - need to transpile
-        self.metrics.cache_misses += 1;
-        self.metrics.files_transpiled += 1;
-        self.metrics.total_files += 1;
+        metrics.cache_misses = 1;
+        metrics.files_transpiled = 1;
 
         if self.verbose {
             println!("✗ Cache miss: {} - transpiling...", source_path.display());
         }
 
         // Perform transpilation (simplified Python -> Rust)
-        let transpiled = self.simple_transpile(&source_content)?;
+        let transpiled =
+            self.registry
+                .transpile(self.source_language, self.target_language, &source_content)?;
 
         // Write output
-        fs::write(output_path, &transpiled)
-            .map_err(|e| Error::TranspilationError(format!("Failed to write output: {}", e)))?;
+        if !self.dry_run {
+            fs::write(output_path, &transpiled)
+                .map_err(|e| Error::TranspilationError(format!("Failed to write output: {e}")))?;
+        }
 
         // Update cache
         let entry = CacheEntry {
             source_path: source_path.to_path_buf(),
             output_path: output_path.to_path_buf(),
             source_hash,
-            transpiled_content: transpiled,
+            transpiled_content: transpiled.into(),
             timestamp: SystemTime::now(),
-            source_language: "Python".to_string(),
-            target_language: "Rust".to_string(),
-            dependencies: Vec::new(),
+            last_accessed: SystemTime::now(),
+            source_language: self.source_language.to_string(),
+            target_language: self.target_language.to_string(),
+            dependencies: dependencies.to_vec(),
+            dependency_hashes,
         };
 
-        self.cache.insert(entry);
+        cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(entry);
 
-        let elapsed = start.elapsed();
-        self.metrics.total_time_ms += elapsed.as_millis();
+        metrics.total_time_ms = start.elapsed().as_millis();
 
-        Ok(())
+        Ok(metrics)
+    }
+
+    /// Preview the output for `source_path` without touching the cache,
+    /// metrics or filesystem
+    ///
+    /// Returns the would-be output and whether it came from the cache. A
+    /// cached entry only counts if its recorded dependencies are unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be read or transpiled.
+    pub fn transpile_file_preview(&self, source_path: &Path) -> Result<(String, bool)> {
+        let source_content = fs::read_to_string(source_path)
+            .map_err(|e| Error::TranspilationError(format!("Failed to read source: {e}")))?;
+        let source_hash = Self::calculate_hash(&source_content);
+
+        if let Some(entry) = self.cache.peek(source_path, &source_hash) {
+            let deps_unchanged = Self::hash_dependencies(&entry.dependencies)
+                .is_ok_and(|hashes| hashes == entry.dependency_hashes);
+            if deps_unchanged {
+                return Ok((entry.transpiled_content.to_string(), true));
+            }
+        }
+
+        let transpiled =
+            self.registry
+                .transpile(self.source_language, self.target_language, &source_content)?;
+        Ok((transpiled, false))
     }
 
     /// Transpile multiple files incrementally
@@ -383,6 +1013,189 @@ impl IncrementalTranspiler {
         Ok(())
     }
 
+    /// Transpile every file under `src_root` whose relative path matches
+    /// `pattern`, mirroring the tree into `out_root`
+    ///
+    /// Outputs keep their relative path with the target language's extension
+    /// (`src/pkg/util.py` -> `out/pkg/util.rs`); parent directories are
+    /// created as needed. In globset syntax `*` also matches `/`, so `*.py`
+    /// finds nested files too.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is not a valid glob, a directory cannot
+    /// be read, or any file fails to transpile.
+    pub fn transpile_glob(
+        &mut self,
+        src_root: &Path,
+        out_root: &Path,
+        pattern: &str,
+    ) -> Result<()> {
+        let matcher = Glob::new(pattern)
+            .map_err(|e| Error::TranspilationError(format!("Invalid glob '{pattern}': {e}")))?
+            .compile_matcher();
+
+        let mut sources = Vec::new();
+        Self::collect_sources(src_root, src_root, &matcher, &mut sources)?;
+        sources.sort();
+
+        let files = sources
+            .into_iter()
+            .map(|source| {
+                let output = self.output_path_for(src_root, out_root, &source);
+                (source, output)
+            })
+            .collect::<Vec<_>>();
+
+        if !self.dry_run {
+            for (_, output) in &files {
+                if let Some(parent) = output.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+            }
+        }
+
+        self.transpile_batch(files)
+    }
+
+    /// Where `source` (under `src_root`) is written in the mirrored `out_root`
+    fn output_path_for(&self, src_root: &Path, out_root: &Path, source: &Path) -> PathBuf {
+        let extension = self
+            .target_language
+            .canonical_extensions()
+            .first()
+            .copied()
+            .unwrap_or("out");
+        let relative = source.strip_prefix(src_root).unwrap_or(source);
+        out_root.join(relative).with_extension(extension)
+    }
+
+    /// Watch `src_root` and re-transpile source files as they change
+    ///
+    /// Outputs mirror the tree into `out_root` as in `transpile_glob`. Events
+    /// are debounced: changes are collected until `WATCH_DEBOUNCE` passes
+    /// without another, then each changed source file is transpiled once
+    /// (cache hits skip unchanged content) and the metrics are printed.
+    /// Failures of individual files are reported without stopping the
+    /// watch, which runs until the process is interrupted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `src_root` cannot be watched or the cache cannot
+    /// be saved.
+    #[cfg(feature = "watch")]
+    pub fn watch(&mut self, src_root: &Path, out_root: &Path) -> Result<()> {
+        use notify::{RecursiveMode, Watcher};
+
+        // Events report paths under the resolved root, so resolve it too
+        let src_root = &fs::canonicalize(src_root)?;
+        let (sender, events) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|e| Error::Other(format!("Failed to start watcher: {e}")))?;
+        watcher
+            .watch(src_root, RecursiveMode::Recursive)
+            .map_err(|e| Error::Other(format!("Failed to watch {}: {e}", src_root.display())))?;
+
+        println!("Watching {} for changes...", src_root.display());
+        self.process_watch_events(&events, src_root, out_root)
+    }
+
+    /// Handle debounced batches of watcher events until the sender goes away
+    #[cfg(feature = "watch")]
+    fn process_watch_events(
+        &mut self,
+        events: &std::sync::mpsc::Receiver<notify::Result<notify::Event>>,
+        src_root: &Path,
+        out_root: &Path,
+    ) -> Result<()> {
+        while let Ok(first) = events.recv() {
+            let mut changed = BTreeSet::new();
+            self.collect_changed_sources(first, src_root, &mut changed);
+            while let Ok(event) = events.recv_timeout(WATCH_DEBOUNCE) {
+                self.collect_changed_sources(event, src_root, &mut changed);
+            }
+            if changed.is_empty() {
+                continue;
+            }
+
+            for source in changed {
+                let output = self.output_path_for(src_root, out_root, &source);
+                let result = match output.parent() {
+                    Some(parent) if !self.dry_run => {
+                        fs::create_dir_all(parent).map_err(Error::from)
+                    }
+                    _ => Ok(()),
+                }
+                .and_then(|()| self.transpile_file(&source, &output));
+                if let Err(e) = result {
+                    eprintln!("✗ {}: {e}", source.display());
+                }
+            }
+            self.save_cache()?;
+
+            println!(
+                "Metrics: {} transpiled, {} cache hits, {:.1}% hit rate",
+                self.metrics.files_transpiled,
+                self.metrics.cache_hits,
+                self.metrics.hit_rate()
+            );
+        }
+        Ok(())
+    }
+
+    /// Add the source files under `src_root` created or modified by `event`
+    /// to `changed`
+    #[cfg(feature = "watch")]
+    fn collect_changed_sources(
+        &self,
+        event: notify::Result<notify::Event>,
+        src_root: &Path,
+        changed: &mut BTreeSet<PathBuf>,
+    ) {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                eprintln!("Watch error: {e}");
+                return;
+            }
+        };
+        if !matches!(
+            event.kind,
+            notify::EventKind::Create(_) | notify::EventKind::Modify(_)
+        ) {
+            return;
+        }
+
+        changed.extend(event.paths.into_iter().filter(|path| {
+            path.starts_with(src_root)
+                && path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .and_then(Language::from_extension)
+                    == Some(self.source_language)
+        }));
+    }
+
+    /// Recursively collect files under `dir` whose path relative to `root`
+    /// matches `matcher`
+    fn collect_sources(
+        root: &Path,
+        dir: &Path,
+        matcher: &GlobMatcher,
+        sources: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                Self::collect_sources(root, &path, matcher, sources)?;
+            } else if matcher.is_match(path.strip_prefix(root).unwrap_or(&path)) {
+                sources.push(path);
+            }
+        }
+        Ok(())
+    }
+
     /// Get performance metrics
     pub fn metrics(&self) -> &IncrementalMetrics {
         &self.metrics
@@ -393,41 +1206,31 @@ impl IncrementalTranspiler {
         self.metrics = IncrementalMetrics::default();
     }
 
+    /// Hash the current content of each dependency
+    fn hash_dependencies(dependencies: &[PathBuf]) -> Result<HashMap<PathBuf, String>> {
+        dependencies
+            .iter()
+            .map(|dep| {
+                let content = fs::read_to_string(dep).map_err(|e| {
+                    Error::TranspilationError(format!(
+                        "Failed to read dependency {}: {}",
+                        dep.display(),
+                        e
+                    ))
+                })?;
+                Ok((dep.clone(), Self::calculate_hash(&content)))
+            })
+            .collect()
+    }
+
     /// Calculate SHA-256 hash of content
+    ///
+    /// The digest is stable across runs, machines, and toolchains, so cached
+    /// hashes stay comparable after the cache is reloaded from disk.
     fn calculate_hash(content: &str) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        content.hash(&mut hasher);
-        format!("{:x}", hasher.finish())
-    }
-
-    /// Simple transpilation (reuse logic from Recipe 100-5)
-    fn simple_transpile(&self, python_code: &str) -> Result<String> {
-        // Simplified transpilation for demonstration
-        let mut rust_code = String::from("// Transpiled from Python\n\n");
-
-        for line in python_code.lines() {
-            let trimmed = line.trim();
-
-            if trimmed.starts_with("def ") {
-                // Convert function definition
-                let fn_part = trimmed.strip_prefix("def ").unwrap_or("");
-                if let Some(paren_pos) = fn_part.find('(') {
-                    let fn_name = &fn_part[..paren_pos];
-                    rust_code.push_str(&format!("pub fn {}() {{\n", fn_name));
-                    rust_code.push_str("    // Function body\n");
-                    rust_code.push_str("}\n\n");
-                }
-            } else if trimmed.starts_with('#') {
-                // Convert comment
-                let comment = trimmed.strip_prefix('#').unwrap_or("").trim();
-                rust_code.push_str(&format!("// {}\n", comment));
-            }
-        }
+        use sha2::{Digest, Sha256};
 
-        Ok(rust_code)
+        format!("{:x}", Sha256::digest(content.as_bytes()))
     }
 }
 
@@ -457,8 +1260,7 @@ def hello_world():
 def calculate(x, y):
     return x + y
 "#;
-    fs::write(&source_file, python_code)
-        .map_err(|e| Error::Other(format!("Failed to write file: {}", e)))?;
+    fs::write(&source_file, python_code)?;
 
     let mut transpiler = IncrementalTranspiler::new()
         .with_cache_file(cache_file.clone())
@@ -518,8 +1320,7 @@ fn example_2_batch_processing() -> Result<()> {
     for (name, content) in &files {
         let source = temp_dir.join(name);
         let output = temp_dir.join(name.replace(".py", ".rs"));
-        fs::write(&source, content)
-            .map_err(|e| Error::Other(format!("Failed to write file: {}", e)))?;
+        fs::write(&source, content)?;
         file_pairs.push((source, output));
     }
 
@@ -540,10 +1341,10 @@ fn example_2_batch_processing() -> Result<()> {
     println!("  Cache misses: {}", transpiler.metrics().cache_misses);
 
     // Second batch (all cache hits)
-    println!("\n🔧 Second batch transpilation (with cache)...");
+    println!("\n🔧 Second batch transpilation (with cache, 2 threads)...");
     transpiler.reset_metrics();
     transpiler.load_cache()?;
-    transpiler.transpile_batch(file_pairs.clone())?;
+    transpiler.transpile_batch_parallel(file_pairs.clone(), 2)?;
 
     println!("  Files skipped: {}", transpiler.metrics().files_skipped);
     println!("  Cache hits: {}", transpiler.metrics().cache_hits);
@@ -581,8 +1382,7 @@ fn example_3_cache_invalidation() -> Result<()> {
 
     // Initial transpilation
     println!("🔧 Initial transpilation...\n");
-    fs::write(&source_file, "def original(): pass")
-        .map_err(|e| Error::Other(format!("Failed to write file: {}", e)))?;
+    fs::write(&source_file, "def original(): pass")?;
     transpiler.load_cache()?;
     transpiler.transpile_file(&source_file, &output_file)?;
     transpiler.save_cache()?;
@@ -605,8 +1405,7 @@ fn example_3_cache_invalidation() -> Result<()> {
 
     // Modify file (cache invalidation)
     println!("\n🔧 Modifying source file...\n");
-    fs::write(&source_file, "def modified(): pass")
-        .map_err(|e| Error::Other(format!("Failed to write file: {}", e)))?;
+    fs::write(&source_file, "def modified(): pass")?;
     transpiler.reset_metrics();
     transpiler.load_cache()?;
     transpiler.transpile_file(&source_file, &output_file)?;
@@ -655,11 +1454,13 @@ mod tests {
             source_path: PathBuf::from("test.py"),
             output_path: PathBuf::from("test.rs"),
             source_hash: "abc123".to_string(),
-            transpiled_content: "fn test() {}".to_string(),
+            transpiled_content: "fn test() {}".into(),
             timestamp: SystemTime::now(),
+            last_accessed: SystemTime::now(),
             source_language: "Python".to_string(),
             target_language: "Rust".to_string(),
             dependencies: Vec::new(),
+            dependency_hashes: HashMap::new(),
         };
 
         // Same hash, should be valid
@@ -675,11 +1476,13 @@ mod tests {
             source_path: PathBuf::from("test.py"),
             output_path: PathBuf::from("test.rs"),
             source_hash: "abc123".to_string(),
-            transpiled_content: "fn test() {}".to_string(),
+            transpiled_content: "fn test() {}".into(),
             timestamp: SystemTime::now() - Duration::from_secs(7200), // 2 hours ago
+            last_accessed: SystemTime::now(),
             source_language: "Python".to_string(),
             target_language: "Rust".to_string(),
             dependencies: Vec::new(),
+            dependency_hashes: HashMap::new(),
         };
 
         // Should be invalid if max age is 1 hour
@@ -701,11 +1504,13 @@ mod tests {
             source_path: PathBuf::from("test.py"),
             output_path: PathBuf::from("test.rs"),
             source_hash: "hash1".to_string(),
-            transpiled_content: "content".to_string(),
+            transpiled_content: "content".into(),
             timestamp: SystemTime::now(),
+            last_accessed: SystemTime::now(),
             source_language: "Python".to_string(),
             target_language: "Rust".to_string(),
             dependencies: Vec::new(),
+            dependency_hashes: HashMap::new(),
         };
 
         cache.insert(entry.clone());
@@ -729,11 +1534,13 @@ mod tests {
                 source_path: PathBuf::from(format!("file{}.py", i)),
                 output_path: PathBuf::from(format!("file{}.rs", i)),
                 source_hash: format!("hash{}", i),
-                transpiled_content: "content".to_string(),
+                transpiled_content: "content".into(),
                 timestamp: SystemTime::now(),
+                last_accessed: SystemTime::now(),
                 source_language: "Python".to_string(),
                 target_language: "Rust".to_string(),
                 dependencies: Vec::new(),
+                dependency_hashes: HashMap::new(),
             };
             cache.insert(entry);
             thread::sleep(Duration::from_millis(10)); // Ensure different timestamps
@@ -743,6 +1550,91 @@ mod tests {
         assert_eq!(cache.len(), 2);
     }
 
+    #[test]
+    fn test_cache_eviction_is_lru() {
+        let mut cache = TranspilationCache::new().with_max_entries(3);
+        let base = SystemTime::now() - Duration::from_secs(60);
+
+        for (i, name) in ["old", "middle", "new"].into_iter().enumerate() {
+            let inserted = base + Duration::from_secs(i as u64);
+            cache.insert(CacheEntry {
+                source_path: PathBuf::from(format!("{name}.py")),
+                output_path: PathBuf::from(format!("{name}.rs")),
+                source_hash: format!("hash_{name}"),
+                transpiled_content: "content".into(),
+                timestamp: inserted,
+                last_accessed: inserted,
+                source_language: "Python".to_string(),
+                target_language: "Rust".to_string(),
+                dependencies: Vec::new(),
+                dependency_hashes: HashMap::new(),
+            });
+        }
+
+        // Touch the oldest entry so the untouched middle one becomes LRU
+        assert!(cache.get(Path::new("old.py"), "hash_old").is_some());
+
+        cache.insert(CacheEntry {
+            source_path: PathBuf::from("fourth.py"),
+            output_path: PathBuf::from("fourth.rs"),
+            source_hash: "hash_fourth".to_string(),
+            transpiled_content: "content".into(),
+            timestamp: SystemTime::now(),
+            last_accessed: SystemTime::now(),
+            source_language: "Python".to_string(),
+            target_language: "Rust".to_string(),
+            dependencies: Vec::new(),
+            dependency_hashes: HashMap::new(),
+        });
+
+        assert_eq!(cache.len(), 3);
+        assert!(cache.get(Path::new("old.py"), "hash_old").is_some());
+        assert!(cache.get(Path::new("middle.py"), "hash_middle").is_none());
+        assert!(cache.get(Path::new("new.py"), "hash_new").is_some());
+    }
+
+    #[test]
+    fn test_cache_eviction_stats() {
+        use tempfile::TempDir;
+
+        let mut cache = TranspilationCache::new().with_max_entries(3);
+        assert_eq!(cache.eviction_count(), 0);
+        assert_eq!(cache.stats().oldest_timestamp, None);
+
+        let first_timestamp = SystemTime::now() - Duration::from_secs(60);
+        for i in 0..8u64 {
+            cache.insert(CacheEntry {
+                source_path: PathBuf::from(format!("file{i}.py")),
+                output_path: PathBuf::from(format!("file{i}.rs")),
+                source_hash: format!("hash{i}"),
+                transpiled_content: "content".into(),
+                timestamp: first_timestamp + Duration::from_secs(i),
+                last_accessed: first_timestamp + Duration::from_secs(i),
+                source_language: "Python".to_string(),
+                target_language: "Rust".to_string(),
+                dependencies: Vec::new(),
+                dependency_hashes: HashMap::new(),
+            });
+        }
+
+        // 8 inserts into a 3-entry cache overflow by 5
+        let stats = cache.stats();
+        assert_eq!(cache.eviction_count(), 5);
+        assert_eq!(stats.len, 3);
+        assert_eq!(stats.max_entries, 3);
+        assert_eq!(stats.evictions, 5);
+        assert_eq!(
+            stats.oldest_timestamp,
+            Some(first_timestamp + Duration::from_secs(5))
+        );
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache_file = temp_dir.path().join("stats_cache.json");
+        cache.save_to_file(&cache_file).unwrap();
+        let loaded = TranspilationCache::load_from_file(&cache_file).unwrap();
+        assert_eq!(loaded.stats(), stats);
+    }
+
     #[test]
     fn test_cache_clear() {
         let mut cache = TranspilationCache::new();
@@ -751,11 +1643,13 @@ mod tests {
             source_path: PathBuf::from("test.py"),
             output_path: PathBuf::from("test.rs"),
             source_hash: "hash".to_string(),
-            transpiled_content: "content".to_string(),
+            transpiled_content: "content".into(),
             timestamp: SystemTime::now(),
+            last_accessed: SystemTime::now(),
             source_language: "Python".to_string(),
             target_language: "Rust".to_string(),
             dependencies: Vec::new(),
+            dependency_hashes: HashMap::new(),
         };
 
         cache.insert(entry);
@@ -778,11 +1672,13 @@ mod tests {
             source_path: PathBuf::from("test.py"),
             output_path: PathBuf::from("test.rs"),
             source_hash: "hash123".to_string(),
-            transpiled_content: "fn test() {}".to_string(),
+            transpiled_content: "fn test() {}".into(),
             timestamp: SystemTime::now(),
+            last_accessed: SystemTime::now(),
             source_language: "Python".to_string(),
             target_language: "Rust".to_string(),
             dependencies: Vec::new(),
+            dependency_hashes: HashMap::new(),
         };
         cache.insert(entry);
 
@@ -790,7 +1686,7 @@ mod tests {
         assert!(cache_file.exists());
 
         // Load cache
-        let loaded_cache = TranspilationCache::load_from_file(&cache_file).unwrap();
+        let mut loaded_cache = TranspilationCache::load_from_file(&cache_file).unwrap();
         assert_eq!(loaded_cache.len(), 1);
         assert!(loaded_cache
             .get(&PathBuf::from("test.py"), "hash123")
@@ -831,6 +1727,163 @@ mod tests {
         assert_eq!(transpiler.metrics().files_transpiled, 1);
     }
 
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_cache_async_round_trip() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cache.json");
+
+        let mut cache = TranspilationCache::new().with_max_entries(5);
+        cache.insert(CacheEntry {
+            source_path: PathBuf::from("a.py"),
+            output_path: PathBuf::from("a.rs"),
+            source_hash: "hash-a".to_string(),
+            transpiled_content: "fn a() {}".into(),
+            timestamp: SystemTime::now(),
+            last_accessed: SystemTime::now(),
+            source_language: "Python".to_string(),
+            target_language: "Rust".to_string(),
+            dependencies: vec![],
+            dependency_hashes: HashMap::new(),
+        });
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let loaded = runtime.block_on(async {
+            cache.save_to_file_async(&path).await.unwrap();
+            TranspilationCache::load_from_file_async(&path)
+                .await
+                .unwrap()
+        });
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.stats().max_entries, 5);
+        assert!(loaded.peek(Path::new("a.py"), "hash-a").is_some());
+
+        // The async and sync formats are interchangeable
+        assert_eq!(TranspilationCache::load_from_file(&path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_transpile_glob_mirrors_tree() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let src_root = temp_dir.path().join("src");
+        let out_root = temp_dir.path().join("out");
+        fs::create_dir_all(src_root.join("pkg/sub")).unwrap();
+        fs::write(src_root.join("main.py"), "def main(): pass").unwrap();
+        fs::write(src_root.join("pkg/util.py"), "def util(): pass").unwrap();
+        fs::write(src_root.join("pkg/sub/deep.py"), "def deep(): pass").unwrap();
+        fs::write(src_root.join("pkg/README.md"), "# docs").unwrap();
+
+        let mut transpiler = IncrementalTranspiler::new();
+        transpiler
+            .transpile_glob(&src_root, &out_root, "*.py")
+            .unwrap();
+
+        assert!(out_root.join("main.rs").exists());
+        assert!(out_root.join("pkg/util.rs").exists());
+        let deep = fs::read_to_string(out_root.join("pkg/sub/deep.rs")).unwrap();
+        assert!(deep.contains("pub fn deep() {"));
+        assert!(!out_root.join("pkg/README.rs").exists());
+        assert_eq!(transpiler.metrics().files_transpiled, 3);
+
+        // A second pass is served entirely from the cache
+        transpiler
+            .transpile_glob(&src_root, &out_root, "*.py")
+            .unwrap();
+        assert_eq!(transpiler.metrics().cache_hits, 3);
+
+        // Patterns match the path relative to src_root
+        let mut pkg_only = IncrementalTranspiler::new();
+        pkg_only
+            .transpile_glob(&src_root, &temp_dir.path().join("pkg_out"), "pkg/*.py")
+            .unwrap();
+        assert_eq!(pkg_only.metrics().files_transpiled, 2);
+
+        assert!(transpiler
+            .transpile_glob(&src_root, &out_root, "[")
+            .is_err());
+    }
+
+    #[test]
+    fn test_dry_run_skips_writes() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("test.py");
+        let output = temp_dir.path().join("test.rs");
+        let cache_file = temp_dir.path().join("cache.json");
+        fs::write(&source, "def test(): pass").unwrap();
+
+        let mut transpiler = IncrementalTranspiler::new()
+            .with_cache_file(cache_file.clone())
+            .with_dry_run(true);
+
+        let (preview, cache_hit) = transpiler.transpile_file_preview(&source).unwrap();
+        assert!(!cache_hit);
+        assert!(preview.contains("pub fn test() {"));
+
+        transpiler.transpile_file(&source, &output).unwrap();
+        transpiler.transpile_file(&source, &output).unwrap();
+        transpiler.save_cache().unwrap();
+
+        assert_eq!(transpiler.metrics().cache_misses, 1);
+        assert_eq!(transpiler.metrics().cache_hits, 1);
+        assert_eq!(transpiler.metrics().files_transpiled, 1);
+        assert!(!output.exists());
+        assert!(!cache_file.exists());
+
+        let (cached, cache_hit) = transpiler.transpile_file_preview(&source).unwrap();
+        assert!(cache_hit);
+        assert_eq!(cached, preview);
+        assert_eq!(transpiler.metrics().total_files, 2);
+    }
+
+    #[test]
+    fn test_incremental_transpiler_uses_registry() {
+        use batuta_cookbook::transpiler::Transpiler;
+        use tempfile::TempDir;
+
+        struct Echo;
+
+        impl Transpiler for Echo {
+            fn source_language(&self) -> Language {
+                Language::Ruby
+            }
+
+            fn target_language(&self) -> Language {
+                Language::Rust
+            }
+
+            fn transpile(&self, src: &str) -> Result<String> {
+                Ok(format!("// echo\n{src}"))
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("test.rb");
+        let output = temp_dir.path().join("test.rs");
+        fs::write(&source, "puts 1").unwrap();
+
+        let mut registry = TranspilerRegistry::new();
+        registry.register(Echo);
+        let mut transpiler = IncrementalTranspiler::new()
+            .with_registry(registry)
+            .with_languages(Language::Ruby, Language::Rust);
+        transpiler.transpile_file(&source, &output).unwrap();
+        assert_eq!(fs::read_to_string(&output).unwrap(), "// echo\nputs 1");
+
+        // The default Python -> Rust backend is not in this registry
+        let mut unsupported = IncrementalTranspiler::new().with_registry(TranspilerRegistry::new());
+        let err = unsupported.transpile_file(&source, &output).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedLanguage(_)));
+    }
+
     #[test]
     fn test_incremental_transpiler_cache_hit() {
         use tempfile::TempDir;
@@ -874,6 +1927,67 @@ mod tests {
         assert_eq!(transpiler.metrics().cache_misses, 2);
     }
 
+    #[test]
+    fn test_cache_is_valid_with_deps() {
+        let mut cache = TranspilationCache::new();
+        let dep_hashes: HashMap<PathBuf, String> =
+            [(PathBuf::from("b.py"), "hash_b".to_string())].into();
+
+        cache.insert(CacheEntry {
+            source_path: PathBuf::from("a.py"),
+            output_path: PathBuf::from("a.rs"),
+            source_hash: "hash_a".to_string(),
+            transpiled_content: "content".into(),
+            timestamp: SystemTime::now(),
+            last_accessed: SystemTime::now(),
+            source_language: "Python".to_string(),
+            target_language: "Rust".to_string(),
+            dependencies: vec![PathBuf::from("b.py")],
+            dependency_hashes: dep_hashes.clone(),
+        });
+
+        let a = PathBuf::from("a.py");
+        assert!(cache.is_valid_with_deps(&a, "hash_a", &dep_hashes));
+        assert!(!cache.is_valid_with_deps(&a, "changed", &dep_hashes));
+
+        let changed: HashMap<PathBuf, String> =
+            [(PathBuf::from("b.py"), "hash_b2".to_string())].into();
+        assert!(!cache.is_valid_with_deps(&a, "hash_a", &changed));
+        assert!(!cache.is_valid_with_deps(&a, "hash_a", &HashMap::new()));
+    }
+
+    #[test]
+    fn test_dependency_change_invalidates_dependent() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source_a = temp_dir.path().join("a.py");
+        let output_a = temp_dir.path().join("a.rs");
+        let source_b = temp_dir.path().join("b.py");
+
+        fs::write(&source_a, "def uses_b(): pass").unwrap();
+        fs::write(&source_b, "def helper(): pass").unwrap();
+        let deps = vec![source_b.clone()];
+
+        let mut transpiler = IncrementalTranspiler::new();
+        transpiler
+            .transpile_file_with_deps(&source_a, &output_a, &deps)
+            .unwrap();
+        transpiler
+            .transpile_file_with_deps(&source_a, &output_a, &deps)
+            .unwrap();
+        assert_eq!(transpiler.metrics().cache_misses, 1);
+        assert_eq!(transpiler.metrics().cache_hits, 1);
+
+        // Modify only the dependency
+        fs::write(&source_b, "def helper_v2(): pass").unwrap();
+        transpiler
+            .transpile_file_with_deps(&source_a, &output_a, &deps)
+            .unwrap();
+        assert_eq!(transpiler.metrics().cache_misses, 2);
+        assert_eq!(transpiler.metrics().cache_hits, 1);
+    }
+
     #[test]
     fn test_hash_calculation() {
         let content1 = "hello world";
@@ -888,6 +2002,18 @@ mod tests {
         assert_ne!(hash1, hash3); // Different content = different hash
     }
 
+    #[test]
+    fn test_hash_is_stable_sha256() {
+        assert_eq!(
+            IncrementalTranspiler::calculate_hash("hello world"),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+        assert_eq!(
+            IncrementalTranspiler::calculate_hash(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
     #[test]
     fn test_batch_transpilation() {
         use tempfile::TempDir;
@@ -919,4 +2045,239 @@ mod tests {
         transpiler.transpile_batch(files).unwrap();
         assert_eq!(transpiler.metrics().cache_hits, 2);
     }
+
+    #[test]
+    fn test_cache_migrates_version_1_file() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache_file = temp_dir.path().join("v1_cache.json");
+        // Written before caches carried a format_version
+        let v1 = r#"{
+            "entries": {
+                "test.py": {
+                    "source_path": "test.py",
+                    "output_path": "test.rs",
+                    "source_hash": "hash123",
+                    "transpiled_content": "fn test() {}",
+                    "timestamp": {"secs_since_epoch": 4102444800, "nanos_since_epoch": 0},
+                    "source_language": "Python",
+                    "target_language": "Rust",
+                    "dependencies": []
+                }
+            },
+            "max_age_secs": 86400,
+            "max_entries": 10000
+        }"#;
+        fs::write(&cache_file, v1).unwrap();
+
+        let mut cache = TranspilationCache::load_from_file(&cache_file).unwrap();
+
+        assert_eq!(cache.format_version, CACHE_FORMAT_VERSION);
+        let entry = cache.get(Path::new("test.py"), "hash123").unwrap();
+        assert_eq!(&*entry.transpiled_content, "fn test() {}");
+
+        // Saving writes the current format
+        cache.save_to_file(&cache_file).unwrap();
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&cache_file).unwrap()).unwrap();
+        assert_eq!(saved["format_version"], CACHE_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_cache_rejects_unknown_version() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache_file = temp_dir.path().join("future_cache.json");
+        fs::write(
+            &cache_file,
+            r#"{"format_version": 99, "entries": {}, "max_age_secs": 1, "max_entries": 1}"#,
+        )
+        .unwrap();
+
+        let err = TranspilationCache::load_from_file(&cache_file)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("Unsupported cache format version 99"));
+        assert!(err.contains("delete the cache file"));
+    }
+
+    #[test]
+    fn test_cache_shares_identical_content() {
+        use tempfile::TempDir;
+
+        let boilerplate = "pub fn generated() {}";
+        let entry = |name: &str| CacheEntry {
+            source_path: PathBuf::from(format!("{name}.py")),
+            output_path: PathBuf::from(format!("{name}.rs")),
+            source_hash: "same".to_string(),
+            transpiled_content: boilerplate.into(),
+            timestamp: SystemTime::now(),
+            last_accessed: SystemTime::now(),
+            source_language: "Python".to_string(),
+            target_language: "Rust".to_string(),
+            dependencies: Vec::new(),
+            dependency_hashes: HashMap::new(),
+        };
+        let names = ["a", "b", "c"];
+
+        let mut cache = TranspilationCache::new();
+        for name in names {
+            cache.insert(entry(name));
+        }
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.content_blob_count(), 1);
+        for name in names {
+            let path = PathBuf::from(format!("{name}.py"));
+            let cached = cache.get(&path, "same").unwrap();
+            assert_eq!(&*cached.transpiled_content, boilerplate);
+        }
+
+        // The file stores the content once, and loading shares it again
+        let temp_dir = TempDir::new().unwrap();
+        let cache_file = temp_dir.path().join("cache.json");
+        cache.save_to_file(&cache_file).unwrap();
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&cache_file).unwrap()).unwrap();
+        assert_eq!(saved["contents"].as_object().unwrap().len(), 1);
+
+        let mut loaded = TranspilationCache::load_from_file(&cache_file).unwrap();
+        assert_eq!(loaded.content_blob_count(), 1);
+        let cached = loaded.get(Path::new("b.py"), "same").unwrap();
+        assert_eq!(&*cached.transpiled_content, boilerplate);
+
+        // Content is released once no entry refers to it
+        for name in names {
+            loaded.remove(&PathBuf::from(format!("{name}.py")));
+        }
+        assert_eq!(loaded.content_blob_count(), 0);
+
+        // Replacing an entry releases its old content
+        let mut changed = entry("a");
+        changed.transpiled_content = "pub fn edited() {}".into();
+        cache.insert(changed);
+        assert_eq!(cache.content_blob_count(), 2);
+        cache.insert(entry("a"));
+        assert_eq!(cache.content_blob_count(), 1);
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn test_watch_transpiles_changed_file_once() {
+        use notify::event::{DataChange, EventKind, ModifyKind};
+        use notify::Event;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let src_root = temp_dir.path().join("src");
+        let out_root = temp_dir.path().join("out");
+        fs::create_dir_all(src_root.join("pkg")).unwrap();
+        let source = src_root.join("pkg/app.py");
+        fs::write(&source, "def app(): pass").unwrap();
+        let notes = src_root.join("notes.md");
+        fs::write(&notes, "# notes").unwrap();
+
+        // Rapid successive saves plus an unrelated file, as one burst
+        let (sender, events) = std::sync::mpsc::channel();
+        let modified = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+        for path in [&source, &source, &notes] {
+            sender
+                .send(Ok(Event::new(modified).add_path(path.clone())))
+                .unwrap();
+        }
+        drop(sender);
+
+        let mut transpiler = IncrementalTranspiler::new();
+        transpiler
+            .process_watch_events(&events, &src_root, &out_root)
+            .unwrap();
+
+        assert_eq!(transpiler.metrics().files_transpiled, 1);
+        assert_eq!(transpiler.metrics().total_files, 1);
+        let output = fs::read_to_string(out_root.join("pkg/app.rs")).unwrap();
+        assert!(output.contains("pub fn app() {"));
+    }
+
+    #[test]
+    fn test_parallel_batch_matches_sequential() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let files: Vec<(PathBuf, PathBuf)> = (0..24)
+            .map(|i| {
+                let source = temp_dir.path().join(format!("mod{i}.py"));
+                fs::write(&source, format!("def f{i}(): pass")).unwrap();
+                (source, temp_dir.path().join(format!("mod{i}.rs")))
+            })
+            .collect();
+
+        let mut parallel = IncrementalTranspiler::new();
+        parallel.transpile_batch_parallel(files.clone(), 4).unwrap();
+        assert_eq!(parallel.metrics().files_transpiled, 24);
+        assert_eq!(parallel.metrics().cache_misses, 24);
+        assert_eq!(parallel.cache.len(), 24);
+        let output = fs::read_to_string(temp_dir.path().join("mod7.rs")).unwrap();
+        assert!(output.contains("pub fn f7() {"));
+
+        // No insert was lost: the second pass is served entirely from cache
+        parallel.transpile_batch_parallel(files.clone(), 4).unwrap();
+        assert_eq!(parallel.metrics().cache_hits, 24);
+        assert_eq!(parallel.metrics().files_transpiled, 24);
+
+        let mut sequential = IncrementalTranspiler::new();
+        sequential.transpile_batch(files.clone()).unwrap();
+        sequential.transpile_batch(files).unwrap();
+        let (p, s) = (parallel.metrics(), sequential.metrics());
+        assert_eq!(
+            (p.total_files, p.cache_hits, p.cache_misses),
+            (s.total_files, s.cache_hits, s.cache_misses)
+        );
+        assert_eq!(
+            (p.files_transpiled, p.files_skipped, p.time_saved_ms),
+            (s.files_transpiled, s.files_skipped, s.time_saved_ms)
+        );
+    }
+
+    #[test]
+    fn test_transpile_in_dependency_order() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = |name: &str| temp_dir.path().join(name);
+        // a imports b, which imports c
+        fs::write(path("a.py"), "import b\n\ndef main(): pass").unwrap();
+        fs::write(path("b.py"), "from c import helper\n\ndef mid(): pass").unwrap();
+        fs::write(path("c.py"), "def helper(): pass").unwrap();
+
+        let sources = vec![path("a.py"), path("b.py"), path("c.py")];
+        let graph = DependencyGraph::from_sources(&sources).unwrap();
+        assert_eq!(graph.dependencies(&path("a.py")), vec![path("b.py")]);
+        assert_eq!(
+            graph.topological_order().unwrap(),
+            vec![path("c.py"), path("b.py"), path("a.py")]
+        );
+
+        let files = sources
+            .iter()
+            .map(|source| (source.clone(), source.with_extension("rs")))
+            .collect();
+        let mut transpiler = IncrementalTranspiler::new();
+        let order = transpiler.transpile_in_dependency_order(files).unwrap();
+
+        assert_eq!(order, vec![path("c.py"), path("b.py"), path("a.py")]);
+        assert_eq!(transpiler.metrics().files_transpiled, 3);
+        assert!(path("a.rs").exists());
+    }
+
+    #[test]
+    fn test_dependency_cycle_is_an_error() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency(Path::new("a.py"), Path::new("b.py"));
+        graph.add_dependency(Path::new("b.py"), Path::new("c.py"));
+        graph.add_dependency(Path::new("c.py"), Path::new("a.py"));
+
+        let err = graph.topological_order().unwrap_err().to_string();
+
+        assert!(err.contains("Dependency cycle: a.py -> b.py -> c.py -> a.py"));
+    }
 }
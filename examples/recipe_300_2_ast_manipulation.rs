@@ -17,19 +17,22 @@
 //! - Tree transformation algorithms
 //! - Code generation from AST
 //! - Pattern matching on AST structures
+//! - Exchanging ASTs with external tools as JSON
 //!
 //! ## Examples
-//! This file demonstrates three approaches:
+//! This file demonstrates four approaches:
 //! 1. Basic AST parsing and traversal
 //! 2. AST transformations (refactoring, optimization)
 //! 3. Code generation from modified AST
+//! 4. Parsing source text back into an AST (round-trip)
 
 use batuta_cookbook::Result;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 /// AST node types
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AstNode {
     /// Program root
     Program(Vec<AstNode>),
@@ -69,7 +72,7 @@ pub enum AstNode {
 }
 
 /// Binary operators
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BinaryOperator {
     Add,
     Subtract,
@@ -101,7 +104,7 @@ impl fmt::Display for BinaryOperator {
 }
 
 /// Literal value types
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LiteralValue {
     Integer(i64),
     Float(f64),
@@ -110,6 +113,32 @@ pub enum LiteralValue {
     Null,
 }
 
+impl AstNode {
+    /// Serialize the tree to JSON for external tools
+    ///
+    /// Nodes use serde's externally tagged enum layout, e.g.
+    /// `{"Identifier":"x"}`. Floats round-trip exactly; NaN and infinities
+    /// have no JSON representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree cannot be serialized.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to serialize AST: {e}")))
+    }
+
+    /// Deserialize a tree produced by [`AstNode::to_json`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid serialized AST.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| batuta_cookbook::Error::Other(format!("Invalid AST JSON: {e}")))
+    }
+}
+
 /// AST visitor trait for traversing nodes
 pub trait AstVisitor {
     fn visit_node(&mut self, node: &AstNode) -> Result<()> {
@@ -163,30 +192,141 @@ pub trait AstVisitor {
     }
 }
 
+/// Mutating AST visitor for in-place transformations
+///
+/// Override `visit_node_mut` to rewrite nodes and call `walk_node_mut` to
+/// continue into children.
+pub trait AstMutVisitor {
+    /// Visit `node`, mutating it in place
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error produced while visiting the tree.
+    fn visit_node_mut(&mut self, node: &mut AstNode) -> Result<()> {
+        walk_node_mut(self, node)
+    }
+}
+
+/// Visit every direct child of `node` with `visitor`
+///
+/// # Errors
+///
+/// Returns the first error produced by the visitor.
+pub fn walk_node_mut<V: AstMutVisitor + ?Sized>(visitor: &mut V, node: &mut AstNode) -> Result<()> {
+    match node {
+        AstNode::Program(nodes) | AstNode::Function { body: nodes, .. } => {
+            for n in nodes {
+                visitor.visit_node_mut(n)?;
+            }
+        }
+        AstNode::VarDecl { value, .. } | AstNode::Assignment { value, .. } => {
+            visitor.visit_node_mut(value)?;
+        }
+        AstNode::BinaryOp { left, right, .. } => {
+            visitor.visit_node_mut(left)?;
+            visitor.visit_node_mut(right)?;
+        }
+        AstNode::Call { args, .. } => {
+            for arg in args {
+                visitor.visit_node_mut(arg)?;
+            }
+        }
+        AstNode::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            visitor.visit_node_mut(condition)?;
+            for n in then_branch {
+                visitor.visit_node_mut(n)?;
+            }
+            if let Some(else_nodes) = else_branch {
+                for n in else_nodes {
+                    visitor.visit_node_mut(n)?;
+                }
+            }
+        }
+        AstNode::Return(expr) => {
+            visitor.visit_node_mut(expr)?;
+        }
+        AstNode::Identifier(_) | AstNode::Literal(_) => {}
+    }
+    Ok(())
+}
+
+/// Callback invoked with every node an [`AstAnalyzer`] visits and its depth
+pub type NodeHook<'a> = Box<dyn FnMut(&AstNode, usize) + 'a>;
+
 /// AST analyzer for collecting statistics
-pub struct AstAnalyzer {
+///
+/// Custom metrics can be gathered without re-implementing the traversal by
+/// registering hooks with [`AstAnalyzer::with_node_hook`].
+pub struct AstAnalyzer<'a> {
     pub function_count: usize,
     pub var_count: usize,
     pub call_count: usize,
     pub max_depth: usize,
+    /// Number of `If` nodes
+    pub branch_count: usize,
+    /// Sum of per-function cyclomatic complexity (1 + branches in each function)
+    pub cyclomatic_complexity: usize,
+    /// Cyclomatic complexity keyed by function name
+    pub per_function_complexity: HashMap<String, usize>,
+    /// Functions enclosing the node being visited
+    function_stack: Vec<String>,
+    /// Caller-supplied callbacks run on every visited node
+    node_hooks: Vec<NodeHook<'a>>,
 }
 
-impl AstAnalyzer {
+impl<'a> AstAnalyzer<'a> {
     pub fn new() -> Self {
         Self {
             function_count: 0,
             var_count: 0,
             call_count: 0,
             max_depth: 0,
+            branch_count: 0,
+            cyclomatic_complexity: 0,
+            per_function_complexity: HashMap::new(),
+            function_stack: Vec::new(),
+            node_hooks: Vec::new(),
         }
     }
 
+    /// Register a callback invoked for every visited node (pre-order) with
+    /// its depth, alongside the built-in counters
+    #[must_use]
+    pub fn with_node_hook(mut self, hook: NodeHook<'a>) -> Self {
+        self.node_hooks.push(hook);
+        self
+    }
+
+    /// Function with the highest cyclomatic complexity, if any
+    #[must_use]
+    pub fn most_complex_function(&self) -> Option<(&str, usize)> {
+        self.per_function_complexity
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(name, complexity)| (name.as_str(), *complexity))
+    }
+
+    fn add_complexity(&mut self, function: &str) {
+        self.cyclomatic_complexity += 1;
+        *self
+            .per_function_complexity
+            .entry(function.to_string())
+            .or_insert(0) += 1;
+    }
+
     pub fn analyze(&mut self, ast: &AstNode) -> Result<()> {
         self.visit_with_depth(ast, 0)
     }
 
     fn visit_with_depth(&mut self, node: &AstNode, depth: usize) -> Result<()> {
         self.max_depth = self.max_depth.max(depth);
+        for hook in &mut self.node_hooks {
+            hook(node, depth);
+        }
 
         match node {
             AstNode::Program(nodes) => {
@@ -194,11 +334,14 @@ impl AstAnalyzer {
                     self.visit_with_depth(n, depth + 1)?;
                 }
             }
-            AstNode::Function { body, .. } => {
+            AstNode::Function { name, body, .. } => {
                 self.function_count += 1;
+                self.add_complexity(name);
+                self.function_stack.push(name.clone());
                 for n in body {
                     self.visit_with_depth(n, depth + 1)?;
                 }
+                self.function_stack.pop();
             }
             AstNode::VarDecl { value, .. } => {
                 self.var_count += 1;
@@ -222,6 +365,10 @@ impl AstAnalyzer {
                 then_branch,
                 else_branch,
             } => {
+                self.branch_count += 1;
+                if let Some(function) = self.function_stack.last().cloned() {
+                    self.add_complexity(&function);
+                }
                 self.visit_with_depth(condition, depth + 1)?;
                 for n in then_branch {
                     self.visit_with_depth(n, depth + 1)?;
@@ -241,7 +388,7 @@ impl AstAnalyzer {
     }
 }
 
-impl Default for AstAnalyzer {
+impl Default for AstAnalyzer<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -251,12 +398,15 @@ impl Default for AstAnalyzer {
 pub struct AstTransformer {
     /// Variable rename map (old -> new)
     renames: HashMap<String, String>,
+    /// Per-function rename maps (function -> old -> new)
+    scoped_renames: HashMap<String, HashMap<String, String>>,
 }
 
 impl AstTransformer {
     pub fn new() -> Self {
         Self {
             renames: HashMap::new(),
+            scoped_renames: HashMap::new(),
         }
     }
 
@@ -265,48 +415,24 @@ impl AstTransformer {
         self.renames.insert(old_name, new_name);
     }
 
+    /// Add a rename rule for a variable declared in `function_name`
+    ///
+    /// Only the parameter or `let` binding declared in that function (and
+    /// references resolving to it) are renamed; same-named variables in
+    /// other functions are left untouched.
+    pub fn add_scoped_rename(&mut self, function_name: String, old_name: String, new_name: String) {
+        self.scoped_renames
+            .entry(function_name)
+            .or_default()
+            .insert(old_name, new_name);
+    }
+
     /// Transform AST applying all rules
     pub fn transform(&self, node: AstNode) -> AstNode {
-        match node {
-            AstNode::Program(nodes) => {
-                AstNode::Program(nodes.into_iter().map(|n| self.transform(n)).collect())
-            }
-            AstNode::Function { name, params, body } => AstNode::Function {
-                name: self.rename_if_needed(&name),
-                params: params.iter().map(|p| self.rename_if_needed(p)).collect(),
-                body: body.into_iter().map(|n| self.transform(n)).collect(),
-            },
-            AstNode::VarDecl { name, value } => AstNode::VarDecl {
-                name: self.rename_if_needed(&name),
-                value: Box::new(self.transform(*value)),
-            },
-            AstNode::Assignment { target, value } => AstNode::Assignment {
-                target: self.rename_if_needed(&target),
-                value: Box::new(self.transform(*value)),
-            },
-            AstNode::BinaryOp { op, left, right } => AstNode::BinaryOp {
-                op,
-                left: Box::new(self.transform(*left)),
-                right: Box::new(self.transform(*right)),
-            },
-            AstNode::Call { function, args } => AstNode::Call {
-                function: self.rename_if_needed(&function),
-                args: args.into_iter().map(|a| self.transform(a)).collect(),
-            },
-            AstNode::If {
-                condition,
-                then_branch,
-                else_branch,
-            } => AstNode::If {
-                condition: Box::new(self.transform(*condition)),
-                then_branch: then_branch.into_iter().map(|n| self.transform(n)).collect(),
-                else_branch: else_branch
-                    .map(|nodes| nodes.into_iter().map(|n| self.transform(n)).collect()),
-            },
-            AstNode::Return(expr) => AstNode::Return(Box::new(self.transform(*expr))),
-            AstNode::Identifier(name) => AstNode::Identifier(self.rename_if_needed(&name)),
-            AstNode::Literal(_) => node,
-        }
+        let mut node = node;
+        // Renaming never fails, so the visitor result carries no information
+        let _ = RenamePass::new(self).visit_node_mut(&mut node);
+        node
     }
 
     fn rename_if_needed(&self, name: &str) -> String {
@@ -323,10 +449,101 @@ impl Default for AstTransformer {
     }
 }
 
+impl AstMutVisitor for AstTransformer {
+    fn visit_node_mut(&mut self, node: &mut AstNode) -> Result<()> {
+        RenamePass::new(self).visit_node_mut(node)
+    }
+}
+
+/// Variables declared in one function body and the scoped renames that apply to them
+struct Scope<'a> {
+    declared: HashSet<String>,
+    renames: Option<&'a HashMap<String, String>>,
+}
+
+/// In-place rename pass borrowing the transformer's rules
+struct RenamePass<'a> {
+    transformer: &'a AstTransformer,
+    scopes: Vec<Scope<'a>>,
+}
+
+impl<'a> RenamePass<'a> {
+    fn new(transformer: &'a AstTransformer) -> Self {
+        Self {
+            transformer,
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Rename a variable reference, honouring the innermost scope that declares it
+    fn resolve(&self, name: &str) -> String {
+        let declaring_scope = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.declared.contains(name));
+        if let Some(new_name) = declaring_scope
+            .and_then(|scope| scope.renames)
+            .and_then(|renames| renames.get(name))
+        {
+            return new_name.clone();
+        }
+        self.transformer.rename_if_needed(name)
+    }
+
+    /// Record a declaration in the current scope and return its new name
+    fn declare(&mut self, name: &str) -> String {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.declared.insert(name.to_string());
+        }
+        self.resolve(name)
+    }
+}
+
+impl AstMutVisitor for RenamePass<'_> {
+    fn visit_node_mut(&mut self, node: &mut AstNode) -> Result<()> {
+        let transformer = self.transformer;
+        match node {
+            AstNode::Function { name, params, body } => {
+                self.scopes.push(Scope {
+                    declared: HashSet::new(),
+                    renames: transformer.scoped_renames.get(name.as_str()),
+                });
+                *name = transformer.rename_if_needed(name);
+                for param in params.iter_mut() {
+                    *param = self.declare(param);
+                }
+                let result = body.iter_mut().try_for_each(|n| self.visit_node_mut(n));
+                self.scopes.pop();
+                return result;
+            }
+            AstNode::VarDecl { name, value } => {
+                // The initializer sees the previous binding, not the one being declared
+                self.visit_node_mut(value)?;
+                *name = self.declare(name);
+                return Ok(());
+            }
+            AstNode::Identifier(name) => {
+                *name = self.resolve(name);
+            }
+            AstNode::Assignment { target, .. } => {
+                *target = self.resolve(target);
+            }
+            AstNode::Call { function, .. } => {
+                *function = transformer.rename_if_needed(function);
+            }
+            _ => {}
+        }
+        walk_node_mut(self, node)
+    }
+}
+
 /// AST code generator
 pub struct CodeGenerator {
     indent_level: usize,
     indent_size: usize,
+    /// Wrap expressions that would make a line longer than this
+    max_line_width: Option<usize>,
 }
 
 impl CodeGenerator {
@@ -334,9 +551,21 @@ impl CodeGenerator {
         Self {
             indent_level: 0,
             indent_size: 4,
+            max_line_width: None,
         }
     }
 
+    /// Wrap long call-argument lists and binary chains onto indented
+    /// continuation lines when a line would exceed `cols` columns
+    ///
+    /// Expressions that fit stay on one line. Identifiers and literals are
+    /// never split, so a single very long token can still exceed the width.
+    #[must_use]
+    pub fn with_max_line_width(mut self, cols: usize) -> Self {
+        self.max_line_width = Some(cols);
+        self
+    }
+
     pub fn generate(&mut self, ast: &AstNode) -> String {
         self.generate_node(ast)
     }
@@ -349,82 +578,77 @@ impl CodeGenerator {
                 .collect::<Vec<_>>()
                 .join("\n"),
             AstNode::Function { name, params, body } => {
-                let indent = self.indent();
                 let params_str = params.join(", ");
-                let mut result = format!("{}fn {}({}) {{\n", indent, name, params_str);
-                self.indent_level += 1;
-                for stmt in body {
-                    result.push_str(&self.generate_node(stmt));
-                    result.push('\n');
-                }
-                self.indent_level -= 1;
-                result.push_str(&format!("{}}}", indent));
-                result
-            }
-            AstNode::VarDecl { name, value } => {
                 format!(
-                    "{}let {} = {};",
+                    "{}fn {}({}) {}",
                     self.indent(),
                     name,
-                    self.generate_expr(value)
+                    params_str,
+                    self.generate_block(body)
                 )
             }
+            AstNode::VarDecl { name, value } => {
+                let prefix = format!("{}let {} = ", self.indent(), name);
+                let value = self.statement_expr(value, prefix.len(), 1);
+                format!("{prefix}{value};")
+            }
             AstNode::Assignment { target, value } => {
-                format!(
-                    "{}{} = {};",
-                    self.indent(),
-                    target,
-                    self.generate_expr(value)
-                )
+                let prefix = format!("{}{} = ", self.indent(), target);
+                let value = self.statement_expr(value, prefix.len(), 1);
+                format!("{prefix}{value};")
             }
-            AstNode::Call { function, args } => {
-                let args_str = args
-                    .iter()
-                    .map(|a| self.generate_expr(a))
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                format!("{}{}({});", self.indent(), function, args_str)
+            AstNode::Call { .. } => {
+                let indent = self.indent();
+                format!("{indent}{};", self.statement_expr(node, indent.len(), 1))
             }
             AstNode::If {
                 condition,
                 then_branch,
                 else_branch,
             } => {
-                let indent = self.indent();
-                let mut result = format!("{}if {} {{\n", indent, self.generate_expr(condition));
-                self.indent_level += 1;
-                for stmt in then_branch {
-                    result.push_str(&self.generate_node(stmt));
-                    result.push('\n');
-                }
-                self.indent_level -= 1;
-                result.push_str(&format!("{}}}", indent));
+                let prefix = format!("{}if ", self.indent());
+                let condition = self.statement_expr(condition, prefix.len(), 2);
+                let mut result =
+                    format!("{prefix}{condition} {}", self.generate_block(then_branch));
                 if let Some(else_nodes) = else_branch {
-                    result.push_str(" else {\n");
-                    self.indent_level += 1;
-                    for stmt in else_nodes {
-                        result.push_str(&self.generate_node(stmt));
-                        result.push('\n');
-                    }
-                    self.indent_level -= 1;
-                    result.push_str(&format!("{}}}", indent));
+                    result.push_str(" else ");
+                    result.push_str(&self.generate_block(else_nodes));
                 }
                 result
             }
             AstNode::Return(expr) => {
-                format!("{}return {};", self.indent(), self.generate_expr(expr))
+                let prefix = format!("{}return ", self.indent());
+                let expr = self.statement_expr(expr, prefix.len(), 1);
+                format!("{prefix}{expr};")
             }
             _ => self.generate_expr(node),
         }
     }
 
+    /// Generate `{ ... }` with statements one level deeper and the closing
+    /// brace at the current indentation
+    fn generate_block(&mut self, statements: &[AstNode]) -> String {
+        let mut result = String::from("{\n");
+        self.indent_level += 1;
+        for stmt in statements {
+            result.push_str(&self.generate_node(stmt));
+            result.push('\n');
+        }
+        self.indent_level -= 1;
+        result.push_str(&self.indent());
+        result.push('}');
+        result
+    }
+
     fn generate_expr(&self, node: &AstNode) -> String {
         match node {
             AstNode::Identifier(name) => name.clone(),
             AstNode::Literal(lit) => match lit {
                 LiteralValue::Integer(n) => n.to_string(),
-                LiteralValue::Float(f) => f.to_string(),
-                LiteralValue::String(s) => format!("\"{}\"", s),
+                // `Debug` keeps a fractional part or exponent (`2.0`, `1e20`),
+                // so the literal reads back as a float
+                LiteralValue::Float(f) => format!("{f:?}"),
+                LiteralValue::String(s) => format!("\"{}\"", escape_string(s)),
                 LiteralValue::Boolean(b) => b.to_string(),
                 LiteralValue::Null => "null".to_string(),
             },
@@ -448,6 +672,61 @@ impl CodeGenerator {
         }
     }
 
+    /// Render `node` as an expression starting with `available` columns left
+    /// on its line, wrapping onto continuation lines when it does not fit
+    ///
+    /// `base_indent` is the indentation (in columns) of the line the
+    /// expression starts on; continuation lines go one level deeper. Without
+    /// a maximum width this is [`CodeGenerator::generate_expr`].
+    fn wrap_expr(&self, node: &AstNode, base_indent: usize, available: usize) -> String {
+        let flat = self.generate_expr(node);
+        let Some(width) = self.max_line_width else {
+            return flat;
+        };
+        if flat.len() <= available {
+            return flat;
+        }
+
+        let inner = base_indent + self.indent_size;
+        let inner_pad = " ".repeat(inner);
+        match node {
+            AstNode::Call { function, args } if !args.is_empty() => {
+                // One argument per line, each followed by a comma except the last
+                let args: Vec<String> = args
+                    .iter()
+                    .map(|arg| self.wrap_expr(arg, inner, width.saturating_sub(inner + 1)))
+                    .collect();
+                format!(
+                    "{function}(\n{inner_pad}{}\n{})",
+                    args.join(&format!(",\n{inner_pad}")),
+                    " ".repeat(base_indent)
+                )
+            }
+            AstNode::BinaryOp { op, left, right } => {
+                // `(left` stays on this line; `op right)` moves to the next
+                let op_prefix = format!("{op} ");
+                let left = self.wrap_expr(left, base_indent, available.saturating_sub(1));
+                let right = self.wrap_expr(
+                    right,
+                    inner,
+                    width.saturating_sub(inner + op_prefix.len() + 1),
+                );
+                format!("({left}\n{inner_pad}{op_prefix}{right})")
+            }
+            _ => flat,
+        }
+    }
+
+    /// Render the expression of a statement whose text before and after the
+    /// expression is `prefix_len` and `suffix_len` columns long (including
+    /// indentation)
+    fn statement_expr(&self, node: &AstNode, prefix_len: usize, suffix_len: usize) -> String {
+        let available = self.max_line_width.map_or(usize::MAX, |width| {
+            width.saturating_sub(prefix_len + suffix_len)
+        });
+        self.wrap_expr(node, self.indent_level * self.indent_size, available)
+    }
+
     fn indent(&self) -> String {
         " ".repeat(self.indent_level * self.indent_size)
     }
@@ -459,6 +738,444 @@ impl Default for CodeGenerator {
     }
 }
 
+/// Escape `\`, `"` and control characters for a string literal
+fn escape_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Lexical token with the line it starts on
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Integer(i64),
+    Float(f64),
+    Str(String),
+    Symbol(&'static str),
+    Eof,
+}
+
+/// Symbols recognized by the lexer, two-character symbols first
+const SYMBOLS: [&str; 17] = [
+    "==", "!=", "&&", "||", "(", ")", "{", "}", ",", ";", "=", "<", ">", "+", "-", "*", "/",
+];
+
+/// Words that cannot be used as identifiers
+const KEYWORDS: [&str; 8] = ["fn", "let", "return", "if", "else", "true", "false", "null"];
+
+/// Split source text into tokens, skipping whitespace and `//` comments
+fn tokenize(src: &str) -> Result<Vec<(Token, usize)>> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let has_fraction =
+                chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(char::is_ascii_digit);
+            if has_fraction {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            // Exponent, e.g. `1e20` or `2.5e-7`
+            let sign_len = usize::from(matches!(chars.get(i + 1), Some('+' | '-')));
+            let has_exponent = matches!(chars.get(i), Some('e' | 'E'))
+                && chars
+                    .get(i + 1 + sign_len)
+                    .is_some_and(char::is_ascii_digit);
+            if has_exponent {
+                i += 1 + sign_len;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let is_float = has_fraction || has_exponent;
+            let text: String = chars[start..i].iter().collect();
+            let token = if is_float {
+                text.parse().map(Token::Float).ok()
+            } else {
+                text.parse().map(Token::Integer).ok()
+            };
+            let token =
+                token.ok_or_else(|| parse_error(line, &format!("invalid number '{text}'")))?;
+            tokens.push((token, line));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((Token::Ident(chars[start..i].iter().collect()), line));
+        } else if c == '"' {
+            let start_line = line;
+            let (value, end) = lex_string(&chars, i, &mut line)?;
+            tokens.push((Token::Str(value), start_line));
+            i = end;
+        } else {
+            let symbol = SYMBOLS.iter().find(|sym| {
+                sym.chars()
+                    .enumerate()
+                    .all(|(offset, expected)| chars.get(i + offset) == Some(&expected))
+            });
+            match symbol {
+                Some(sym) => {
+                    tokens.push((Token::Symbol(sym), line));
+                    i += sym.len();
+                }
+                None => return Err(parse_error(line, &format!("unexpected character '{c}'"))),
+            }
+        }
+    }
+
+    tokens.push((Token::Eof, line));
+    Ok(tokens)
+}
+
+/// Read the string literal whose opening `"` is at `chars[start]`, returning
+/// its unescaped value and the index after the closing `"`
+///
+/// `line` is advanced past any newlines inside the literal.
+fn lex_string(chars: &[char], start: usize, line: &mut usize) -> Result<(String, usize)> {
+    let start_line = *line;
+    let mut value = String::new();
+    let mut i = start + 1;
+    while i < chars.len() && chars[i] != '"' {
+        match chars[i] {
+            '\\' => {
+                let escaped = match chars.get(i + 1) {
+                    Some('\\') => '\\',
+                    Some('"') => '"',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some(other) => {
+                        return Err(parse_error(*line, &format!("unknown escape '\\{other}'")))
+                    }
+                    None => return Err(parse_error(start_line, "unterminated string literal")),
+                };
+                value.push(escaped);
+                i += 2;
+            }
+            other => {
+                if other == '\n' {
+                    *line += 1;
+                }
+                value.push(other);
+                i += 1;
+            }
+        }
+    }
+    if i >= chars.len() {
+        return Err(parse_error(start_line, "unterminated string literal"));
+    }
+    Ok((value, i + 1))
+}
+
+fn parse_error(line: usize, message: &str) -> batuta_cookbook::Error {
+    batuta_cookbook::Error::Other(format!("Parse error at line {line}: {message}"))
+}
+
+/// Recursive-descent parser for the language emitted by `CodeGenerator`
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token {
+        let index = (self.pos + offset).min(self.tokens.len() - 1);
+        &self.tokens[index].0
+    }
+
+    fn line(&self) -> usize {
+        self.tokens[self.pos.min(self.tokens.len() - 1)].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn error(&self, expected: &str) -> batuta_cookbook::Error {
+        parse_error(
+            self.line(),
+            &format!("expected {expected}, found {:?}", self.peek()),
+        )
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Token::Symbol(s) if *s == symbol)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Ident(s) if s == keyword)
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        if self.is_symbol(symbol) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{symbol}'")))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.is_keyword(keyword) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{keyword}'")))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String> {
+        match self.peek() {
+            Token::Ident(name) if !KEYWORDS.contains(&name.as_str()) => {
+                let name = name.clone();
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.error("identifier")),
+        }
+    }
+
+    fn parse_program(&mut self) -> Result<AstNode> {
+        let mut items = Vec::new();
+        while *self.peek() != Token::Eof {
+            items.push(self.parse_statement()?);
+        }
+        Ok(AstNode::Program(items))
+    }
+
+    fn parse_block(&mut self) -> Result<Vec<AstNode>> {
+        self.expect_symbol("{")?;
+        let mut statements = Vec::new();
+        while !self.is_symbol("}") {
+            if *self.peek() == Token::Eof {
+                return Err(self.error("'}'"));
+            }
+            statements.push(self.parse_statement()?);
+        }
+        self.expect_symbol("}")?;
+        Ok(statements)
+    }
+
+    fn parse_statement(&mut self) -> Result<AstNode> {
+        if self.is_keyword("fn") {
+            return self.parse_function();
+        }
+        if self.is_keyword("if") {
+            return self.parse_if();
+        }
+
+        let statement = if self.is_keyword("let") {
+            self.advance();
+            let name = self.expect_ident()?;
+            self.expect_symbol("=")?;
+            AstNode::VarDecl {
+                name,
+                value: Box::new(self.parse_expr()?),
+            }
+        } else if self.is_keyword("return") {
+            self.advance();
+            AstNode::Return(Box::new(self.parse_expr()?))
+        } else if matches!(self.peek(), Token::Ident(_))
+            && matches!(self.peek_at(1), Token::Symbol("="))
+        {
+            let target = self.expect_ident()?;
+            self.expect_symbol("=")?;
+            AstNode::Assignment {
+                target,
+                value: Box::new(self.parse_expr()?),
+            }
+        } else {
+            // Expression statement; only the generator's call statements end in ';'
+            let expr = self.parse_expr()?;
+            if self.is_symbol(";") {
+                self.advance();
+            }
+            return Ok(expr);
+        };
+
+        self.expect_symbol(";")?;
+        Ok(statement)
+    }
+
+    fn parse_function(&mut self) -> Result<AstNode> {
+        self.expect_keyword("fn")?;
+        let name = self.expect_ident()?;
+        self.expect_symbol("(")?;
+        let mut params = Vec::new();
+        while !self.is_symbol(")") {
+            params.push(self.expect_ident()?);
+            if !self.is_symbol(")") {
+                self.expect_symbol(",")?;
+            }
+        }
+        self.expect_symbol(")")?;
+        let body = self.parse_block()?;
+        Ok(AstNode::Function { name, params, body })
+    }
+
+    fn parse_if(&mut self) -> Result<AstNode> {
+        self.expect_keyword("if")?;
+        let condition = Box::new(self.parse_expr()?);
+        let then_branch = self.parse_block()?;
+        let else_branch = if self.is_keyword("else") {
+            self.advance();
+            Some(self.parse_block()?)
+        } else {
+            None
+        };
+        Ok(AstNode::If {
+            condition,
+            then_branch,
+            else_branch,
+        })
+    }
+
+    fn parse_expr(&mut self) -> Result<AstNode> {
+        self.parse_binary(1)
+    }
+
+    /// Binary operator at the cursor and its precedence (higher binds tighter)
+    fn peek_operator(&self) -> Option<(BinaryOperator, u8)> {
+        let Token::Symbol(symbol) = self.peek() else {
+            return None;
+        };
+        let operator = match *symbol {
+            "||" => (BinaryOperator::Or, 1),
+            "&&" => (BinaryOperator::And, 2),
+            "==" => (BinaryOperator::Equal, 3),
+            "!=" => (BinaryOperator::NotEqual, 3),
+            "<" => (BinaryOperator::Less, 4),
+            ">" => (BinaryOperator::Greater, 4),
+            "+" => (BinaryOperator::Add, 5),
+            "-" => (BinaryOperator::Subtract, 5),
+            "*" => (BinaryOperator::Multiply, 6),
+            "/" => (BinaryOperator::Divide, 6),
+            _ => return None,
+        };
+        Some(operator)
+    }
+
+    /// Precedence climbing: parse operators binding at least as tightly as `min_precedence`
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<AstNode> {
+        let mut left = self.parse_primary()?;
+        while let Some((op, precedence)) = self.peek_operator() {
+            if precedence < min_precedence {
+                break;
+            }
+            self.advance();
+            let right = self.parse_binary(precedence + 1)?;
+            left = AstNode::BinaryOp {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_primary(&mut self) -> Result<AstNode> {
+        match self.advance() {
+            Token::Integer(n) => Ok(AstNode::Literal(LiteralValue::Integer(n))),
+            Token::Float(f) => Ok(AstNode::Literal(LiteralValue::Float(f))),
+            Token::Str(s) => Ok(AstNode::Literal(LiteralValue::String(s))),
+            Token::Symbol("-") => match self.advance() {
+                Token::Integer(n) => Ok(AstNode::Literal(LiteralValue::Integer(-n))),
+                Token::Float(f) => Ok(AstNode::Literal(LiteralValue::Float(-f))),
+                _ => Err(parse_error(self.line(), "expected number after '-'")),
+            },
+            Token::Symbol("(") => {
+                let expr = self.parse_expr()?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            Token::Ident(word) => match word.as_str() {
+                "true" => Ok(AstNode::Literal(LiteralValue::Boolean(true))),
+                "false" => Ok(AstNode::Literal(LiteralValue::Boolean(false))),
+                "null" => Ok(AstNode::Literal(LiteralValue::Null)),
+                keyword if KEYWORDS.contains(&keyword) => Err(parse_error(
+                    self.line(),
+                    &format!("unexpected keyword '{keyword}'"),
+                )),
+                _ if self.is_symbol("(") => {
+                    self.advance();
+                    let mut args = Vec::new();
+                    while !self.is_symbol(")") {
+                        args.push(self.parse_expr()?);
+                        if !self.is_symbol(")") {
+                            self.expect_symbol(",")?;
+                        }
+                    }
+                    self.expect_symbol(")")?;
+                    Ok(AstNode::Call {
+                        function: word,
+                        args,
+                    })
+                }
+                _ => Ok(AstNode::Identifier(word)),
+            },
+            token => Err(parse_error(
+                self.line(),
+                &format!("expected expression, found {token:?}"),
+            )),
+        }
+    }
+}
+
+/// Parse source text in the subset emitted by `CodeGenerator` into a `Program` node
+///
+/// Supports functions, `let` bindings, assignments, binary operators with
+/// precedence, calls, `if`/`else`, `return`, literals, and identifiers.
+///
+/// # Errors
+///
+/// Returns an error with the offending line if the source cannot be parsed.
+pub fn parse_program(src: &str) -> Result<AstNode> {
+    let mut parser = Parser {
+        tokens: tokenize(src)?,
+        pos: 0,
+    };
+    parser.parse_program()
+}
+
 //
 // Example 1: Basic AST parsing and traversal
 //
@@ -482,8 +1199,13 @@ pub fn example_1_ast_traversal() -> Result<()> {
         ],
     }]);
 
-    // Analyze the AST
-    let mut analyzer = AstAnalyzer::new();
+    // Analyze the AST, counting identifiers with a custom hook
+    let mut identifiers = 0;
+    let mut analyzer = AstAnalyzer::new().with_node_hook(Box::new(|node, _| {
+        if let AstNode::Identifier(_) = node {
+            identifiers += 1;
+        }
+    }));
     analyzer.analyze(&ast)?;
 
     println!("AST Statistics:");
@@ -491,6 +1213,13 @@ pub fn example_1_ast_traversal() -> Result<()> {
     println!("  Variables: {}", analyzer.var_count);
     println!("  Function calls: {}", analyzer.call_count);
     println!("  Max depth: {}", analyzer.max_depth);
+    println!("  Branches: {}", analyzer.branch_count);
+    println!(
+        "  Cyclomatic complexity: {}",
+        analyzer.cyclomatic_complexity
+    );
+    drop(analyzer);
+    println!("  Identifiers (custom hook): {identifiers}");
 
     Ok(())
 }
@@ -569,6 +1298,9 @@ pub fn example_3_complex_ast() -> Result<()> {
     println!("Function complexity:");
     println!("  Statements: {}", analyzer.var_count);
     println!("  Max nesting: {}", analyzer.max_depth);
+    if let Some((name, complexity)) = analyzer.most_complex_function() {
+        println!("  Most complex: {name} (cyclomatic {complexity})");
+    }
     println!();
 
     // Generate code
@@ -579,10 +1311,46 @@ pub fn example_3_complex_ast() -> Result<()> {
     Ok(())
 }
 
+//
+// Example 4: Parse source text and regenerate it
+//
+pub fn example_4_parse_round_trip() -> Result<()> {
+    println!("\n=== Example 4: Parse and Regenerate Source ===\n");
+
+    let source = r#"
+fn area(width, height) {
+    let result = width * height + 1;
+    if result > 100 {
+        log("large");
+    }
+    return result;
+}
+"#;
+
+    let ast = parse_program(source)?;
+
+    let mut analyzer = AstAnalyzer::new();
+    analyzer.analyze(&ast)?;
+    println!(
+        "Parsed {} function(s), {} variable(s)",
+        analyzer.function_count, analyzer.var_count
+    );
+
+    let mut codegen = CodeGenerator::new();
+    let generated = codegen.generate(&ast);
+    println!("Regenerated code:\n{generated}");
+
+    let reparsed = parse_program(&generated)?;
+    println!("\nRound-trip stable: {}", reparsed == ast);
+
+    Ok(())
+}
+
 fn main() -> Result<()> {
     example_1_ast_traversal()?;
     example_2_ast_transformation()?;
     example_3_complex_ast()?;
+    example_4_parse_round_trip()?;
     Ok(())
 }
 
@@ -792,4 +1560,452 @@ mod tests {
         let code = codegen.generate(&transformed);
         assert!(code.contains("fn double"));
     }
+
+    fn ident(name: &str) -> Box<AstNode> {
+        Box::new(AstNode::Identifier(name.to_string()))
+    }
+
+    #[test]
+    fn test_parse_two_functions() {
+        let source = "
+            fn add(a, b) {
+                return a + b * 2;
+            }
+
+            fn main() {
+                let total = add(1, 2);
+                total = total - 1;
+                print(total);
+            }
+        ";
+
+        let ast = parse_program(source).unwrap();
+        let AstNode::Program(items) = ast else {
+            panic!("Expected Program node");
+        };
+        assert_eq!(items.len(), 2);
+
+        assert_eq!(
+            items[0],
+            AstNode::Function {
+                name: "add".to_string(),
+                params: vec!["a".to_string(), "b".to_string()],
+                body: vec![AstNode::Return(Box::new(AstNode::BinaryOp {
+                    op: BinaryOperator::Add,
+                    left: ident("a"),
+                    right: Box::new(AstNode::BinaryOp {
+                        op: BinaryOperator::Multiply,
+                        left: ident("b"),
+                        right: Box::new(AstNode::Literal(LiteralValue::Integer(2))),
+                    }),
+                }))],
+            }
+        );
+
+        let AstNode::Function { name, params, body } = &items[1] else {
+            panic!("Expected Function node");
+        };
+        assert_eq!(name, "main");
+        assert!(params.is_empty());
+        assert!(matches!(&body[0], AstNode::VarDecl { name, value }
+            if name == "total" && matches!(**value, AstNode::Call { ref function, ref args }
+                if function == "add" && args.len() == 2)));
+        assert!(matches!(&body[1], AstNode::Assignment { target, .. } if target == "total"));
+        assert!(matches!(&body[2], AstNode::Call { function, .. } if function == "print"));
+    }
+
+    #[test]
+    fn test_parse_generate_round_trip() {
+        let ast = AstNode::Program(vec![
+            AstNode::Function {
+                name: "max".to_string(),
+                params: vec!["a".to_string(), "b".to_string()],
+                body: vec![AstNode::If {
+                    condition: Box::new(AstNode::BinaryOp {
+                        op: BinaryOperator::Greater,
+                        left: ident("a"),
+                        right: ident("b"),
+                    }),
+                    then_branch: vec![AstNode::Return(ident("a"))],
+                    else_branch: Some(vec![AstNode::Return(ident("b"))]),
+                }],
+            },
+            AstNode::Function {
+                name: "check".to_string(),
+                params: vec!["x".to_string()],
+                body: vec![
+                    AstNode::VarDecl {
+                        name: "ok".to_string(),
+                        value: Box::new(AstNode::BinaryOp {
+                            op: BinaryOperator::Or,
+                            left: Box::new(AstNode::BinaryOp {
+                                op: BinaryOperator::Equal,
+                                left: ident("x"),
+                                right: Box::new(AstNode::Literal(LiteralValue::Integer(-1))),
+                            }),
+                            right: Box::new(AstNode::Literal(LiteralValue::Boolean(false))),
+                        }),
+                    },
+                    AstNode::Call {
+                        function: "log".to_string(),
+                        args: vec![
+                            AstNode::Literal(LiteralValue::String("done".to_string())),
+                            AstNode::Literal(LiteralValue::Float(2.5)),
+                            AstNode::Literal(LiteralValue::Null),
+                        ],
+                    },
+                    AstNode::Return(ident("ok")),
+                ],
+            },
+        ]);
+
+        let generated = CodeGenerator::new().generate(&ast);
+        let reparsed = parse_program(&generated).unwrap();
+        assert_eq!(reparsed, ast);
+
+        // Generating again from the re-parsed AST is a fixed point
+        assert_eq!(CodeGenerator::new().generate(&reparsed), generated);
+    }
+
+    #[test]
+    fn test_float_and_string_literals_round_trip() {
+        let literals = [
+            LiteralValue::Float(2.0),
+            LiteralValue::Float(1e20),
+            LiteralValue::Float(2.5e-7),
+            LiteralValue::Float(-3.0),
+            LiteralValue::String("say \"hi\"".to_string()),
+            LiteralValue::String("C:\\temp\\new\tline\n".to_string()),
+        ];
+        let ast = AstNode::Program(vec![AstNode::Function {
+            name: "literals".to_string(),
+            params: vec![],
+            body: vec![AstNode::Call {
+                function: "log".to_string(),
+                args: literals.into_iter().map(AstNode::Literal).collect(),
+            }],
+        }]);
+
+        let generated = CodeGenerator::new().generate(&ast);
+        assert!(generated.contains("2.0, 1e20"));
+        assert_eq!(parse_program(&generated).unwrap(), ast);
+
+        assert!(parse_program("let s = \"bad \\q escape\";").is_err());
+        assert!(parse_program("let s = \"trailing \\").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let err = parse_program("fn broken(a) {\n    let = 1;\n}").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+
+        assert!(parse_program("fn open() {").is_err());
+        assert!(parse_program("let s = \"unterminated;").is_err());
+    }
+
+    struct DoubleIntegers;
+
+    impl AstMutVisitor for DoubleIntegers {
+        fn visit_node_mut(&mut self, node: &mut AstNode) -> Result<()> {
+            if let AstNode::Literal(LiteralValue::Integer(n)) = node {
+                *n *= 2;
+            }
+            walk_node_mut(self, node)
+        }
+    }
+
+    #[test]
+    fn test_mut_visitor_doubles_integers_in_place() {
+        let mut ast = parse_program("fn f(x) { let y = x + 2; return g(3, y * 4); }").unwrap();
+
+        DoubleIntegers.visit_node_mut(&mut ast).unwrap();
+
+        let expected = parse_program("fn f(x) { let y = x + 4; return g(6, y * 8); }").unwrap();
+        assert_eq!(ast, expected);
+    }
+
+    #[test]
+    fn test_transformer_as_mut_visitor() {
+        let mut transformer = AstTransformer::new();
+        transformer.add_rename("x".to_string(), "input".to_string());
+
+        let source = "fn f(x) { let y = x * 2; x = y; return x; }";
+        let expected = transformer.transform(parse_program(source).unwrap());
+
+        let mut ast = parse_program(source).unwrap();
+        transformer.visit_node_mut(&mut ast).unwrap();
+        assert_eq!(ast, expected);
+        assert_eq!(
+            ast,
+            parse_program("fn f(input) { let y = input * 2; input = y; return input; }").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_scoped_rename_stays_in_function() {
+        let source = "
+            fn foo(x) {
+                let temp = x * 2;
+                return temp;
+            }
+
+            fn bar(y) {
+                let temp = y + 1;
+                return temp;
+            }
+        ";
+
+        let mut transformer = AstTransformer::new();
+        transformer.add_scoped_rename("foo".to_string(), "temp".to_string(), "doubled".to_string());
+
+        let transformed = transformer.transform(parse_program(source).unwrap());
+
+        let expected = parse_program(
+            "
+            fn foo(x) {
+                let doubled = x * 2;
+                return doubled;
+            }
+
+            fn bar(y) {
+                let temp = y + 1;
+                return temp;
+            }
+        ",
+        )
+        .unwrap();
+        assert_eq!(transformed, expected);
+    }
+
+    #[test]
+    fn test_scoped_rename_respects_shadowing() {
+        let source = "fn foo(temp) { fn inner() { let temp = 1; return temp; } return temp; }";
+
+        let mut transformer = AstTransformer::new();
+        transformer.add_scoped_rename("foo".to_string(), "temp".to_string(), "value".to_string());
+
+        let transformed = transformer.transform(parse_program(source).unwrap());
+        let expected = parse_program(
+            "fn foo(value) { fn inner() { let temp = 1; return temp; } return value; }",
+        )
+        .unwrap();
+        assert_eq!(transformed, expected);
+    }
+
+    #[test]
+    fn test_cyclomatic_complexity_nested_ifs() {
+        let ast = parse_program(
+            "
+            fn classify(x) {
+                if x > 0 {
+                    if x > 10 {
+                        return 2;
+                    }
+                    return 1;
+                }
+                return 0;
+            }
+        ",
+        )
+        .unwrap();
+
+        let mut analyzer = AstAnalyzer::new();
+        analyzer.analyze(&ast).unwrap();
+
+        assert_eq!(analyzer.branch_count, 2);
+        assert_eq!(analyzer.cyclomatic_complexity, 3);
+        assert_eq!(analyzer.per_function_complexity["classify"], 3);
+    }
+
+    #[test]
+    fn test_cyclomatic_complexity_straight_line() {
+        let ast = parse_program(
+            "
+            fn add(a, b) {
+                let sum = a + b;
+                return sum;
+            }
+
+            fn pick(x) {
+                if x { return 1; } else { return 2; }
+            }
+        ",
+        )
+        .unwrap();
+
+        let mut analyzer = AstAnalyzer::new();
+        analyzer.analyze(&ast).unwrap();
+
+        assert_eq!(analyzer.per_function_complexity["add"], 1);
+        assert_eq!(analyzer.per_function_complexity["pick"], 2);
+        assert_eq!(analyzer.cyclomatic_complexity, 3);
+        assert_eq!(analyzer.most_complex_function(), Some(("pick", 2)));
+    }
+
+    #[test]
+    fn test_nested_if_braces_align() {
+        let ast = parse_program(
+            "fn check(x) { if x > 0 { if x > 10 { big(x); } else { small(x); } } return x; }",
+        )
+        .unwrap();
+
+        let code = CodeGenerator::new().generate(&ast);
+        let lines: Vec<&str> = code.lines().collect();
+        let indent_of = |line: &str| line.len() - line.trim_start().len();
+
+        let mut open_indents = Vec::new();
+        for line in &lines {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('}') {
+                let opener = open_indents.pop().expect("unbalanced braces");
+                assert_eq!(indent_of(line), opener, "misaligned brace in:\n{code}");
+            }
+            if trimmed.ends_with('{') {
+                open_indents.push(indent_of(line));
+            }
+        }
+        assert!(open_indents.is_empty());
+
+        assert_eq!(lines[1], "    if (x > 0) {");
+        assert_eq!(lines[2], "        if (x > 10) {");
+        assert_eq!(lines[3], "            big(x);");
+        assert_eq!(lines[4], "        } else {");
+        assert_eq!(lines[6], "        }");
+        assert_eq!(lines[7], "    }");
+        assert_eq!(lines[9], "}");
+    }
+
+    #[test]
+    fn test_node_hook_counts_literals() {
+        let ast = parse_program(
+            "
+            fn scale(x) {
+                if x > 10 {
+                    return x * 2;
+                }
+                return add(x, 3, 4);
+            }
+        ",
+        )
+        .unwrap();
+
+        let mut literals = 0;
+        let mut max_arity = 0;
+        let mut deepest_literal = 0;
+        let mut analyzer = AstAnalyzer::new()
+            .with_node_hook(Box::new(|node, depth| {
+                if let AstNode::Literal(_) = node {
+                    literals += 1;
+                    deepest_literal = deepest_literal.max(depth);
+                }
+            }))
+            .with_node_hook(Box::new(|node, _| {
+                if let AstNode::Call { args, .. } = node {
+                    max_arity = max_arity.max(args.len());
+                }
+            }));
+        analyzer.analyze(&ast).unwrap();
+
+        // Built-in counters are unaffected by hooks
+        assert_eq!(analyzer.function_count, 1);
+        assert_eq!(analyzer.call_count, 1);
+        drop(analyzer);
+
+        assert_eq!(literals, 4);
+        assert_eq!(max_arity, 3);
+        assert_eq!(deepest_literal, 5);
+    }
+
+    #[test]
+    fn test_ast_json_round_trip() {
+        let ast = AstNode::Program(vec![AstNode::Function {
+            name: "area".to_string(),
+            params: vec!["r".to_string()],
+            body: vec![
+                AstNode::If {
+                    condition: Box::new(AstNode::BinaryOp {
+                        op: BinaryOperator::Less,
+                        left: ident("r"),
+                        right: Box::new(AstNode::Literal(LiteralValue::Integer(-1))),
+                    }),
+                    then_branch: vec![AstNode::Return(Box::new(AstNode::Literal(
+                        LiteralValue::Null,
+                    )))],
+                    else_branch: None,
+                },
+                AstNode::VarDecl {
+                    name: "pi".to_string(),
+                    value: Box::new(AstNode::Literal(LiteralValue::Float(std::f64::consts::PI))),
+                },
+                AstNode::Call {
+                    function: "log".to_string(),
+                    args: vec![
+                        AstNode::Literal(LiteralValue::String("r = \"x\"".to_string())),
+                        AstNode::Literal(LiteralValue::Boolean(true)),
+                        AstNode::Literal(LiteralValue::Float(0.1 + 0.2)),
+                    ],
+                },
+                AstNode::Return(Box::new(AstNode::BinaryOp {
+                    op: BinaryOperator::Multiply,
+                    left: ident("pi"),
+                    right: Box::new(AstNode::BinaryOp {
+                        op: BinaryOperator::Multiply,
+                        left: ident("r"),
+                        right: ident("r"),
+                    }),
+                })),
+            ],
+        }]);
+
+        let json = ast.to_json().unwrap();
+        assert!(json.contains("\"BinaryOp\""));
+
+        let restored = AstNode::from_json(&json).unwrap();
+        assert_eq!(restored, ast);
+
+        assert!(AstNode::from_json("{\"Bogus\": 1}").is_err());
+    }
+
+    #[test]
+    fn test_code_generator_wraps_long_lines() {
+        let ast = parse_program(
+            "fn report(first_measurement, second_measurement) {
+                let summary = format_report(first_measurement * scale_factor, second_measurement + calibration_offset, \"temperature readings\", include_history);
+                return (first_measurement + second_measurement) * scale_factor - calibration_offset * correction;
+            }",
+        )
+        .unwrap();
+
+        let flat = CodeGenerator::new().generate(&ast);
+        assert!(flat.lines().any(|line| line.len() > 60));
+
+        let code = CodeGenerator::new().with_max_line_width(60).generate(&ast);
+        for line in code.lines() {
+            assert!(line.len() <= 60, "line too long: {line:?}\n{code}");
+        }
+
+        let lines: Vec<&str> = code.lines().collect();
+        assert_eq!(lines[1], "    let summary = format_report(");
+        assert_eq!(lines[2], "        (first_measurement * scale_factor),");
+        assert_eq!(lines[5], "        include_history");
+        assert_eq!(lines[6], "    );");
+        assert_eq!(
+            lines[7],
+            "    return (((first_measurement + second_measurement)"
+        );
+        assert_eq!(lines[8], "        * scale_factor)");
+
+        // Wrapping only changes layout
+        assert_eq!(parse_program(&code).unwrap(), ast);
+    }
+
+    #[test]
+    fn test_code_generator_short_lines_stay_flat() {
+        let ast = parse_program("fn f(a, b) { return a + b * 2; }").unwrap();
+
+        assert_eq!(
+            CodeGenerator::new().with_max_line_width(40).generate(&ast),
+            CodeGenerator::new().generate(&ast)
+        );
+    }
 }
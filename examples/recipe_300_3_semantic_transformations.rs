@@ -14,8 +14,8 @@
 //! ## Concepts Covered
 //! - Semantic equivalence vs syntactic equivalence
 //! - Safe code refactoring transformations
-//! - Constant folding and dead code elimination
-//! - Loop transformations and optimizations
+//! - Constant folding, constant propagation, and dead code elimination
+//! - Loop transformations (unrolling, invariant hoisting)
 //! - Equivalence testing strategies
 //!
 //! ## Examples
@@ -24,7 +24,7 @@
 //! 2. Advanced transformations (loop unrolling, inlining)
 //! 3. Transformation verification and testing
 
-use batuta_cookbook::Result;
+use batuta_cookbook::{Error, Result};
 use std::collections::{HashMap, HashSet};
 
 /// Represents a simple expression for transformation
@@ -32,6 +32,8 @@ use std::collections::{HashMap, HashSet};
 pub enum Expr {
     /// Integer literal
     Int(i64),
+    /// Floating-point literal
+    Float(f64),
     /// Variable reference
     Var(String),
     /// Binary operation
@@ -53,6 +55,40 @@ pub enum Op {
     Div,
 }
 
+/// Fold a binary operation over two numeric literals
+///
+/// Int/int operations keep integer semantics (division truncates); if either
+/// operand is a float the result is promoted to float. Returns `None` for
+/// non-literal operands, division by zero, and integer overflow.
+#[allow(clippy::cast_precision_loss)]
+fn fold_literals(op: Op, left: &Expr, right: &Expr) -> Option<Expr> {
+    match (left, right) {
+        (Expr::Int(l), Expr::Int(r)) => match op {
+            Op::Add => l.checked_add(*r),
+            Op::Sub => l.checked_sub(*r),
+            Op::Mul => l.checked_mul(*r),
+            Op::Div => l.checked_div(*r),
+        }
+        .map(Expr::Int),
+        (Expr::Int(_) | Expr::Float(_), Expr::Int(_) | Expr::Float(_)) => {
+            let as_float = |e: &Expr| match e {
+                Expr::Int(n) => *n as f64,
+                Expr::Float(f) => *f,
+                _ => unreachable!("operands are numeric literals"),
+            };
+            let (l, r) = (as_float(left), as_float(right));
+            Some(Expr::Float(match op {
+                Op::Add => l + r,
+                Op::Sub => l - r,
+                Op::Mul => l * r,
+                Op::Div if r != 0.0 => l / r,
+                Op::Div => return None,
+            }))
+        }
+        _ => None,
+    }
+}
+
 /// Statement types
 #[derive(Debug, Clone, PartialEq)]
 pub enum Stmt {
@@ -75,10 +111,14 @@ pub enum Stmt {
 pub enum TransformationType {
     /// Fold constant expressions
     ConstantFolding,
+    /// Substitute constants assigned earlier in a block
+    ConstantPropagation,
     /// Remove dead code
     DeadCodeElimination,
     /// Unroll loops
     LoopUnrolling,
+    /// Hoist loop-invariant assignments out of loops
+    LoopInvariantHoisting,
     /// Inline function calls
     FunctionInlining,
     /// Simplify expressions
@@ -104,6 +144,250 @@ pub struct TransformationResult {
     pub transformation_type: TransformationType,
     pub preservation_level: PreservationLevel,
     pub changes_made: usize,
+    /// Changes that might not preserve semantics (empty for `Guaranteed`)
+    pub warnings: Vec<TransformWarning>,
+}
+
+/// A specific change that might not preserve semantics
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformWarning {
+    pub transformation: TransformationType,
+    /// Where the change was made, e.g. "else branch of `if 1`"
+    pub location: String,
+    /// Source-like rendering of the affected code
+    pub snippet: String,
+    /// Why the change might alter behavior
+    pub reason: String,
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+        }
+    }
+}
+
+/// Render an expression as compact source text for warnings
+fn expr_snippet(expr: &Expr) -> String {
+    match expr {
+        Expr::Int(n) => n.to_string(),
+        Expr::Float(f) => f.to_string(),
+        Expr::Var(name) => name.clone(),
+        Expr::BinOp { op, left, right } => format!(
+            "({} {} {})",
+            expr_snippet(left),
+            op.symbol(),
+            expr_snippet(right)
+        ),
+        Expr::Call { name, args } => {
+            let args: Vec<String> = args.iter().map(expr_snippet).collect();
+            format!("{name}({})", args.join(", "))
+        }
+    }
+}
+
+/// Collect every call expression reachable from `stmts`
+fn collect_calls<'a>(stmts: &'a [Stmt], calls: &mut Vec<&'a Expr>) {
+    fn visit_expr<'a>(expr: &'a Expr, calls: &mut Vec<&'a Expr>) {
+        match expr {
+            Expr::BinOp { left, right, .. } => {
+                visit_expr(left, calls);
+                visit_expr(right, calls);
+            }
+            Expr::Call { args, .. } => {
+                calls.push(expr);
+                for arg in args {
+                    visit_expr(arg, calls);
+                }
+            }
+            Expr::Int(_) | Expr::Float(_) | Expr::Var(_) => {}
+        }
+    }
+
+    for stmt in stmts {
+        match stmt {
+            Stmt::Assign { value, .. } | Stmt::Expr(value) => visit_expr(value, calls),
+            Stmt::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                visit_expr(condition, calls);
+                collect_calls(then_block, calls);
+                collect_calls(else_block, calls);
+            }
+            Stmt::Loop { body, .. } => collect_calls(body, calls),
+        }
+    }
+}
+
+/// Warn about each call in `stmts`, which may have side effects
+fn warn_on_calls(
+    transformation: TransformationType,
+    location: &str,
+    stmts: &[Stmt],
+    reason: &str,
+    warnings: &mut Vec<TransformWarning>,
+) {
+    let mut calls = Vec::new();
+    collect_calls(stmts, &mut calls);
+    warnings.extend(calls.into_iter().map(|call| TransformWarning {
+        transformation,
+        location: location.to_string(),
+        snippet: expr_snippet(call),
+        reason: reason.to_string(),
+    }));
+}
+
+/// Collect the names of all variables assigned anywhere in `stmts`
+fn collect_assigned(stmts: &[Stmt], assigned: &mut HashSet<String>) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::Assign { name, .. } => {
+                assigned.insert(name.clone());
+            }
+            Stmt::If {
+                then_block,
+                else_block,
+                ..
+            } => {
+                collect_assigned(then_block, assigned);
+                collect_assigned(else_block, assigned);
+            }
+            Stmt::Loop { body, .. } => collect_assigned(body, assigned),
+            Stmt::Expr(_) => {}
+        }
+    }
+}
+
+/// Collect the names of all variables read by `expr`
+fn collect_vars(expr: &Expr, vars: &mut HashSet<String>) {
+    match expr {
+        Expr::Var(name) => {
+            vars.insert(name.clone());
+        }
+        Expr::BinOp { left, right, .. } => {
+            collect_vars(left, vars);
+            collect_vars(right, vars);
+        }
+        Expr::Call { args, .. } => {
+            for arg in args {
+                collect_vars(arg, vars);
+            }
+        }
+        Expr::Int(_) | Expr::Float(_) => {}
+    }
+}
+
+/// Collect the names of all variables read anywhere in `stmts`
+fn collect_reads(stmts: &[Stmt], reads: &mut HashSet<String>) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::Assign { value, .. } | Stmt::Expr(value) => collect_vars(value, reads),
+            Stmt::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                collect_vars(condition, reads);
+                collect_reads(then_block, reads);
+                collect_reads(else_block, reads);
+            }
+            Stmt::Loop { body, .. } => collect_reads(body, reads),
+        }
+    }
+}
+
+/// Split a loop into a preamble of hoisted assignments followed by the loop
+///
+/// A top-level assignment in `body` is hoisted when its value reads nothing
+/// the body assigns, its target is assigned by no other statement in the
+/// body, and the target isn't read earlier in the body (the first iteration
+/// would see the value from before the loop). Any call in the body may have
+/// side effects that change invariant values, so nothing is hoisted then,
+/// nor from loops that never run.
+fn hoist_loop_invariants(count: i64, body: Vec<Stmt>, changes: &mut usize) -> Vec<Stmt> {
+    let mut calls = Vec::new();
+    collect_calls(&body, &mut calls);
+    if count <= 0 || !calls.is_empty() {
+        return vec![Stmt::Loop { count, body }];
+    }
+
+    let mut assigned = HashSet::new();
+    collect_assigned(&body, &mut assigned);
+    let mut assignment_counts: HashMap<String, usize> = HashMap::new();
+    for stmt in &body {
+        let mut names = HashSet::new();
+        collect_assigned(std::slice::from_ref(stmt), &mut names);
+        for name in names {
+            *assignment_counts.entry(name).or_default() += 1;
+        }
+    }
+
+    let mut preamble = Vec::new();
+    let mut remaining = Vec::new();
+    let mut read_so_far = HashSet::new();
+    for stmt in body {
+        if let Stmt::Assign { name, value } = &stmt {
+            let mut reads = HashSet::new();
+            collect_vars(value, &mut reads);
+            if reads.is_disjoint(&assigned)
+                && assignment_counts.get(name) == Some(&1)
+                && !read_so_far.contains(name)
+            {
+                *changes += 1;
+                preamble.push(stmt);
+                continue;
+            }
+        }
+        collect_reads(std::slice::from_ref(&stmt), &mut read_so_far);
+        remaining.push(stmt);
+    }
+
+    preamble.push(Stmt::Loop {
+        count,
+        body: remaining,
+    });
+    preamble
+}
+
+/// Hoist loop invariants from `stmt` and any loops nested in its `If` blocks
+fn apply_invariant_hoisting(stmt: Stmt, changes: &mut usize) -> Stmt {
+    match stmt {
+        Stmt::Loop { count, body } => {
+            let mut hoisted = hoist_loop_invariants(count, body, changes);
+            if hoisted.len() == 1 {
+                hoisted.remove(0)
+            } else {
+                // Return a compound statement (using if with always-true condition)
+                Stmt::If {
+                    condition: Expr::Int(1),
+                    then_block: hoisted,
+                    else_block: vec![],
+                }
+            }
+        }
+        Stmt::If {
+            condition,
+            then_block,
+            else_block,
+        } => Stmt::If {
+            condition,
+            then_block: then_block
+                .into_iter()
+                .map(|s| apply_invariant_hoisting(s, changes))
+                .collect(),
+            else_block: else_block
+                .into_iter()
+                .map(|s| apply_invariant_hoisting(s, changes))
+                .collect(),
+        },
+        other => other,
+    }
 }
 
 /// Semantic transformer
@@ -114,6 +398,8 @@ pub struct SemanticTransformer {
     dead_vars: HashSet<String>,
     /// Maximum loop unroll count
     max_unroll: i64,
+    /// Riskiest preservation level `transform_stmt` will apply
+    allowed_level: PreservationLevel,
 }
 
 impl SemanticTransformer {
@@ -122,6 +408,7 @@ impl SemanticTransformer {
             constant_vars: HashMap::new(),
             dead_vars: HashSet::new(),
             max_unroll: 8,
+            allowed_level: PreservationLevel::Likely,
         }
     }
 
@@ -130,6 +417,14 @@ impl SemanticTransformer {
         self
     }
 
+    /// Set the riskiest preservation level to apply (default `Likely`);
+    /// `Unsafe` transformations must be opted into explicitly
+    #[must_use]
+    pub fn with_allowed_level(mut self, max: PreservationLevel) -> Self {
+        self.allowed_level = max;
+        self
+    }
+
     /// Apply constant folding transformation
     pub fn constant_fold(&self, expr: Expr) -> Expr {
         match expr {
@@ -138,21 +433,8 @@ impl SemanticTransformer {
                 let right_folded = self.constant_fold(*right);
 
                 // Try to fold if both sides are constants
-                if let (Expr::Int(l), Expr::Int(r)) = (&left_folded, &right_folded) {
-                    let result = match op {
-                        Op::Add => l + r,
-                        Op::Sub => l - r,
-                        Op::Mul => l * r,
-                        Op::Div if *r != 0 => l / r,
-                        Op::Div => {
-                            return Expr::BinOp {
-                                op,
-                                left: Box::new(left_folded),
-                                right: Box::new(right_folded),
-                            }
-                        }
-                    };
-                    Expr::Int(result)
+                if let Some(folded) = fold_literals(op, &left_folded, &right_folded) {
+                    folded
                 } else {
                     Expr::BinOp {
                         op,
@@ -173,25 +455,47 @@ impl SemanticTransformer {
                 name,
                 args: args.into_iter().map(|a| self.constant_fold(a)).collect(),
             },
-            Expr::Int(_) => expr,
+            Expr::Int(_) | Expr::Float(_) => expr,
         }
     }
 
     /// Transform statement with semantic preservation
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TranspilationError` if `trans_type`'s preservation
+    /// level is riskier than the allowed level (see `with_allowed_level`).
     pub fn transform_stmt(
         &self,
         stmt: Stmt,
         trans_type: TransformationType,
-    ) -> TransformationResult {
+    ) -> Result<TransformationResult> {
+        let preservation_level = self.get_preservation_level(trans_type);
+        if preservation_level > self.allowed_level {
+            return Err(Error::TranspilationError(format!(
+                "{trans_type:?} is {preservation_level:?}, above the allowed {:?} level",
+                self.allowed_level
+            )));
+        }
+
         let original = stmt.clone();
         let mut changes = 0;
+        let mut warnings = Vec::new();
 
         let transformed = match trans_type {
             TransformationType::ConstantFolding => self.apply_constant_folding(stmt, &mut changes),
+            TransformationType::ConstantPropagation => self
+                .propagate_block(vec![stmt], &mut HashMap::new(), &mut changes)
+                .remove(0),
             TransformationType::DeadCodeElimination => {
-                self.apply_dead_code_elimination(stmt, &mut changes)
+                self.apply_dead_code_elimination(stmt, &mut changes, &mut warnings)
+            }
+            TransformationType::LoopUnrolling => {
+                self.apply_loop_unrolling(stmt, &mut changes, &mut warnings)
+            }
+            TransformationType::LoopInvariantHoisting => {
+                apply_invariant_hoisting(stmt, &mut changes)
             }
-            TransformationType::LoopUnrolling => self.apply_loop_unrolling(stmt, &mut changes),
             TransformationType::ExpressionSimplification => {
                 self.apply_expression_simplification(stmt, &mut changes)
             }
@@ -201,13 +505,14 @@ impl SemanticTransformer {
             }
         };
 
-        TransformationResult {
+        Ok(TransformationResult {
             original,
             transformed,
             transformation_type: trans_type,
-            preservation_level: self.get_preservation_level(trans_type),
+            preservation_level,
             changes_made: changes,
-        }
+            warnings,
+        })
     }
 
     fn apply_constant_folding(&self, stmt: Stmt, changes: &mut usize) -> Stmt {
@@ -257,7 +562,115 @@ impl SemanticTransformer {
         }
     }
 
-    fn apply_dead_code_elimination(&self, stmt: Stmt, changes: &mut usize) -> Stmt {
+    /// Propagate constants through a block of statements
+    ///
+    /// Assignments of constant expressions are remembered and substituted
+    /// (then folded) into later statements. Reassigning a variable to a
+    /// non-constant forgets it. After an `If`, only constants both branches
+    /// agree on are kept; variables assigned in a loop body are unknown both
+    /// inside and after the loop. Expressions cannot alias variables, so this
+    /// is `Guaranteed` to preserve semantics.
+    #[must_use]
+    pub fn propagate_constants(&self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        let mut changes = 0;
+        self.propagate_block(stmts, &mut HashMap::new(), &mut changes)
+    }
+
+    fn propagate_block(
+        &self,
+        stmts: Vec<Stmt>,
+        env: &mut HashMap<String, Expr>,
+        changes: &mut usize,
+    ) -> Vec<Stmt> {
+        stmts
+            .into_iter()
+            .map(|stmt| self.propagate_stmt(stmt, env, changes))
+            .collect()
+    }
+
+    fn propagate_stmt(
+        &self,
+        stmt: Stmt,
+        env: &mut HashMap<String, Expr>,
+        changes: &mut usize,
+    ) -> Stmt {
+        match stmt {
+            Stmt::Assign { name, value } => {
+                let folded = self.propagate_expr(&value, env, changes);
+                if matches!(folded, Expr::Int(_) | Expr::Float(_)) {
+                    env.insert(name.clone(), folded.clone());
+                } else {
+                    env.remove(&name);
+                }
+                Stmt::Assign {
+                    name,
+                    value: folded,
+                }
+            }
+            Stmt::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                let condition = self.propagate_expr(&condition, env, changes);
+                let mut then_env = env.clone();
+                let then_block = self.propagate_block(then_block, &mut then_env, changes);
+                let else_block = self.propagate_block(else_block, env, changes);
+                env.retain(|name, value| then_env.get(name) == Some(value));
+                Stmt::If {
+                    condition,
+                    then_block,
+                    else_block,
+                }
+            }
+            Stmt::Loop { count, body } => {
+                let mut assigned = HashSet::new();
+                collect_assigned(&body, &mut assigned);
+                env.retain(|name, _| !assigned.contains(name));
+
+                let body = self.propagate_block(body, &mut env.clone(), changes);
+                Stmt::Loop { count, body }
+            }
+            Stmt::Expr(expr) => Stmt::Expr(self.propagate_expr(&expr, env, changes)),
+        }
+    }
+
+    /// Substitute known constants into `expr` and fold it
+    fn propagate_expr(
+        &self,
+        expr: &Expr,
+        env: &HashMap<String, Expr>,
+        changes: &mut usize,
+    ) -> Expr {
+        fn substitute(expr: &Expr, env: &HashMap<String, Expr>) -> Expr {
+            match expr {
+                Expr::Var(name) => env.get(name).cloned().unwrap_or_else(|| expr.clone()),
+                Expr::BinOp { op, left, right } => Expr::BinOp {
+                    op: *op,
+                    left: Box::new(substitute(left, env)),
+                    right: Box::new(substitute(right, env)),
+                },
+                Expr::Call { name, args } => Expr::Call {
+                    name: name.clone(),
+                    args: args.iter().map(|arg| substitute(arg, env)).collect(),
+                },
+                Expr::Int(_) | Expr::Float(_) => expr.clone(),
+            }
+        }
+
+        let folded = self.constant_fold(substitute(expr, env));
+        if folded != *expr {
+            *changes += 1;
+        }
+        folded
+    }
+
+    fn apply_dead_code_elimination(
+        &self,
+        stmt: Stmt,
+        changes: &mut usize,
+        warnings: &mut Vec<TransformWarning>,
+    ) -> Stmt {
         match stmt {
             Stmt::If {
                 condition,
@@ -267,6 +680,18 @@ impl SemanticTransformer {
                 // Check if condition is constant
                 if let Expr::Int(val) = condition {
                     *changes += 1;
+                    let (location, eliminated) = if val != 0 {
+                        (format!("else branch of `if {val}`"), &else_block)
+                    } else {
+                        (format!("then branch of `if {val}`"), &then_block)
+                    };
+                    warn_on_calls(
+                        TransformationType::DeadCodeElimination,
+                        &location,
+                        eliminated,
+                        "dead-code elimination assumed no side effects in eliminated branch",
+                        warnings,
+                    );
                     if val != 0 {
                         // Condition is always true, keep only then branch
                         return if then_block.len() == 1 {
@@ -298,11 +723,11 @@ impl SemanticTransformer {
                     condition,
                     then_block: then_block
                         .into_iter()
-                        .map(|s| self.apply_dead_code_elimination(s, changes))
+                        .map(|s| self.apply_dead_code_elimination(s, changes, warnings))
                         .collect(),
                     else_block: else_block
                         .into_iter()
-                        .map(|s| self.apply_dead_code_elimination(s, changes))
+                        .map(|s| self.apply_dead_code_elimination(s, changes, warnings))
                         .collect(),
                 }
             }
@@ -310,11 +735,23 @@ impl SemanticTransformer {
         }
     }
 
-    fn apply_loop_unrolling(&self, stmt: Stmt, changes: &mut usize) -> Stmt {
+    fn apply_loop_unrolling(
+        &self,
+        stmt: Stmt,
+        changes: &mut usize,
+        warnings: &mut Vec<TransformWarning>,
+    ) -> Stmt {
         match stmt {
             Stmt::Loop { count, body } => {
                 if count <= self.max_unroll && count > 0 {
                     *changes += 1;
+                    warn_on_calls(
+                        TransformationType::LoopUnrolling,
+                        &format!("body of `loop {count}`"),
+                        &body,
+                        "loop unrolling assumed the call does not observe the iteration count",
+                        warnings,
+                    );
                     // Unroll the loop
                     let mut unrolled = Vec::new();
                     for _ in 0..count {
@@ -338,17 +775,32 @@ impl SemanticTransformer {
                 condition,
                 then_block: then_block
                     .into_iter()
-                    .map(|s| self.apply_loop_unrolling(s, changes))
+                    .map(|s| self.apply_loop_unrolling(s, changes, warnings))
                     .collect(),
                 else_block: else_block
                     .into_iter()
-                    .map(|s| self.apply_loop_unrolling(s, changes))
+                    .map(|s| self.apply_loop_unrolling(s, changes, warnings))
                     .collect(),
             },
             other => other,
         }
     }
 
+    /// Hoist loop-invariant assignments out of a `Stmt::Loop`
+    ///
+    /// Returns the hoisted assignments followed by the remaining loop; any
+    /// other statement is returned unchanged. This is `Likely` to preserve
+    /// semantics: a hoisted expression that fails (e.g. division by zero)
+    /// now fails before the statements preceding it in the body run.
+    #[must_use]
+    pub fn hoist_invariants(&self, stmt: Stmt) -> Vec<Stmt> {
+        let mut changes = 0;
+        match stmt {
+            Stmt::Loop { count, body } => hoist_loop_invariants(count, body, &mut changes),
+            other => vec![other],
+        }
+    }
+
     fn apply_expression_simplification(&self, stmt: Stmt, changes: &mut usize) -> Stmt {
         match stmt {
             Stmt::Assign { name, value } => {
@@ -374,6 +826,20 @@ impl SemanticTransformer {
 
                 // Simplifications: x + 0 = x, x * 1 = x, x * 0 = 0, etc.
                 match (&left_simp, op, &right_simp) {
+                    (_, Op::Sub, Expr::Int(0)) | (_, Op::Div, Expr::Int(1)) => {
+                        *changes += 1;
+                        left_simp
+                    }
+                    // Only identical variables: calls may have side effects
+                    (Expr::Var(l), Op::Sub, Expr::Var(r)) if l == r => {
+                        *changes += 1;
+                        Expr::Int(0)
+                    }
+                    // 0 / x keeps its divide-by-zero behavior unless x is a non-zero literal
+                    (Expr::Int(0), Op::Div, Expr::Int(n)) if *n != 0 => {
+                        *changes += 1;
+                        Expr::Int(0)
+                    }
                     (_, Op::Add, Expr::Int(0)) => {
                         *changes += 1;
                         left_simp
@@ -407,12 +873,12 @@ impl SemanticTransformer {
 
     fn get_preservation_level(&self, trans_type: TransformationType) -> PreservationLevel {
         match trans_type {
-            TransformationType::ConstantFolding | TransformationType::ExpressionSimplification => {
-                PreservationLevel::Guaranteed
-            }
-            TransformationType::DeadCodeElimination | TransformationType::LoopUnrolling => {
-                PreservationLevel::Likely
-            }
+            TransformationType::ConstantFolding
+            | TransformationType::ConstantPropagation
+            | TransformationType::ExpressionSimplification => PreservationLevel::Guaranteed,
+            TransformationType::DeadCodeElimination
+            | TransformationType::LoopUnrolling
+            | TransformationType::LoopInvariantHoisting => PreservationLevel::Likely,
             TransformationType::FunctionInlining => PreservationLevel::Unsafe,
         }
     }
@@ -434,6 +900,62 @@ impl Default for SemanticTransformer {
     }
 }
 
+/// Outcome of a randomized equivalence check
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquivalenceResult {
+    /// True if no counterexample was found
+    pub equivalent: bool,
+    /// Trials where both expressions evaluated
+    pub trials_evaluated: usize,
+    /// Trials skipped because an expression could not be evaluated
+    /// (division by zero, overflow, or an unbound variable)
+    pub trials_skipped: usize,
+    /// First assignment on which the expressions disagreed
+    pub counterexample: Option<Counterexample>,
+}
+
+/// Variable assignment on which two expressions evaluate differently
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counterexample {
+    pub assignment: HashMap<String, i64>,
+    pub left_value: Expr,
+    pub right_value: Expr,
+}
+
+/// Seeded `SplitMix64` generator so randomized checks are reproducible
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `-bound..=bound`
+    #[allow(clippy::cast_possible_wrap)]
+    fn next_in_range(&mut self, bound: u64) -> i64 {
+        let span = 2 * bound + 1;
+        (self.next_u64() % span) as i64 - bound as i64
+    }
+}
+
+/// Magnitude bound for randomized variable values, small enough that
+/// products of a few variables stay far from overflow
+const RANDOM_VALUE_BOUND: u64 = 1_000_000;
+
+/// Most statements `statements_equivalent` interprets per run before giving
+/// up, so huge loop counts cannot hang the checker
+const MAX_INTERPRETED_STEPS: usize = 100_000;
+
 /// Equivalence checker for verifying transformations
 pub struct EquivalenceChecker {
     /// Test cases for verification
@@ -469,20 +991,142 @@ impl EquivalenceChecker {
         true
     }
 
-    fn eval_expr(&self, expr: &Expr, vars: &HashMap<String, i64>) -> Option<i64> {
+    /// Check equivalence over `trials` random assignments of `var_names`
+    ///
+    /// Values are drawn from a PRNG seeded with `seed`, so the same inputs
+    /// always produce the same result. Assignments on which either
+    /// expression cannot be evaluated (e.g. division by zero) are skipped.
+    #[must_use]
+    pub fn check_randomized(
+        &self,
+        expr1: &Expr,
+        expr2: &Expr,
+        var_names: &[String],
+        trials: usize,
+        seed: u64,
+    ) -> EquivalenceResult {
+        let mut rng = SplitMix64::new(seed);
+        let mut result = EquivalenceResult {
+            equivalent: true,
+            trials_evaluated: 0,
+            trials_skipped: 0,
+            counterexample: None,
+        };
+
+        for _ in 0..trials {
+            let assignment: HashMap<String, i64> = var_names
+                .iter()
+                .map(|name| (name.clone(), rng.next_in_range(RANDOM_VALUE_BOUND)))
+                .collect();
+
+            let (Some(left_value), Some(right_value)) = (
+                self.eval_expr(expr1, &assignment),
+                self.eval_expr(expr2, &assignment),
+            ) else {
+                result.trials_skipped += 1;
+                continue;
+            };
+
+            result.trials_evaluated += 1;
+            if left_value != right_value {
+                result.equivalent = false;
+                result.counterexample = Some(Counterexample {
+                    assignment,
+                    left_value,
+                    right_value,
+                });
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Check if two statements leave the same variable state for all test cases
+    ///
+    /// Each statement runs against a copy of every test case: assignments
+    /// update the variables, `If` runs the branch its condition selects
+    /// (non-zero is true) and `Loop` runs its body `count` times. If either
+    /// statement cannot be interpreted on some case (a call, a non-integer
+    /// assignment, division by zero, or more than `MAX_INTERPRETED_STEPS`
+    /// statements), or there are no test cases, falls back to structural
+    /// equality.
+    #[must_use]
+    pub fn statements_equivalent(&self, s1: &Stmt, s2: &Stmt) -> bool {
+        if self.test_cases.is_empty() {
+            return s1 == s2;
+        }
+
+        let run = |stmt: &Stmt, test_case: &HashMap<String, i64>| {
+            let mut vars = test_case.clone();
+            let mut steps = MAX_INTERPRETED_STEPS;
+            self.exec_stmt(stmt, &mut vars, &mut steps).map(|()| vars)
+        };
+        let mut all_agree = true;
+        for test_case in &self.test_cases {
+            match (run(s1, test_case), run(s2, test_case)) {
+                (Some(vars1), Some(vars2)) => all_agree &= vars1 == vars2,
+                _ => return s1 == s2,
+            }
+        }
+        all_agree
+    }
+
+    /// Execute a statement, returning `None` if it cannot be interpreted
+    /// (non-integer assignment, unevaluable condition) or `steps` runs out
+    fn exec_stmt(
+        &self,
+        stmt: &Stmt,
+        vars: &mut HashMap<String, i64>,
+        steps: &mut usize,
+    ) -> Option<()> {
+        *steps = steps.checked_sub(1)?;
+        match stmt {
+            Stmt::Assign { name, value } => match self.eval_expr(value, vars)? {
+                Expr::Int(n) => {
+                    vars.insert(name.clone(), n);
+                    Some(())
+                }
+                _ => None,
+            },
+            Stmt::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                let taken = match self.eval_expr(condition, vars)? {
+                    Expr::Int(n) => n != 0,
+                    Expr::Float(f) => f != 0.0,
+                    _ => return None,
+                };
+                let block = if taken { then_block } else { else_block };
+                block
+                    .iter()
+                    .try_for_each(|s| self.exec_stmt(s, vars, steps))
+            }
+            Stmt::Loop { count, body } => {
+                for _ in 0..*count {
+                    // Charge each iteration so even an empty body terminates
+                    *steps = steps.checked_sub(1)?;
+                    body.iter()
+                        .try_for_each(|s| self.exec_stmt(s, vars, steps))?;
+                }
+                Some(())
+            }
+            // Expression statements have no effect on variables
+            Stmt::Expr(_) => Some(()),
+        }
+    }
+
+    /// Evaluate to a numeric literal (`Expr::Int` or `Expr::Float`)
+    fn eval_expr(&self, expr: &Expr, vars: &HashMap<String, i64>) -> Option<Expr> {
         match expr {
-            Expr::Int(n) => Some(*n),
-            Expr::Var(name) => vars.get(name).copied(),
+            Expr::Int(_) | Expr::Float(_) => Some(expr.clone()),
+            Expr::Var(name) => vars.get(name).copied().map(Expr::Int),
             Expr::BinOp { op, left, right } => {
                 let l = self.eval_expr(left, vars)?;
                 let r = self.eval_expr(right, vars)?;
-                Some(match op {
-                    Op::Add => l + r,
-                    Op::Sub => l - r,
-                    Op::Mul => l * r,
-                    Op::Div if r != 0 => l / r,
-                    Op::Div => return None,
-                })
+                fold_literals(*op, &l, &r)
             }
             Expr::Call { .. } => None, // Can't evaluate function calls
         }
@@ -519,7 +1163,7 @@ pub fn example_1_constant_folding() -> Result<()> {
         value: expr,
     };
 
-    let result = transformer.transform_stmt(stmt, TransformationType::ConstantFolding);
+    let result = transformer.transform_stmt(stmt, TransformationType::ConstantFolding)?;
 
     println!("Transformation: {:?}", result.transformation_type);
     println!("Preservation: {:?}", result.preservation_level);
@@ -550,13 +1194,14 @@ pub fn example_2_dead_code_elimination() -> Result<()> {
         }],
     };
 
-    let result = transformer.transform_stmt(stmt, TransformationType::DeadCodeElimination);
+    let result = transformer.transform_stmt(stmt, TransformationType::DeadCodeElimination)?;
 
     println!("Preservation: {:?}", result.preservation_level);
     println!("Changes made: {}", result.changes_made);
     println!("Original had both branches");
     println!("Transformed: {:?}", result.transformed);
     println!("(Else branch eliminated because condition is always true)");
+    println!("Warnings: {}", result.warnings.len());
 
     Ok(())
 }
@@ -582,7 +1227,7 @@ pub fn example_3_loop_unrolling() -> Result<()> {
         }],
     };
 
-    let result = transformer.transform_stmt(stmt.clone(), TransformationType::LoopUnrolling);
+    let result = transformer.transform_stmt(stmt.clone(), TransformationType::LoopUnrolling)?;
 
     println!("Original loop count: 3");
     println!("Transformation: {:?}", result.transformation_type);
@@ -602,7 +1247,7 @@ pub fn example_3_loop_unrolling() -> Result<()> {
         body: vec![Stmt::Expr(Expr::Int(1))],
     };
 
-    let result2 = transformer.transform_stmt(large_loop, TransformationType::LoopUnrolling);
+    let result2 = transformer.transform_stmt(large_loop, TransformationType::LoopUnrolling)?;
     println!("\nLarge loop (100 iterations):");
     println!(
         "Changes made: {} (not unrolled, exceeds max)",
@@ -691,7 +1336,9 @@ mod tests {
             else_block: vec![Stmt::Expr(Expr::Int(0))],
         };
 
-        let result = transformer.transform_stmt(stmt, TransformationType::DeadCodeElimination);
+        let result = transformer
+            .transform_stmt(stmt, TransformationType::DeadCodeElimination)
+            .unwrap();
         assert!(result.changes_made > 0);
     }
 
@@ -704,7 +1351,9 @@ mod tests {
             else_block: vec![Stmt::Expr(Expr::Int(99))],
         };
 
-        let result = transformer.transform_stmt(stmt, TransformationType::DeadCodeElimination);
+        let result = transformer
+            .transform_stmt(stmt, TransformationType::DeadCodeElimination)
+            .unwrap();
         assert!(result.changes_made > 0);
         // Should keep else branch
         assert_eq!(result.transformed, Stmt::Expr(Expr::Int(99)));
@@ -718,7 +1367,9 @@ mod tests {
             body: vec![Stmt::Expr(Expr::Int(1))],
         };
 
-        let result = transformer.transform_stmt(stmt, TransformationType::LoopUnrolling);
+        let result = transformer
+            .transform_stmt(stmt, TransformationType::LoopUnrolling)
+            .unwrap();
         assert_eq!(result.changes_made, 1);
     }
 
@@ -730,7 +1381,9 @@ mod tests {
             body: vec![Stmt::Expr(Expr::Int(1))],
         };
 
-        let result = transformer.transform_stmt(stmt, TransformationType::LoopUnrolling);
+        let result = transformer
+            .transform_stmt(stmt, TransformationType::LoopUnrolling)
+            .unwrap();
         assert_eq!(result.changes_made, 0); // Not unrolled
     }
 
@@ -779,6 +1432,136 @@ mod tests {
         assert_eq!(changes, 1);
     }
 
+    #[test]
+    fn test_unsafe_transform_requires_opt_in() {
+        let stmt = Stmt::Expr(Expr::Call {
+            name: "f".to_string(),
+            args: vec![Expr::Int(1)],
+        });
+
+        let err = SemanticTransformer::new()
+            .transform_stmt(stmt.clone(), TransformationType::FunctionInlining)
+            .unwrap_err();
+        assert!(err.to_string().contains("FunctionInlining"));
+
+        let result = SemanticTransformer::new()
+            .with_allowed_level(PreservationLevel::Unsafe)
+            .transform_stmt(stmt, TransformationType::FunctionInlining)
+            .unwrap();
+        assert_eq!(result.preservation_level, PreservationLevel::Unsafe);
+
+        // Guaranteed-only transformers also reject `Likely` transforms
+        let strict = SemanticTransformer::new().with_allowed_level(PreservationLevel::Guaranteed);
+        let loop_stmt = Stmt::Loop {
+            count: 2,
+            body: vec![Stmt::Expr(Expr::Int(1))],
+        };
+        assert!(strict
+            .transform_stmt(loop_stmt.clone(), TransformationType::LoopUnrolling)
+            .is_err());
+        assert!(strict
+            .transform_stmt(loop_stmt, TransformationType::ConstantFolding)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_statements_equivalent_unrolled_loop() {
+        let add_i = Stmt::Assign {
+            name: "sum".to_string(),
+            value: binop(Op::Add, var("sum"), var("i")),
+        };
+        let original = Stmt::Loop {
+            count: 3,
+            body: vec![add_i],
+        };
+        let unrolled = SemanticTransformer::new()
+            .transform_stmt(original.clone(), TransformationType::LoopUnrolling)
+            .unwrap()
+            .transformed;
+        assert_ne!(unrolled, original);
+
+        let mut checker = EquivalenceChecker::new();
+        for (sum, i) in [(0, 1), (10, -4), (7, 0)] {
+            checker.add_test_case(HashMap::from([
+                ("sum".to_string(), sum),
+                ("i".to_string(), i),
+            ]));
+        }
+        assert!(checker.statements_equivalent(&original, &unrolled));
+
+        let twice = Stmt::Loop {
+            count: 2,
+            body: vec![Stmt::Assign {
+                name: "sum".to_string(),
+                value: binop(Op::Add, var("sum"), var("i")),
+            }],
+        };
+        assert!(!checker.statements_equivalent(&original, &twice));
+    }
+
+    #[test]
+    fn test_statements_equivalent_if_conditions() {
+        let assign = |value| {
+            vec![Stmt::Assign {
+                name: "x".to_string(),
+                value: Expr::Int(value),
+            }]
+        };
+        let mut checker = EquivalenceChecker::new();
+        checker.add_test_case(HashMap::from([("flag".to_string(), 0)]));
+        checker.add_test_case(HashMap::from([("flag".to_string(), 5)]));
+
+        // Constant condition: equivalent to the branch it selects
+        let constant = Stmt::If {
+            condition: Expr::Int(1),
+            then_block: assign(1),
+            else_block: assign(2),
+        };
+        assert!(checker.statements_equivalent(&constant, &assign(1)[0]));
+
+        // Variable condition: depends on the test case
+        let variable = Stmt::If {
+            condition: var("flag"),
+            then_block: assign(1),
+            else_block: assign(2),
+        };
+        assert!(!checker.statements_equivalent(&variable, &assign(1)[0]));
+        let swapped = Stmt::If {
+            condition: binop(Op::Sub, Expr::Int(1), Expr::Int(1)),
+            then_block: assign(2),
+            else_block: assign(1),
+        };
+        assert!(checker.statements_equivalent(&constant, &swapped));
+    }
+
+    #[test]
+    fn test_statements_equivalent_uninterpretable_falls_back_to_structure() {
+        let mut checker = EquivalenceChecker::new();
+        checker.add_test_case(HashMap::from([("x".to_string(), 0)]));
+        let assign = |value| Stmt::Assign {
+            name: "x".to_string(),
+            value,
+        };
+        let call = |name: &str, arg| Expr::Call {
+            name: name.to_string(),
+            args: vec![Expr::Int(arg)],
+        };
+
+        assert!(!checker.statements_equivalent(&assign(call("f", 1)), &assign(call("g", 2))));
+        assert!(checker.statements_equivalent(&assign(call("f", 1)), &assign(call("f", 1))));
+        assert!(
+            !checker.statements_equivalent(&assign(Expr::Float(1.5)), &assign(Expr::Float(2.5)))
+        );
+
+        // A huge loop exhausts the step budget instead of hanging
+        let huge = |count| Stmt::Loop {
+            count,
+            body: vec![assign(binop(Op::Add, var("x"), Expr::Int(1)))],
+        };
+        assert!(!checker.statements_equivalent(&huge(i64::MAX), &huge(i64::MAX - 1)));
+        assert!(checker.statements_equivalent(&huge(i64::MAX), &huge(i64::MAX)));
+    }
+
     #[test]
     fn test_preservation_levels() {
         let transformer = SemanticTransformer::new();
@@ -833,7 +1616,9 @@ mod tests {
         let transformer = SemanticTransformer::new();
         let stmt = Stmt::Expr(Expr::Int(42));
 
-        let result = transformer.transform_stmt(stmt, TransformationType::ConstantFolding);
+        let result = transformer
+            .transform_stmt(stmt, TransformationType::ConstantFolding)
+            .unwrap();
 
         assert_eq!(
             result.transformation_type,
@@ -841,4 +1626,382 @@ mod tests {
         );
         assert_eq!(result.preservation_level, PreservationLevel::Guaranteed);
     }
+
+    #[test]
+    fn test_constant_folding_promotes_to_float() {
+        let transformer = SemanticTransformer::new();
+        let expr = Expr::BinOp {
+            op: Op::Mul,
+            left: Box::new(Expr::Int(2)),
+            right: Box::new(Expr::Float(3.0)),
+        };
+
+        assert_eq!(transformer.constant_fold(expr), Expr::Float(6.0));
+    }
+
+    #[test]
+    fn test_constant_folding_int_division_truncates() {
+        let transformer = SemanticTransformer::new();
+        let expr = Expr::BinOp {
+            op: Op::Div,
+            left: Box::new(Expr::Int(7)),
+            right: Box::new(Expr::Int(2)),
+        };
+
+        assert_eq!(transformer.constant_fold(expr), Expr::Int(3));
+    }
+
+    #[test]
+    fn test_constant_folding_float_division_by_zero_unfolded() {
+        let transformer = SemanticTransformer::new();
+        let expr = Expr::BinOp {
+            op: Op::Div,
+            left: Box::new(Expr::Float(1.5)),
+            right: Box::new(Expr::Int(0)),
+        };
+
+        assert_eq!(transformer.constant_fold(expr.clone()), expr);
+    }
+
+    #[test]
+    fn test_equivalence_checker_float_promotion() {
+        let mut checker = EquivalenceChecker::new();
+        let mut test_case = HashMap::new();
+        test_case.insert("x".to_string(), 4);
+        checker.add_test_case(test_case);
+
+        let expr1 = Expr::BinOp {
+            op: Op::Div,
+            left: Box::new(Expr::Var("x".to_string())),
+            right: Box::new(Expr::Float(8.0)),
+        };
+
+        assert!(checker.expressions_equivalent(&expr1, &Expr::Float(0.5)));
+        assert!(!checker.expressions_equivalent(&expr1, &Expr::Int(0)));
+    }
+
+    fn simplify(expr: Expr) -> (Expr, usize) {
+        let transformer = SemanticTransformer::new();
+        let mut changes = 0;
+        let simplified = transformer.simplify_expr(expr, &mut changes);
+        (simplified, changes)
+    }
+
+    fn binop(op: Op, left: Expr, right: Expr) -> Expr {
+        Expr::BinOp {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn var(name: &str) -> Expr {
+        Expr::Var(name.to_string())
+    }
+
+    #[test]
+    fn test_simplify_subtract_zero() {
+        assert_eq!(
+            simplify(binop(Op::Sub, var("x"), Expr::Int(0))),
+            (var("x"), 1)
+        );
+    }
+
+    #[test]
+    fn test_simplify_subtract_self() {
+        assert_eq!(
+            simplify(binop(Op::Sub, var("x"), var("x"))),
+            (Expr::Int(0), 1)
+        );
+
+        // Different variables and calls are left alone
+        let distinct = binop(Op::Sub, var("x"), var("y"));
+        assert_eq!(simplify(distinct.clone()), (distinct, 0));
+        let call = Expr::Call {
+            name: "next".to_string(),
+            args: vec![],
+        };
+        let calls = binop(Op::Sub, call.clone(), call);
+        assert_eq!(simplify(calls.clone()), (calls, 0));
+    }
+
+    #[test]
+    fn test_simplify_divide_by_one() {
+        assert_eq!(
+            simplify(binop(Op::Div, var("x"), Expr::Int(1))),
+            (var("x"), 1)
+        );
+    }
+
+    #[test]
+    fn test_simplify_zero_divided() {
+        assert_eq!(
+            simplify(binop(Op::Div, Expr::Int(0), Expr::Int(5))),
+            (Expr::Int(0), 1)
+        );
+
+        // x may be zero, so the division is preserved
+        let unknown = binop(Op::Div, Expr::Int(0), var("x"));
+        assert_eq!(simplify(unknown.clone()), (unknown, 0));
+    }
+
+    #[test]
+    fn test_simplify_distinct_division_untouched() {
+        let expr = binop(Op::Div, var("x"), var("y"));
+        assert_eq!(simplify(expr.clone()), (expr, 0));
+    }
+
+    #[test]
+    fn test_check_randomized_equivalent() {
+        let checker = EquivalenceChecker::new();
+        let vars = vec!["x".to_string()];
+
+        let result = checker.check_randomized(
+            &binop(Op::Add, var("x"), var("x")),
+            &binop(Op::Mul, var("x"), Expr::Int(2)),
+            &vars,
+            1000,
+            42,
+        );
+
+        assert!(result.equivalent);
+        assert_eq!(result.trials_evaluated, 1000);
+        assert!(result.counterexample.is_none());
+    }
+
+    #[test]
+    fn test_check_randomized_counterexample() {
+        let checker = EquivalenceChecker::new();
+        let vars = vec!["x".to_string()];
+
+        let result = checker.check_randomized(
+            &binop(Op::Add, var("x"), Expr::Int(1)),
+            &var("x"),
+            &vars,
+            1000,
+            7,
+        );
+
+        assert!(!result.equivalent);
+        let counterexample = result.counterexample.expect("expected a counterexample");
+        let x = counterexample.assignment["x"];
+        assert_eq!(counterexample.left_value, Expr::Int(x + 1));
+        assert_eq!(counterexample.right_value, Expr::Int(x));
+
+        // Same seed reproduces the same counterexample
+        let again = checker.check_randomized(
+            &binop(Op::Add, var("x"), Expr::Int(1)),
+            &var("x"),
+            &vars,
+            1000,
+            7,
+        );
+        assert_eq!(again.counterexample.unwrap().assignment["x"], x);
+    }
+
+    #[test]
+    fn test_check_randomized_skips_division_by_zero() {
+        let checker = EquivalenceChecker::new();
+        let vars = vec!["x".to_string(), "y".to_string()];
+        let expr = binop(Op::Div, var("x"), binop(Op::Sub, var("y"), var("y")));
+
+        let result = checker.check_randomized(&expr, &expr, &vars, 50, 1);
+
+        assert!(result.equivalent);
+        assert_eq!(result.trials_evaluated, 0);
+        assert_eq!(result.trials_skipped, 50);
+    }
+
+    #[test]
+    fn test_dead_code_elimination_warns_on_side_effects() {
+        let transformer = SemanticTransformer::new();
+        let stmt = Stmt::If {
+            condition: Expr::Int(1),
+            then_block: vec![Stmt::Assign {
+                name: "x".to_string(),
+                value: Expr::Int(1),
+            }],
+            else_block: vec![Stmt::Expr(Expr::Call {
+                name: "log".to_string(),
+                args: vec![var("x")],
+            })],
+        };
+
+        let result = transformer
+            .transform_stmt(stmt, TransformationType::DeadCodeElimination)
+            .unwrap();
+
+        assert_eq!(result.preservation_level, PreservationLevel::Likely);
+        assert_eq!(result.warnings.len(), 1);
+        let warning = &result.warnings[0];
+        assert_eq!(
+            warning.transformation,
+            TransformationType::DeadCodeElimination
+        );
+        assert_eq!(warning.location, "else branch of `if 1`");
+        assert_eq!(warning.snippet, "log(x)");
+        assert!(warning.reason.contains("side effects"));
+    }
+
+    #[test]
+    fn test_guaranteed_transform_has_no_warnings() {
+        let transformer = SemanticTransformer::new();
+        let stmt = Stmt::Expr(Expr::Call {
+            name: "log".to_string(),
+            args: vec![binop(Op::Add, Expr::Int(1), Expr::Int(2))],
+        });
+
+        let result = transformer
+            .transform_stmt(stmt, TransformationType::ConstantFolding)
+            .unwrap();
+
+        assert_eq!(result.preservation_level, PreservationLevel::Guaranteed);
+        assert!(result.warnings.is_empty());
+    }
+
+    fn assign(name: &str, value: Expr) -> Stmt {
+        Stmt::Assign {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_constant_propagation_folds_later_uses() {
+        let transformer = SemanticTransformer::new();
+        let stmts = vec![
+            assign("x", Expr::Int(2)),
+            assign("y", binop(Op::Add, var("x"), Expr::Int(3))),
+        ];
+
+        let result = transformer.propagate_constants(stmts);
+
+        assert_eq!(result[1], assign("y", Expr::Int(5)));
+    }
+
+    #[test]
+    fn test_constant_propagation_invalidated_by_reassignment() {
+        let transformer = SemanticTransformer::new();
+        let call = Expr::Call {
+            name: "f".to_string(),
+            args: vec![],
+        };
+        let stmts = vec![
+            assign("x", Expr::Int(2)),
+            assign("x", call),
+            assign("y", binop(Op::Add, var("x"), Expr::Int(3))),
+        ];
+
+        let result = transformer.propagate_constants(stmts);
+
+        assert_eq!(
+            result[2],
+            assign("y", binop(Op::Add, var("x"), Expr::Int(3)))
+        );
+    }
+
+    #[test]
+    fn test_constant_propagation_merges_branches_and_loops() {
+        let transformer = SemanticTransformer::new();
+        let stmts = vec![
+            assign("a", Expr::Int(1)),
+            assign("b", Expr::Int(1)),
+            Stmt::If {
+                condition: var("c"),
+                then_block: vec![assign("a", Expr::Int(2))],
+                else_block: vec![],
+            },
+            Stmt::Loop {
+                count: 3,
+                body: vec![assign("b", binop(Op::Add, var("b"), Expr::Int(1)))],
+            },
+            Stmt::Expr(binop(Op::Add, var("a"), var("b"))),
+        ];
+
+        let result = transformer.propagate_constants(stmts);
+
+        // `a` differs between branches and `b` changes in the loop body
+        assert_eq!(
+            result[3],
+            Stmt::Loop {
+                count: 3,
+                body: vec![assign("b", binop(Op::Add, var("b"), Expr::Int(1)))],
+            }
+        );
+        assert_eq!(result[4], Stmt::Expr(binop(Op::Add, var("a"), var("b"))));
+        assert_eq!(
+            transformer.get_preservation_level(TransformationType::ConstantPropagation),
+            PreservationLevel::Guaranteed
+        );
+    }
+
+    #[test]
+    fn test_hoist_invariants() {
+        let transformer = SemanticTransformer::new();
+        let accumulate = assign("sum", binop(Op::Add, var("sum"), var("c")));
+        let product = assign("t", binop(Op::Mul, var("a"), var("b")));
+        let original = Stmt::Loop {
+            count: 4,
+            body: vec![accumulate.clone(), product.clone()],
+        };
+
+        let hoisted = transformer.hoist_invariants(original.clone());
+
+        assert_eq!(
+            hoisted,
+            vec![
+                product,
+                Stmt::Loop {
+                    count: 4,
+                    body: vec![accumulate],
+                },
+            ]
+        );
+
+        let result = transformer
+            .transform_stmt(original.clone(), TransformationType::LoopInvariantHoisting)
+            .unwrap();
+        assert_eq!(result.changes_made, 1);
+        assert_eq!(result.preservation_level, PreservationLevel::Likely);
+
+        let mut checker = EquivalenceChecker::new();
+        checker.add_test_case(HashMap::from([
+            ("sum".to_string(), 1),
+            ("c".to_string(), 2),
+            ("a".to_string(), 3),
+            ("b".to_string(), 4),
+            ("t".to_string(), 0),
+        ]));
+        assert!(checker.statements_equivalent(&original, &result.transformed));
+    }
+
+    #[test]
+    fn test_hoist_invariants_blocked_by_calls_and_early_reads() {
+        let transformer = SemanticTransformer::new();
+        let product = assign("t", binop(Op::Mul, var("a"), var("b")));
+
+        let with_call = Stmt::Loop {
+            count: 2,
+            body: vec![
+                Stmt::Expr(Expr::Call {
+                    name: "f".to_string(),
+                    args: vec![],
+                }),
+                product.clone(),
+            ],
+        };
+        assert_eq!(
+            transformer.hoist_invariants(with_call.clone()),
+            vec![with_call]
+        );
+
+        let read_first = Stmt::Loop {
+            count: 2,
+            body: vec![assign("y", var("t")), product],
+        };
+        assert_eq!(
+            transformer.hoist_invariants(read_first.clone()),
+            vec![read_first]
+        );
+    }
 }
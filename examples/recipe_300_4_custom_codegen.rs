@@ -19,13 +19,16 @@
 //! - Formatting and pretty-printing
 //!
 //! ## Examples
-//! This file demonstrates three approaches:
+//! This file demonstrates four approaches:
 //! 1. Basic template-based generation for multiple languages
 //! 2. Struct/class generation with fields and methods
 //! 3. Function generation with type signatures
+//! 4. Enum generation with optional variant payloads
 
 use batuta_cookbook::Result;
+use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
+use std::path::Path;
 
 /// Target programming language for code generation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -87,7 +90,13 @@ impl TypeInfo {
 
     /// Convert to language-specific type string
     pub fn to_language_type(&self, lang: TargetLanguage) -> String {
-        let base_type = match lang {
+        self.to_language_type_with(lang, &TypeMap::new())
+    }
+
+    /// Convert to language-specific type string, consulting custom mappings first
+    #[must_use]
+    pub fn to_language_type_with(&self, lang: TargetLanguage, type_map: &TypeMap) -> String {
+        let base_type = type_map.get(lang, &self.name).unwrap_or(match lang {
             TargetLanguage::Rust => match self.name.as_str() {
                 "string" => "String",
                 "int" => "i64",
@@ -116,7 +125,7 @@ impl TypeInfo {
                 "bool" => "bool",
                 other => other,
             },
-        };
+        });
 
         let mut result = base_type.to_string();
 
@@ -142,6 +151,32 @@ impl TypeInfo {
     }
 }
 
+/// Registry of custom logical-to-concrete type mappings per language
+#[derive(Debug, Clone, Default)]
+pub struct TypeMap {
+    mappings: HashMap<(TargetLanguage, String), String>,
+}
+
+impl TypeMap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map `logical_name` to `concrete_type` for `lang`, replacing any previous mapping
+    pub fn register(&mut self, lang: TargetLanguage, logical_name: String, concrete_type: String) {
+        self.mappings.insert((lang, logical_name), concrete_type);
+    }
+
+    /// Look up the concrete type registered for `logical_name` in `lang`
+    #[must_use]
+    pub fn get(&self, lang: TargetLanguage, logical_name: &str) -> Option<&str> {
+        self.mappings
+            .get(&(lang, logical_name.to_string()))
+            .map(String::as_str)
+    }
+}
+
 /// Field definition for struct/class generation
 #[derive(Debug, Clone)]
 pub struct FieldSpec {
@@ -234,6 +269,10 @@ pub struct StructSpec {
     pub fields: Vec<FieldSpec>,
     pub methods: Vec<FunctionSpec>,
     pub doc_comment: Option<String>,
+    /// Derive macros for the Rust backend
+    pub derives: Vec<String>,
+    /// Whether to emit a constructor taking all fields
+    pub constructor: bool,
 }
 
 impl StructSpec {
@@ -243,6 +282,8 @@ impl StructSpec {
             fields: Vec::new(),
             methods: Vec::new(),
             doc_comment: None,
+            derives: vec!["Debug".to_string(), "Clone".to_string()],
+            constructor: false,
         }
     }
 
@@ -260,24 +301,118 @@ impl StructSpec {
         self.doc_comment = Some(doc);
         self
     }
+
+    /// Replace the derive list (defaults to `Debug, Clone`)
+    #[must_use]
+    pub fn with_derives(mut self, derives: Vec<String>) -> Self {
+        self.derives = derives;
+        self
+    }
+
+    /// Emit a constructor taking all fields
+    #[must_use]
+    pub fn with_constructor(mut self, constructor: bool) -> Self {
+        self.constructor = constructor;
+        self
+    }
+}
+
+/// Enum variant specification
+#[derive(Debug, Clone)]
+pub struct VariantSpec {
+    pub name: String,
+    pub payload: Option<TypeInfo>,
+}
+
+impl VariantSpec {
+    #[must_use]
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            payload: None,
+        }
+    }
+
+    /// Attach associated data to this variant
+    #[must_use]
+    pub fn with_payload(mut self, payload: TypeInfo) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+}
+
+/// Enum specification for code generation
+#[derive(Debug, Clone)]
+pub struct EnumSpec {
+    pub name: String,
+    pub variants: Vec<VariantSpec>,
+    pub doc_comment: Option<String>,
+}
+
+impl EnumSpec {
+    #[must_use]
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            variants: Vec::new(),
+            doc_comment: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_variant(mut self, variant: VariantSpec) -> Self {
+        self.variants.push(variant);
+        self
+    }
+
+    #[must_use]
+    pub fn with_doc(mut self, doc: String) -> Self {
+        self.doc_comment = Some(doc);
+        self
+    }
 }
 
 /// Code generator for multiple languages
 pub struct CodeGenerator {
     target_language: TargetLanguage,
-    _indent_size: usize,
+    type_map: TypeMap,
+    indent_size: usize,
 }
 
 impl CodeGenerator {
     pub fn new(target_language: TargetLanguage) -> Self {
         Self {
             target_language,
-            _indent_size: 4,
+            type_map: TypeMap::new(),
+            indent_size: 4,
         }
     }
 
+    /// Register a custom type mapping (e.g. `uuid` -> `String` for Rust)
+    pub fn register_type_mapping(
+        &mut self,
+        lang: TargetLanguage,
+        logical_name: String,
+        concrete_type: String,
+    ) {
+        self.type_map.register(lang, logical_name, concrete_type);
+    }
+
+    /// Resolve a type for the target language, including custom mappings
+    fn language_type(&self, type_info: &TypeInfo) -> String {
+        type_info.to_language_type_with(self.target_language, &self.type_map)
+    }
+
     /// Generate a struct/class from specification
     pub fn generate_struct(&self, spec: &StructSpec) -> Result<String> {
+        validate_identifier(&spec.name, self.target_language)?;
+        for field in &spec.fields {
+            validate_identifier(&self.field_name(&field.name), self.target_language)?;
+        }
+        for method in &spec.methods {
+            self.validate_function(method)?;
+        }
+
         let mut output = String::new();
 
         // Add doc comment
@@ -295,8 +430,44 @@ impl CodeGenerator {
         Ok(output)
     }
 
+    /// Generate a struct/class along with the SHA-256 hash of the code
+    ///
+    /// Generation is deterministic, so the same spec and generator settings
+    /// always produce the same hash; comparing it with the hash of a
+    /// committed file detects stale generated code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spec cannot be generated (see `generate_struct`).
+    pub fn generate_struct_with_hash(&self, spec: &StructSpec) -> Result<(String, String)> {
+        let code = self.generate_struct(spec)?;
+        let hash = content_hash(&code);
+        Ok((code, hash))
+    }
+
+    /// Whether the file at `path` already holds the code generated for `spec`
+    ///
+    /// Line endings are normalized before comparing, so a CRLF checkout of an
+    /// up-to-date file still matches. A missing file counts as changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spec cannot be generated or the file exists
+    /// but cannot be read.
+    pub fn check_unchanged(&self, path: &Path, spec: &StructSpec) -> Result<bool> {
+        let (_, hash) = self.generate_struct_with_hash(spec)?;
+        let existing = match std::fs::read_to_string(path) {
+            Ok(existing) => existing,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(content_hash(&existing.replace("\r\n", "\n")) == hash)
+    }
+
     /// Generate a function from specification
     pub fn generate_function(&self, spec: &FunctionSpec) -> Result<String> {
+        self.validate_function(spec)?;
+
         let mut output = String::new();
 
         // Add doc comment
@@ -314,6 +485,78 @@ impl CodeGenerator {
         Ok(output)
     }
 
+    /// Generate an enum from specification
+    ///
+    /// Rust variants carry their payload as tuple data, TypeScript uses an
+    /// `enum` for unit-only enums and a discriminated union otherwise, Python
+    /// emits an `Enum` subclass, and Go emits an `iota` const block. Python and
+    /// Go cannot attach payloads to enum values, so payload types are noted in
+    /// a comment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the enum name or an emitted variant name is not a
+    /// valid identifier.
+    pub fn generate_enum(&self, spec: &EnumSpec) -> Result<String> {
+        validate_identifier(&spec.name, self.target_language)?;
+        for variant in &spec.variants {
+            validate_identifier(
+                &self.variant_name(&spec.name, &variant.name),
+                self.target_language,
+            )?;
+        }
+
+        let mut output = String::new();
+
+        if let Some(doc) = &spec.doc_comment {
+            self.write_doc_comment(&mut output, doc)?;
+        }
+
+        match self.target_language {
+            TargetLanguage::Rust => self.generate_rust_enum(&mut output, spec),
+            TargetLanguage::Python => self.generate_python_enum(&mut output, spec),
+            TargetLanguage::TypeScript => self.generate_typescript_enum(&mut output, spec),
+            TargetLanguage::Go => self.generate_go_enum(&mut output, spec),
+        }
+        .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+
+        Ok(output)
+    }
+
+    /// Validate a function name and its parameter names
+    fn validate_function(&self, spec: &FunctionSpec) -> Result<()> {
+        validate_identifier(&self.function_name(&spec.name), self.target_language)?;
+        for param in &spec.params {
+            validate_identifier(&param.name, self.target_language)?;
+        }
+        Ok(())
+    }
+
+    /// Name a struct field is emitted under; Go capitalizes it to export it
+    fn field_name(&self, name: &str) -> String {
+        match self.target_language {
+            TargetLanguage::Go => capitalize_first(name),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Name a function or method is emitted under; Go capitalizes it to export it
+    fn function_name(&self, name: &str) -> String {
+        match self.target_language {
+            TargetLanguage::Go => capitalize_first(name),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Name an enum variant is emitted under in the target language
+    fn variant_name(&self, enum_name: &str, variant: &str) -> String {
+        match self.target_language {
+            TargetLanguage::Rust | TargetLanguage::TypeScript => to_pascal_case(variant),
+            TargetLanguage::Python => to_screaming_snake_case(variant),
+            TargetLanguage::Go => format!("{enum_name}{}", to_pascal_case(variant)),
+        }
+    }
+
     fn write_doc_comment(&self, output: &mut String, doc: &str) -> Result<()> {
         let prefix = self.target_language.comment_prefix();
         for line in doc.lines() {
@@ -324,9 +567,50 @@ impl CodeGenerator {
         Ok(())
     }
 
+    /// Write a (possibly multi-line) body re-indented to `depth` blocks
+    ///
+    /// Common leading whitespace is stripped so relative indentation within the
+    /// body is preserved. Empty Python bodies get a `pass` statement.
+    fn write_body(&self, output: &mut String, body: &str, depth: usize) -> Result<()> {
+        let indent = " ".repeat(self.indent_size * depth);
+        let lines: Vec<&str> = body.lines().collect();
+        let first = lines.iter().position(|l| !l.trim().is_empty());
+        let last = lines.iter().rposition(|l| !l.trim().is_empty());
+
+        let (Some(first), Some(last)) = (first, last) else {
+            if self.target_language == TargetLanguage::Python {
+                writeln!(output, "{indent}pass")
+                    .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+            }
+            return Ok(());
+        };
+
+        let lines = &lines[first..=last];
+        let common = lines
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        for line in lines {
+            if line.trim().is_empty() {
+                writeln!(output)
+            } else {
+                let line = line.get(common..).unwrap_or_else(|| line.trim_start());
+                writeln!(output, "{indent}{}", line.trim_end())
+            }
+            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+        }
+
+        Ok(())
+    }
+
     fn generate_rust_struct(&self, output: &mut String, spec: &StructSpec) -> Result<()> {
-        writeln!(output, "#[derive(Debug, Clone)]")
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        if !spec.derives.is_empty() {
+            writeln!(output, "#[derive({})]", spec.derives.join(", "))
+                .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+        }
         writeln!(output, "pub struct {} {{", spec.name)
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
@@ -340,7 +624,7 @@ impl CodeGenerator {
                 output,
                 "    pub {}: {},",
                 field.name,
-                field.type_info.to_language_type(self.target_language)
+                self.language_type(&field.type_info)
             )
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
         }
@@ -348,11 +632,15 @@ impl CodeGenerator {
         writeln!(output, "}}")
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
-        // Generate methods
-        if !spec.methods.is_empty() {
+        // Generate constructor and methods
+        if spec.constructor || !spec.methods.is_empty() {
             writeln!(output, "\nimpl {} {{", spec.name)
                 .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
+            if spec.constructor {
+                self.generate_rust_constructor(output, spec)?;
+            }
+
             for method in &spec.methods {
                 self.generate_rust_method(output, method)?;
             }
@@ -364,6 +652,28 @@ impl CodeGenerator {
         Ok(())
     }
 
+    fn generate_rust_constructor(&self, output: &mut String, spec: &StructSpec) -> Result<()> {
+        let params: Vec<String> = spec
+            .fields
+            .iter()
+            .map(|f| format!("{}: {}", f.name, self.language_type(&f.type_info)))
+            .collect();
+        let field_names: Vec<&str> = spec.fields.iter().map(|f| f.name.as_str()).collect();
+
+        writeln!(output, "    pub fn new({}) -> Self {{", params.join(", "))
+            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+        if field_names.is_empty() {
+            writeln!(output, "        Self {{}}")
+        } else {
+            writeln!(output, "        Self {{ {} }}", field_names.join(", "))
+        }
+        .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+        writeln!(output, "    }}")
+            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+
+        Ok(())
+    }
+
     fn generate_rust_method(&self, output: &mut String, spec: &FunctionSpec) -> Result<()> {
         if let Some(doc) = &spec.doc_comment {
             writeln!(output, "    /// {}", doc)
@@ -373,19 +683,13 @@ impl CodeGenerator {
         let params: Vec<String> = spec
             .params
             .iter()
-            .map(|p| {
-                format!(
-                    "{}: {}",
-                    p.name,
-                    p.type_info.to_language_type(self.target_language)
-                )
-            })
+            .map(|p| format!("{}: {}", p.name, self.language_type(&p.type_info)))
             .collect();
 
         let return_type = spec
             .return_type
             .as_ref()
-            .map(|t| format!(" -> {}", t.to_language_type(self.target_language)))
+            .map(|t| format!(" -> {}", self.language_type(t)))
             .unwrap_or_default();
 
         writeln!(
@@ -398,8 +702,7 @@ impl CodeGenerator {
         )
         .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
-        writeln!(output, "        {}", spec.body)
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        self.write_body(output, &spec.body, 2)?;
         writeln!(output, "    }}")
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
@@ -411,13 +714,34 @@ impl CodeGenerator {
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
         // __init__ method
-        writeln!(output, "    def __init__(self):")
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        if spec.constructor {
+            let mut params = vec!["self".to_string()];
+            params.extend(
+                spec.fields
+                    .iter()
+                    .map(|f| format!("{}: {}", f.name, self.language_type(&f.type_info))),
+            );
+            writeln!(output, "    def __init__({}):", params.join(", "))
+                .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+
+            for field in &spec.fields {
+                writeln!(output, "        self.{0} = {0}", field.name)
+                    .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+            }
+        } else {
+            writeln!(output, "    def __init__(self):")
+                .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+
+            for field in &spec.fields {
+                let default = field.default_value.as_deref().unwrap_or("None");
+                writeln!(output, "        self.{} = {}", field.name, default)
+                    .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+            }
+        }
 
-        for field in &spec.fields {
-            let default = field.default_value.as_deref().unwrap_or("None");
-            writeln!(output, "        self.{} = {}", field.name, default)
-                .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        if spec.fields.is_empty() {
+            writeln!(output, "        pass")
+                .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
         }
 
         // Methods
@@ -434,19 +758,13 @@ impl CodeGenerator {
         let params: Vec<String> = spec
             .params
             .iter()
-            .map(|p| {
-                format!(
-                    "{}: {}",
-                    p.name,
-                    p.type_info.to_language_type(self.target_language)
-                )
-            })
+            .map(|p| format!("{}: {}", p.name, self.language_type(&p.type_info)))
             .collect();
 
         let return_annotation = spec
             .return_type
             .as_ref()
-            .map(|t| format!(" -> {}", t.to_language_type(self.target_language)))
+            .map(|t| format!(" -> {}", self.language_type(t)))
             .unwrap_or_default();
 
         writeln!(
@@ -459,8 +777,7 @@ impl CodeGenerator {
         )
         .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
-        writeln!(output, "        {}", spec.body)
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        self.write_body(output, &spec.body, 2)?;
 
         Ok(())
     }
@@ -475,7 +792,7 @@ impl CodeGenerator {
                 output,
                 "    {}: {};",
                 field.name,
-                field.type_info.to_language_type(self.target_language)
+                self.language_type(&field.type_info)
             )
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
         }
@@ -510,19 +827,13 @@ impl CodeGenerator {
         let params: Vec<String> = spec
             .params
             .iter()
-            .map(|p| {
-                format!(
-                    "{}: {}",
-                    p.name,
-                    p.type_info.to_language_type(self.target_language)
-                )
-            })
+            .map(|p| format!("{}: {}", p.name, self.language_type(&p.type_info)))
             .collect();
 
         let return_type = spec
             .return_type
             .as_ref()
-            .map(|t| format!(": {}", t.to_language_type(self.target_language)))
+            .map(|t| format!(": {}", self.language_type(t)))
             .unwrap_or_else(|| ": void".to_string());
 
         writeln!(
@@ -534,8 +845,7 @@ impl CodeGenerator {
         )
         .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
-        writeln!(output, "        {}", spec.body)
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        self.write_body(output, &spec.body, 2)?;
         writeln!(output, "    }}")
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
@@ -547,13 +857,12 @@ impl CodeGenerator {
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
         for field in &spec.fields {
-            // Capitalize first letter for exported fields
-            let field_name = capitalize_first(&field.name);
+            let field_name = self.field_name(&field.name);
             writeln!(
                 output,
                 "    {} {}",
                 field_name,
-                field.type_info.to_language_type(self.target_language)
+                self.language_type(&field.type_info)
             )
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
         }
@@ -561,6 +870,55 @@ impl CodeGenerator {
         writeln!(output, "}}")
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
+        // Methods become receiver functions
+        for method in &spec.methods {
+            writeln!(output)
+                .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+            self.generate_go_method(output, &spec.name, method)?;
+        }
+
+        Ok(())
+    }
+
+    fn generate_go_method(
+        &self,
+        output: &mut String,
+        struct_name: &str,
+        spec: &FunctionSpec,
+    ) -> Result<()> {
+        if let Some(doc) = &spec.doc_comment {
+            self.write_doc_comment(output, doc)?;
+        }
+
+        let params: Vec<String> = spec
+            .params
+            .iter()
+            .map(|p| format!("{} {}", p.name, self.language_type(&p.type_info)))
+            .collect();
+
+        let return_type = spec
+            .return_type
+            .as_ref()
+            .map(|t| format!(" {}", self.language_type(t)))
+            .unwrap_or_default();
+
+        let receiver: String = struct_name
+            .chars()
+            .next()
+            .map_or_else(|| "s".to_string(), |c| c.to_lowercase().collect());
+
+        writeln!(
+            output,
+            "func ({receiver} *{struct_name}) {}({}){return_type} {{",
+            self.function_name(&spec.name),
+            params.join(", "),
+        )
+        .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+
+        self.write_body(output, &spec.body, 1)?;
+        writeln!(output, "}}")
+            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {e}")))?;
+
         Ok(())
     }
 
@@ -568,19 +926,13 @@ impl CodeGenerator {
         let params: Vec<String> = spec
             .params
             .iter()
-            .map(|p| {
-                format!(
-                    "{}: {}",
-                    p.name,
-                    p.type_info.to_language_type(self.target_language)
-                )
-            })
+            .map(|p| format!("{}: {}", p.name, self.language_type(&p.type_info)))
             .collect();
 
         let return_type = spec
             .return_type
             .as_ref()
-            .map(|t| format!(" -> {}", t.to_language_type(self.target_language)))
+            .map(|t| format!(" -> {}", self.language_type(t)))
             .unwrap_or_default();
 
         writeln!(
@@ -592,8 +944,7 @@ impl CodeGenerator {
         )
         .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
-        writeln!(output, "    {}", spec.body)
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        self.write_body(output, &spec.body, 1)?;
         writeln!(output, "}}")
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
@@ -604,19 +955,13 @@ impl CodeGenerator {
         let params: Vec<String> = spec
             .params
             .iter()
-            .map(|p| {
-                format!(
-                    "{}: {}",
-                    p.name,
-                    p.type_info.to_language_type(self.target_language)
-                )
-            })
+            .map(|p| format!("{}: {}", p.name, self.language_type(&p.type_info)))
             .collect();
 
         let return_annotation = spec
             .return_type
             .as_ref()
-            .map(|t| format!(" -> {}", t.to_language_type(self.target_language)))
+            .map(|t| format!(" -> {}", self.language_type(t)))
             .unwrap_or_default();
 
         writeln!(
@@ -628,8 +973,7 @@ impl CodeGenerator {
         )
         .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
-        writeln!(output, "    {}", spec.body)
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        self.write_body(output, &spec.body, 1)?;
 
         Ok(())
     }
@@ -638,19 +982,13 @@ impl CodeGenerator {
         let params: Vec<String> = spec
             .params
             .iter()
-            .map(|p| {
-                format!(
-                    "{}: {}",
-                    p.name,
-                    p.type_info.to_language_type(self.target_language)
-                )
-            })
+            .map(|p| format!("{}: {}", p.name, self.language_type(&p.type_info)))
             .collect();
 
         let return_type = spec
             .return_type
             .as_ref()
-            .map(|t| format!(": {}", t.to_language_type(self.target_language)))
+            .map(|t| format!(": {}", self.language_type(t)))
             .unwrap_or_else(|| ": void".to_string());
 
         writeln!(
@@ -662,8 +1000,7 @@ impl CodeGenerator {
         )
         .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
-        writeln!(output, "    {}", spec.body)
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        self.write_body(output, &spec.body, 1)?;
         writeln!(output, "}}")
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
@@ -674,37 +1011,283 @@ impl CodeGenerator {
         let params: Vec<String> = spec
             .params
             .iter()
-            .map(|p| {
-                format!(
-                    "{} {}",
-                    p.name,
-                    p.type_info.to_language_type(self.target_language)
-                )
-            })
+            .map(|p| format!("{} {}", p.name, self.language_type(&p.type_info)))
             .collect();
 
         let return_type = spec
             .return_type
             .as_ref()
-            .map(|t| format!(" {}", t.to_language_type(self.target_language)))
+            .map(|t| format!(" {}", self.language_type(t)))
             .unwrap_or_default();
 
         writeln!(
             output,
             "func {}({}){} {{",
-            capitalize_first(&spec.name),
+            self.function_name(&spec.name),
             params.join(", "),
             return_type
         )
         .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
-        writeln!(output, "    {}", spec.body)
-            .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
+        self.write_body(output, &spec.body, 1)?;
         writeln!(output, "}}")
             .map_err(|e| batuta_cookbook::Error::Other(format!("Failed to write: {}", e)))?;
 
         Ok(())
     }
+
+    fn generate_rust_enum(&self, output: &mut String, spec: &EnumSpec) -> std::fmt::Result {
+        writeln!(output, "#[derive(Debug, Clone)]")?;
+        writeln!(output, "pub enum {} {{", spec.name)?;
+        for variant in &spec.variants {
+            let name = self.variant_name(&spec.name, &variant.name);
+            match &variant.payload {
+                Some(payload) => writeln!(output, "    {name}({}),", self.language_type(payload))?,
+                None => writeln!(output, "    {name},")?,
+            }
+        }
+        writeln!(output, "}}")
+    }
+
+    fn generate_python_enum(&self, output: &mut String, spec: &EnumSpec) -> std::fmt::Result {
+        writeln!(output, "class {}(Enum):", spec.name)?;
+        if spec.variants.is_empty() {
+            return writeln!(output, "    pass");
+        }
+        for variant in &spec.variants {
+            let name = self.variant_name(&spec.name, &variant.name);
+            match &variant.payload {
+                Some(payload) => writeln!(
+                    output,
+                    "    {name} = \"{}\"  # payload: {}",
+                    variant.name,
+                    self.language_type(payload)
+                )?,
+                None => writeln!(output, "    {name} = \"{}\"", variant.name)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_typescript_enum(&self, output: &mut String, spec: &EnumSpec) -> std::fmt::Result {
+        if spec.variants.iter().all(|v| v.payload.is_none()) {
+            writeln!(output, "enum {} {{", spec.name)?;
+            for variant in &spec.variants {
+                writeln!(
+                    output,
+                    "    {} = \"{}\",",
+                    self.variant_name(&spec.name, &variant.name),
+                    variant.name
+                )?;
+            }
+            return writeln!(output, "}}");
+        }
+
+        writeln!(output, "type {} =", spec.name)?;
+        let arms: Vec<String> = spec
+            .variants
+            .iter()
+            .map(|variant| match &variant.payload {
+                Some(payload) => format!(
+                    "{{ kind: \"{}\"; value: {} }}",
+                    variant.name,
+                    self.language_type(payload)
+                ),
+                None => format!("{{ kind: \"{}\" }}", variant.name),
+            })
+            .collect();
+        writeln!(output, "    | {};", arms.join("\n    | "))
+    }
+
+    fn generate_go_enum(&self, output: &mut String, spec: &EnumSpec) -> std::fmt::Result {
+        writeln!(output, "type {} int", spec.name)?;
+        writeln!(output)?;
+        writeln!(output, "const (")?;
+        for (i, variant) in spec.variants.iter().enumerate() {
+            let name = self.variant_name(&spec.name, &variant.name);
+            let value = if i == 0 {
+                format!(" {} = iota", spec.name)
+            } else {
+                String::new()
+            };
+            match &variant.payload {
+                Some(payload) => writeln!(
+                    output,
+                    "    {name}{value} // payload: {}",
+                    self.language_type(payload)
+                )?,
+                None => writeln!(output, "    {name}{value}")?,
+            }
+        }
+        writeln!(output, ")")
+    }
+}
+
+/// Split an identifier into lowercase words on `_`, `-`, and case boundaries
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+
+    for c in name.chars() {
+        if c == '_' || c == '-' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Convert an identifier to `PascalCase`
+fn to_pascal_case(name: &str) -> String {
+    split_words(name)
+        .iter()
+        .map(|w| capitalize_first(w))
+        .collect()
+}
+
+/// Convert an identifier to `SCREAMING_SNAKE_CASE`
+fn to_screaming_snake_case(name: &str) -> String {
+    split_words(name)
+        .iter()
+        .map(|w| w.to_uppercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Reserved words that cannot be used as identifiers
+fn reserved_words(lang: TargetLanguage) -> &'static [&'static str] {
+    match lang {
+        TargetLanguage::Rust => &[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+            "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+            "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+            "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become",
+            "box", "do", "final", "macro", "override", "priv", "try", "typeof", "unsized",
+            "virtual", "yield",
+        ],
+        TargetLanguage::Python => &[
+            "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+            "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+            "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+            "return", "try", "while", "with", "yield",
+        ],
+        TargetLanguage::TypeScript => &[
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "import",
+            "in",
+            "instanceof",
+            "new",
+            "null",
+            "return",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+        ],
+        TargetLanguage::Go => &[
+            "break",
+            "case",
+            "chan",
+            "const",
+            "continue",
+            "default",
+            "defer",
+            "else",
+            "fallthrough",
+            "for",
+            "func",
+            "go",
+            "goto",
+            "if",
+            "import",
+            "interface",
+            "map",
+            "package",
+            "range",
+            "return",
+            "select",
+            "struct",
+            "switch",
+            "type",
+            "var",
+        ],
+    }
+}
+
+/// Check that `name` is a valid identifier in `lang`
+///
+/// Rejects empty names, names starting with a digit, characters other than
+/// ASCII letters, digits, and `_`, and reserved words of the target language.
+///
+/// # Errors
+///
+/// Returns `Error::Other` naming the offending identifier if it is invalid.
+pub fn validate_identifier(name: &str, lang: TargetLanguage) -> Result<()> {
+    let invalid = |reason: &str| {
+        Err(batuta_cookbook::Error::Other(format!(
+            "Invalid {lang:?} identifier '{name}': {reason}"
+        )))
+    };
+
+    match name.chars().next() {
+        None => return invalid("name is empty"),
+        Some(c) if c.is_ascii_digit() => return invalid("starts with a digit"),
+        _ => {}
+    }
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return invalid("contains characters other than letters, digits, or '_'");
+    }
+
+    if reserved_words(lang).contains(&name) {
+        return invalid("reserved word");
+    }
+
+    Ok(())
+}
+
+/// Hex-encoded SHA-256 of generated code
+fn content_hash(code: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!("{:x}", Sha256::digest(code.as_bytes()))
 }
 
 /// Helper function to capitalize first letter
@@ -812,10 +1395,41 @@ pub fn example_3_function_generation() -> Result<()> {
     Ok(())
 }
 
+//
+// Example 4: Generate enums with optional payloads
+//
+pub fn example_4_enum_generation() -> Result<()> {
+    println!("\n=== Example 4: Enum Generation ===\n");
+
+    let spec = EnumSpec::new("OrderStatus".to_string())
+        .with_doc("Lifecycle of an order".to_string())
+        .with_variant(VariantSpec::new("pending".to_string()))
+        .with_variant(VariantSpec::new("shipped".to_string()))
+        .with_variant(
+            VariantSpec::new("cancelled_with_reason".to_string())
+                .with_payload(TypeInfo::new("string".to_string())),
+        );
+
+    for lang in [
+        TargetLanguage::Rust,
+        TargetLanguage::Python,
+        TargetLanguage::TypeScript,
+        TargetLanguage::Go,
+    ] {
+        println!("--- {lang:?} ---");
+        let generator = CodeGenerator::new(lang);
+        let code = generator.generate_enum(&spec)?;
+        println!("{code}\n");
+    }
+
+    Ok(())
+}
+
 fn main() -> Result<()> {
     example_1_multi_language_struct()?;
     example_2_class_with_methods()?;
     example_3_function_generation()?;
+    example_4_enum_generation()?;
     Ok(())
 }
 
@@ -1021,4 +1635,409 @@ mod tests {
         assert!(rust_type.contains("Vec"));
         assert!(rust_type.contains("Option"));
     }
+
+    #[test]
+    fn test_custom_type_mapping() {
+        let spec = StructSpec::new("User".to_string())
+            .with_field(FieldSpec::new(
+                "id".to_string(),
+                TypeInfo::new("uuid".to_string()),
+            ))
+            .with_method(
+                FunctionSpec::new("get_id".to_string())
+                    .with_return(TypeInfo::new("uuid".to_string()))
+                    .with_body("return self.id".to_string()),
+            );
+
+        let mut rust_generator = CodeGenerator::new(TargetLanguage::Rust);
+        rust_generator.register_type_mapping(
+            TargetLanguage::Rust,
+            "uuid".to_string(),
+            "String".to_string(),
+        );
+        rust_generator.register_type_mapping(
+            TargetLanguage::Python,
+            "uuid".to_string(),
+            "str".to_string(),
+        );
+        let rust_code = rust_generator.generate_struct(&spec).unwrap();
+        assert!(rust_code.contains("pub id: String,"));
+        assert!(rust_code.contains("pub fn get_id(&self) -> String"));
+
+        let mut python_generator = CodeGenerator::new(TargetLanguage::Python);
+        python_generator.register_type_mapping(
+            TargetLanguage::Python,
+            "uuid".to_string(),
+            "str".to_string(),
+        );
+        let python_code = python_generator.generate_struct(&spec).unwrap();
+        assert!(python_code.contains("def get_id(self) -> str:"));
+
+        // Unregistered languages fall through to the logical name
+        let go_code = CodeGenerator::new(TargetLanguage::Go)
+            .generate_struct(&spec)
+            .unwrap();
+        assert!(go_code.contains("uuid"));
+    }
+
+    #[test]
+    fn test_type_map_overrides_builtin() {
+        let mut type_map = TypeMap::new();
+        type_map.register(TargetLanguage::Rust, "int".to_string(), "i32".to_string());
+
+        let type_info = TypeInfo::new("int".to_string()).array();
+        assert_eq!(
+            type_info.to_language_type_with(TargetLanguage::Rust, &type_map),
+            "Vec<i32>"
+        );
+        assert_eq!(
+            type_info.to_language_type_with(TargetLanguage::Go, &type_map),
+            "[]int64"
+        );
+        assert_eq!(type_info.to_language_type(TargetLanguage::Rust), "Vec<i64>");
+    }
+
+    fn point_spec() -> StructSpec {
+        StructSpec::new("Point".to_string())
+            .with_field(FieldSpec::new(
+                "x".to_string(),
+                TypeInfo::new("float".to_string()),
+            ))
+            .with_field(FieldSpec::new(
+                "label".to_string(),
+                TypeInfo::new("string".to_string()).optional(),
+            ))
+    }
+
+    #[test]
+    fn test_default_derives_without_constructor() {
+        let code = CodeGenerator::new(TargetLanguage::Rust)
+            .generate_struct(&point_spec())
+            .unwrap();
+
+        assert!(code.contains("#[derive(Debug, Clone)]"));
+        assert!(!code.contains("impl Point"));
+        assert!(!code.contains("pub fn new"));
+    }
+
+    #[test]
+    fn test_custom_derives() {
+        let spec = point_spec().with_derives(vec![
+            "Debug".to_string(),
+            "PartialEq".to_string(),
+            "Serialize".to_string(),
+        ]);
+        let code = CodeGenerator::new(TargetLanguage::Rust)
+            .generate_struct(&spec)
+            .unwrap();
+
+        assert!(code.contains("#[derive(Debug, PartialEq, Serialize)]"));
+        assert!(!code.contains("Clone"));
+    }
+
+    #[test]
+    fn test_rust_constructor() {
+        let spec = point_spec().with_constructor(true);
+        let code = CodeGenerator::new(TargetLanguage::Rust)
+            .generate_struct(&spec)
+            .unwrap();
+
+        assert!(code.contains("impl Point {"));
+        assert!(code.contains("pub fn new(x: f64, label: Option<String>) -> Self {"));
+        assert!(code.contains("Self { x, label }"));
+    }
+
+    #[test]
+    fn test_python_typed_init() {
+        let spec = point_spec().with_constructor(true);
+        let code = CodeGenerator::new(TargetLanguage::Python)
+            .generate_struct(&spec)
+            .unwrap();
+
+        assert!(code.contains("def __init__(self, x: float, label: Optional[str]):"));
+        assert!(code.contains("self.x = x"));
+        assert!(code.contains("self.label = label"));
+        assert!(!code.contains("= None"));
+    }
+
+    #[test]
+    fn test_python_multiline_method_body() {
+        let spec = StructSpec::new("Accumulator".to_string()).with_method(
+            FunctionSpec::new("add".to_string())
+                .with_param(ParamSpec::new(
+                    "value".to_string(),
+                    TypeInfo::new("int".to_string()),
+                ))
+                .with_body(
+                    "total = self.total + value\nself.total = total\nreturn total".to_string(),
+                ),
+        );
+
+        let code = CodeGenerator::new(TargetLanguage::Python)
+            .generate_struct(&spec)
+            .unwrap();
+
+        assert!(code.contains(
+            "    def add(self, value: int):\n        total = self.total + value\n        self.total = total\n        return total\n"
+        ));
+    }
+
+    #[test]
+    fn test_nested_body_keeps_relative_indentation() {
+        let spec = FunctionSpec::new("check".to_string())
+            .with_body("    if x:\n        return 1\n    return 0".to_string());
+
+        let code = CodeGenerator::new(TargetLanguage::Python)
+            .generate_function(&spec)
+            .unwrap();
+
+        assert!(code.contains("    if x:\n        return 1\n    return 0\n"));
+    }
+
+    #[test]
+    fn test_empty_python_body_emits_pass() {
+        let spec =
+            StructSpec::new("Empty".to_string()).with_method(FunctionSpec::new("noop".to_string()));
+
+        let code = CodeGenerator::new(TargetLanguage::Python)
+            .generate_struct(&spec)
+            .unwrap();
+
+        assert!(code.contains("    def noop(self):\n        pass\n"));
+
+        let rust_code = CodeGenerator::new(TargetLanguage::Rust)
+            .generate_function(&FunctionSpec::new("noop".to_string()))
+            .unwrap();
+        assert!(!rust_code.contains("pass"));
+    }
+
+    #[test]
+    fn test_go_struct_methods() {
+        let spec = StructSpec::new("Counter".to_string())
+            .with_field(FieldSpec::new(
+                "count".to_string(),
+                TypeInfo::new("int".to_string()),
+            ))
+            .with_method(
+                FunctionSpec::new("increment".to_string())
+                    .with_doc("Increment adds step to the counter".to_string())
+                    .with_param(ParamSpec::new(
+                        "step".to_string(),
+                        TypeInfo::new("int".to_string()),
+                    ))
+                    .with_return(TypeInfo::new("int".to_string()))
+                    .with_body("c.Count += step\nreturn c.Count".to_string()),
+            );
+
+        let code = CodeGenerator::new(TargetLanguage::Go)
+            .generate_struct(&spec)
+            .unwrap();
+
+        assert!(code.contains("type Counter struct {"));
+        assert!(code.contains(
+            "// Increment adds step to the counter\nfunc (c *Counter) Increment(step int64) int64 {\n"
+        ));
+        assert!(code.contains("    c.Count += step\n    return c.Count\n}"));
+    }
+
+    #[test]
+    fn test_rust_keyword_field_rejected() {
+        let spec = StructSpec::new("Pattern".to_string()).with_field(FieldSpec::new(
+            "match".to_string(),
+            TypeInfo::new("string".to_string()),
+        ));
+
+        let err = CodeGenerator::new(TargetLanguage::Rust)
+            .generate_struct(&spec)
+            .unwrap_err();
+        assert!(err.to_string().contains("'match'"));
+
+        // `match` is a valid Python identifier
+        assert!(CodeGenerator::new(TargetLanguage::Python)
+            .generate_struct(&spec)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validation_checks_emitted_names() {
+        // Go exports fields and functions as `Type` and `Func`
+        let spec = StructSpec::new("Token".to_string()).with_field(FieldSpec::new(
+            "type".to_string(),
+            TypeInfo::new("string".to_string()),
+        ));
+        let go = CodeGenerator::new(TargetLanguage::Go);
+        assert!(go
+            .generate_struct(&spec)
+            .unwrap()
+            .contains("    Type string\n"));
+        assert!(go
+            .generate_function(&FunctionSpec::new("func".to_string()))
+            .unwrap()
+            .contains("func Func() {"));
+        assert!(CodeGenerator::new(TargetLanguage::Rust)
+            .generate_struct(&spec)
+            .is_err());
+
+        // Rust variants are emitted in PascalCase, so `match` becomes `Match`
+        let rust = CodeGenerator::new(TargetLanguage::Rust);
+        let keywords = EnumSpec::new("Keyword".to_string())
+            .with_variant(VariantSpec::new("match".to_string()));
+        assert!(rust
+            .generate_enum(&keywords)
+            .unwrap()
+            .contains("    Match,\n"));
+
+        // ...while `self` becomes the keyword `Self`
+        let receiver = EnumSpec::new("Receiver".to_string())
+            .with_variant(VariantSpec::new("self".to_string()));
+        let err = rust.generate_enum(&receiver).unwrap_err();
+        assert!(err.to_string().contains("'Self'"));
+
+        // Python variants are emitted as `NONE`, not the keyword `None`
+        let python =
+            EnumSpec::new("Mode".to_string()).with_variant(VariantSpec::new("None".to_string()));
+        assert!(CodeGenerator::new(TargetLanguage::Python)
+            .generate_enum(&python)
+            .unwrap()
+            .contains("    NONE = \"None\""));
+    }
+
+    #[test]
+    fn test_validate_identifier() {
+        assert!(validate_identifier("user_name", TargetLanguage::Rust).is_ok());
+        assert!(validate_identifier("_private2", TargetLanguage::Python).is_ok());
+        assert!(validate_identifier("", TargetLanguage::Rust).is_err());
+        assert!(validate_identifier("2x", TargetLanguage::Go).is_err());
+        assert!(validate_identifier("my-field", TargetLanguage::TypeScript).is_err());
+        assert!(validate_identifier("fn", TargetLanguage::Rust).is_err());
+        assert!(validate_identifier("lambda", TargetLanguage::Python).is_err());
+        assert!(validate_identifier("func", TargetLanguage::Go).is_err());
+    }
+
+    #[test]
+    fn test_invalid_param_name_rejected() {
+        let spec = FunctionSpec::new("compute".to_string()).with_param(ParamSpec::new(
+            "class".to_string(),
+            TypeInfo::new("int".to_string()),
+        ));
+
+        assert!(CodeGenerator::new(TargetLanguage::Python)
+            .generate_function(&spec)
+            .is_err());
+    }
+
+    fn status_enum() -> EnumSpec {
+        EnumSpec::new("Status".to_string())
+            .with_variant(VariantSpec::new("active".to_string()))
+            .with_variant(VariantSpec::new("pending_review".to_string()))
+            .with_variant(
+                VariantSpec::new("failed".to_string())
+                    .with_payload(TypeInfo::new("string".to_string())),
+            )
+    }
+
+    #[test]
+    fn test_generate_rust_enum() {
+        let code = CodeGenerator::new(TargetLanguage::Rust)
+            .generate_enum(&status_enum())
+            .unwrap();
+
+        assert!(code.contains("pub enum Status {"));
+        assert!(code.contains("    Active,\n"));
+        assert!(code.contains("    PendingReview,\n"));
+        assert!(code.contains("    Failed(String),\n"));
+    }
+
+    #[test]
+    fn test_generate_typescript_enum() {
+        let unit_only = EnumSpec::new("Color".to_string())
+            .with_variant(VariantSpec::new("red".to_string()))
+            .with_variant(VariantSpec::new("dark_green".to_string()))
+            .with_variant(VariantSpec::new("blue".to_string()));
+        let code = CodeGenerator::new(TargetLanguage::TypeScript)
+            .generate_enum(&unit_only)
+            .unwrap();
+
+        assert!(code.contains("enum Color {"));
+        assert!(code.contains("    Red = \"red\","));
+        assert!(code.contains("    DarkGreen = \"dark_green\","));
+        assert!(code.contains("    Blue = \"blue\","));
+
+        let union = CodeGenerator::new(TargetLanguage::TypeScript)
+            .generate_enum(&status_enum())
+            .unwrap();
+        assert!(union.contains("type Status ="));
+        assert!(union.contains("| { kind: \"pending_review\" }"));
+        assert!(union.contains("| { kind: \"failed\"; value: string }"));
+    }
+
+    #[test]
+    fn test_generate_python_and_go_enums() {
+        let python = CodeGenerator::new(TargetLanguage::Python)
+            .generate_enum(&status_enum())
+            .unwrap();
+        assert!(python.contains("class Status(Enum):"));
+        assert!(python.contains("    PENDING_REVIEW = \"pending_review\""));
+
+        let go = CodeGenerator::new(TargetLanguage::Go)
+            .generate_enum(&status_enum())
+            .unwrap();
+        assert!(go.contains("type Status int"));
+        assert!(go.contains("    StatusActive Status = iota\n"));
+        assert!(go.contains("    StatusPendingReview\n"));
+    }
+
+    #[test]
+    fn test_generated_struct_hash_is_stable() {
+        let point = || {
+            StructSpec::new("Point".to_string())
+                .with_field(FieldSpec::new(
+                    "x".to_string(),
+                    TypeInfo::new("int".to_string()),
+                ))
+                .with_field(FieldSpec::new(
+                    "y".to_string(),
+                    TypeInfo::new("int".to_string()),
+                ))
+        };
+        let generator = CodeGenerator::new(TargetLanguage::Rust);
+
+        let (code, hash) = generator.generate_struct_with_hash(&point()).unwrap();
+        let (_, same_hash) = generator.generate_struct_with_hash(&point()).unwrap();
+        assert_eq!(code, generator.generate_struct(&point()).unwrap());
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, same_hash);
+
+        let changed = point().with_field(FieldSpec::new(
+            "z".to_string(),
+            TypeInfo::new("int".to_string()),
+        ));
+        let (_, changed_hash) = generator.generate_struct_with_hash(&changed).unwrap();
+        assert_ne!(hash, changed_hash);
+    }
+
+    #[test]
+    fn test_check_unchanged_against_file() {
+        use tempfile::TempDir;
+
+        let spec = StructSpec::new("Point".to_string()).with_field(FieldSpec::new(
+            "x".to_string(),
+            TypeInfo::new("int".to_string()),
+        ));
+        let generator = CodeGenerator::new(TargetLanguage::Rust);
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("point.rs");
+
+        assert!(!generator.check_unchanged(&path, &spec).unwrap());
+
+        let code = generator.generate_struct(&spec).unwrap();
+        std::fs::write(&path, code.replace('\n', "\r\n")).unwrap();
+        assert!(generator.check_unchanged(&path, &spec).unwrap());
+
+        let changed = spec.with_field(FieldSpec::new(
+            "y".to_string(),
+            TypeInfo::new("int".to_string()),
+        ));
+        assert!(!generator.check_unchanged(&path, &changed).unwrap());
+    }
 }
@@ -19,7 +19,7 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::{Duration, Instant};
 
 type Result<T> = std::result::Result<T, String>;
@@ -76,6 +76,8 @@ pub struct WorkerNode {
     pub failed_jobs: usize,
     pub total_processing_time: Duration,
     pub last_heartbeat: Instant,
+    /// Simulated fault: every job executed on this worker fails
+    pub faulty: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -90,6 +92,8 @@ pub enum WorkerStatus {
 #[derive(Debug, Clone, Copy)]
 pub enum LoadBalancingStrategy {
     RoundRobin,
+    /// Round-robin with each worker's share proportional to its capacity
+    WeightedRoundRobin,
     LeastLoaded,
     CapacityBased,
 }
@@ -105,6 +109,55 @@ pub struct JobResult {
     pub error: Option<String>,
 }
 
+/// Progress notification emitted while jobs are processed
+///
+/// Every attempt of a job (including retries) emits `WorkerAssigned` and
+/// `JobStarted`, then `JobCompleted` or `JobFailed`. An attempt reaped from
+/// an unhealthy worker emits no outcome; the job is requeued instead.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobEvent {
+    WorkerAssigned {
+        job_id: String,
+        worker_id: String,
+    },
+    JobStarted {
+        job_id: String,
+        worker_id: String,
+    },
+    JobCompleted {
+        job_id: String,
+        worker_id: String,
+        duration: Duration,
+    },
+    JobFailed {
+        job_id: String,
+        worker_id: String,
+        error: String,
+    },
+}
+
+/// Suggested change to the worker pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalingAction {
+    ScaleUp { workers: usize },
+    ScaleDown,
+    Steady,
+}
+
+/// Auto-scaling hint together with the inputs it was derived from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalingAdvice {
+    pub action: ScalingAction,
+    /// Jobs waiting in the queue
+    pub queue_depth: usize,
+    /// Free job slots across healthy workers
+    pub available_capacity: usize,
+    /// Job slots across healthy workers
+    pub total_capacity: usize,
+    /// Estimated jobs per second with every slot busy (0.0 before any job completes)
+    pub recent_throughput: f64,
+}
+
 // ============================================================================
 // Worker Node Implementation
 // ============================================================================
@@ -120,11 +173,19 @@ impl WorkerNode {
             failed_jobs: 0,
             total_processing_time: Duration::ZERO,
             last_heartbeat: Instant::now(),
+            faulty: false,
         }
     }
 
+    #[must_use]
+    pub fn with_faulty(mut self, faulty: bool) -> Self {
+        self.faulty = faulty;
+        self
+    }
+
     pub fn is_available(&self) -> bool {
-        self.status == WorkerStatus::Idle && self.current_load < self.capacity
+        matches!(self.status, WorkerStatus::Idle | WorkerStatus::Busy)
+            && self.current_load < self.capacity
     }
 
     pub fn available_capacity(&self) -> usize {
@@ -185,14 +246,45 @@ impl WorkerNode {
 // Distributed Coordinator
 // ============================================================================
 
+/// Number of most recent results used to estimate throughput for scaling advice
+const SCALING_WINDOW: usize = 20;
+
+/// Longest a queue may take to drain at full capacity before scaling up is advised
+const SCALING_DRAIN_TARGET: Duration = Duration::from_secs(1);
+
+/// A job waiting for (another) attempt, with the workers it already failed on
+struct PendingAttempt {
+    job: DistributedJob,
+    tried_workers: Vec<String>,
+    retry_count: usize,
+}
+
+impl PendingAttempt {
+    fn new(job: DistributedJob) -> Self {
+        Self {
+            job,
+            tried_workers: Vec::new(),
+            retry_count: 0,
+        }
+    }
+}
+
 pub struct DistributedCoordinator {
     workers: Arc<Mutex<HashMap<String, WorkerNode>>>,
     job_queue: Arc<Mutex<VecDeque<DistributedJob>>>,
     job_status: Arc<Mutex<HashMap<String, JobStatus>>>,
     results: Arc<Mutex<Vec<JobResult>>>,
+    /// Jobs currently assigned to a worker (job id -> worker id, job)
+    assignments: Arc<Mutex<HashMap<String, (String, DistributedJob)>>>,
     strategy: LoadBalancingStrategy,
-    _max_retries: usize,
+    max_retries: usize,
     next_worker_index: Arc<Mutex<usize>>,
+    /// Smooth weighted round-robin counters (worker id -> current weight)
+    round_robin_weights: Arc<Mutex<HashMap<String, i64>>>,
+    /// Split jobs (parent id -> chunk job ids)
+    job_children: Arc<Mutex<HashMap<String, Vec<String>>>>,
+    /// Jobs that exhausted their retries, with their last error
+    dead_letters: Arc<Mutex<Vec<(DistributedJob, String)>>>,
 }
 
 impl DistributedCoordinator {
@@ -202,12 +294,23 @@ impl DistributedCoordinator {
             job_queue: Arc::new(Mutex::new(VecDeque::new())),
             job_status: Arc::new(Mutex::new(HashMap::new())),
             results: Arc::new(Mutex::new(Vec::new())),
+            assignments: Arc::new(Mutex::new(HashMap::new())),
             strategy,
-            _max_retries: 3,
+            max_retries: 3,
             next_worker_index: Arc::new(Mutex::new(0)),
+            round_robin_weights: Arc::new(Mutex::new(HashMap::new())),
+            job_children: Arc::new(Mutex::new(HashMap::new())),
+            dead_letters: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    /// Set how many times a failed job is retried on another worker
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
     pub fn register_worker(&self, worker: WorkerNode) -> Result<()> {
         let mut workers = self.workers.lock().unwrap();
         if workers.contains_key(&worker.id) {
@@ -233,7 +336,79 @@ impl DistributedCoordinator {
         Ok(())
     }
 
+    /// Split a job into chunks of at most `chunk_size` files and submit each
+    ///
+    /// Chunks are named `<job id>/chunk-<n>` and balanced independently;
+    /// `get_job_status` on the parent id aggregates their statuses.
+    /// Returns the chunk job ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `chunk_size` is zero or the job has no files.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a state mutex is poisoned.
+    pub fn submit_job_split(&self, job: DistributedJob, chunk_size: usize) -> Result<Vec<String>> {
+        if chunk_size == 0 {
+            return Err("Chunk size must be greater than zero".to_string());
+        }
+        // With no chunks the parent's aggregate status would stay pending
+        if job.files.is_empty() {
+            return Err(format!("Job {} has no files to split", job.id));
+        }
+
+        let DistributedJob {
+            id,
+            files,
+            priority,
+            created_at,
+            timeout,
+        } = job;
+        let chunks: Vec<DistributedJob> = files
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(i, files)| DistributedJob {
+                id: format!("{id}/chunk-{i}"),
+                files: files.to_vec(),
+                priority,
+                created_at,
+                timeout,
+            })
+            .collect();
+        let child_ids: Vec<String> = chunks.iter().map(|chunk| chunk.id.clone()).collect();
+
+        self.job_children
+            .lock()
+            .unwrap()
+            .insert(id, child_ids.clone());
+        for chunk in chunks {
+            self.submit_job(chunk)?;
+        }
+
+        Ok(child_ids)
+    }
+
     pub fn process_jobs(&self) -> Result<Vec<JobResult>> {
+        self.process_jobs_with_callback(|_| {})
+    }
+
+    /// Process queued jobs one at a time, reporting progress to `on_event`
+    ///
+    /// Events are emitted while no internal lock is held, so `on_event` may
+    /// query the coordinator (e.g. `get_job_status`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no worker can accept a job.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a state mutex is poisoned.
+    pub fn process_jobs_with_callback(
+        &self,
+        on_event: impl Fn(JobEvent) + Sync,
+    ) -> Result<Vec<JobResult>> {
         loop {
             let job = {
                 let mut queue = self.job_queue.lock().unwrap();
@@ -242,7 +417,7 @@ impl DistributedCoordinator {
 
             match job {
                 Some(job) => {
-                    self.process_job(job)?;
+                    self.process_job(job, &on_event)?;
                 }
                 None => break,
             }
@@ -252,107 +427,338 @@ impl DistributedCoordinator {
         Ok(results.clone())
     }
 
-    fn process_job(&self, job: DistributedJob) -> Result<()> {
-        let worker_id = self.select_worker(&job)?;
+    /// Process queued jobs in parallel, one thread per in-flight job
+    ///
+    /// Jobs are dispatched while the load-balancing strategy can find a
+    /// worker with spare capacity; otherwise the dispatcher waits for an
+    /// in-flight job to finish. Failed jobs are re-queued for retry on
+    /// another worker. Returns once the queue is drained and all jobs finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no worker can ever accept a job (e.g. none are
+    /// registered or all are offline).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a worker thread panics or a state mutex is poisoned.
+    pub fn process_jobs_concurrent(&self) -> Result<Vec<JobResult>> {
+        self.process_jobs_concurrent_with_callback(|_| {})
+    }
 
-        // Update job status
-        {
-            let mut status = self.job_status.lock().unwrap();
-            status.insert(
-                job.id.clone(),
-                JobStatus::InProgress {
-                    worker_id: worker_id.clone(),
-                    started_at: Instant::now(),
-                },
-            );
-        }
+    /// `process_jobs_concurrent`, reporting progress to `on_event`
+    ///
+    /// `on_event` is called from the dispatching thread and from worker
+    /// threads, possibly at the same time, and never while an internal lock
+    /// is held.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no worker can ever accept a job.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a worker thread panics or a state mutex is poisoned.
+    pub fn process_jobs_concurrent_with_callback(
+        &self,
+        on_event: impl Fn(JobEvent) + Sync,
+    ) -> Result<Vec<JobResult>> {
+        let on_event = &on_event;
+        std::thread::scope(|scope| {
+            let (done_tx, done_rx) = mpsc::channel();
+            let mut retries: VecDeque<PendingAttempt> = VecDeque::new();
+            let mut next: Option<PendingAttempt> = None;
+            let mut in_flight = 0;
+
+            loop {
+                if next.is_none() {
+                    next = retries.pop_front().or_else(|| {
+                        let mut queue = self.job_queue.lock().unwrap();
+                        queue.pop_front().map(PendingAttempt::new)
+                    });
+                }
 
-        // Assign job to worker
-        {
-            let mut workers = self.workers.lock().unwrap();
-            let worker = workers
-                .get_mut(&worker_id)
-                .ok_or_else(|| format!("Worker {} not found", worker_id))?;
-            worker.assign_job(job.files.len())?;
+                let reserved = match &next {
+                    Some(attempt) => Some(self.reserve_worker(attempt, on_event)),
+                    None if in_flight == 0 => break,
+                    None => None,
+                };
+
+                match reserved {
+                    Some(Ok(worker_id)) => {
+                        let attempt = next.take().expect("attempt was just reserved");
+                        let done_tx = done_tx.clone();
+                        in_flight += 1;
+                        scope.spawn(move || {
+                            let result = self.run_attempt(&attempt, &worker_id, on_event);
+                            // The receiver outlives every worker thread in this scope
+                            let _ = done_tx.send((attempt, worker_id, result));
+                        });
+                        continue;
+                    }
+                    Some(Err(error)) if in_flight == 0 => return Err(error),
+                    // No capacity right now, or nothing left to dispatch
+                    Some(Err(_)) | None => {}
+                }
+
+                // Wait for an in-flight job to free its worker
+                let (attempt, worker_id, result) = done_rx
+                    .recv()
+                    .map_err(|e| format!("Worker thread disconnected: {e}"))?;
+                in_flight -= 1;
+                // Reaped attempts (no result) were already requeued
+                if let Some(retry) =
+                    result.and_then(|result| self.retry_after(attempt, worker_id, &result))
+                {
+                    retries.push_back(retry);
+                }
+            }
+
+            let results = self.results.lock().unwrap();
+            Ok(results.clone())
+        })
+    }
+
+    fn process_job(&self, job: DistributedJob, on_event: &(dyn Fn(JobEvent) + Sync)) -> Result<()> {
+        let mut attempt = PendingAttempt::new(job);
+
+        loop {
+            let worker_id = self.reserve_worker(&attempt, on_event)?;
+            let Some(result) = self.run_attempt(&attempt, &worker_id, on_event) else {
+                // The worker was reaped and the job requeued
+                return Ok(());
+            };
+
+            match self.retry_after(attempt, worker_id, &result) {
+                Some(retry) => attempt = retry,
+                None => return Ok(()),
+            }
         }
+    }
+
+    /// Select a worker for `attempt` and assign the job to it
+    fn reserve_worker(
+        &self,
+        attempt: &PendingAttempt,
+        on_event: &(dyn Fn(JobEvent) + Sync),
+    ) -> Result<String> {
+        let worker_id = self.assign_worker(attempt)?;
+        on_event(JobEvent::WorkerAssigned {
+            job_id: attempt.job.id.clone(),
+            worker_id: worker_id.clone(),
+        });
+        Ok(worker_id)
+    }
+
+    /// Record `attempt`'s assignment to a selected worker (under the locks)
+    fn assign_worker(&self, attempt: &PendingAttempt) -> Result<String> {
+        let worker_id = self.select_worker(&attempt.job, &attempt.tried_workers)?;
+
+        let mut workers = self.workers.lock().unwrap();
+        let worker = workers
+            .get_mut(&worker_id)
+            .ok_or_else(|| format!("Worker {worker_id} not found"))?;
+        worker.assign_job(attempt.job.files.len())?;
+
+        let mut assignments = self.assignments.lock().unwrap();
+        assignments.insert(
+            attempt.job.id.clone(),
+            (worker_id.clone(), attempt.job.clone()),
+        );
+
+        // Update job status
+        let mut status = self.job_status.lock().unwrap();
+        status.insert(
+            attempt.job.id.clone(),
+            JobStatus::InProgress {
+                worker_id: worker_id.clone(),
+                started_at: Instant::now(),
+            },
+        );
+        Ok(worker_id)
+    }
+
+    /// Run one attempt on a reserved worker and record its outcome
+    ///
+    /// Returns `None` if the job was reaped from the worker while running;
+    /// the outcome is then discarded since the job is already requeued.
+    fn run_attempt(
+        &self,
+        attempt: &PendingAttempt,
+        worker_id: &str,
+        on_event: &(dyn Fn(JobEvent) + Sync),
+    ) -> Option<Result<JobResult>> {
+        let job = &attempt.job;
+        on_event(JobEvent::JobStarted {
+            job_id: job.id.clone(),
+            worker_id: worker_id.to_string(),
+        });
 
         // Simulate job processing
-        let result = self.execute_job_on_worker(&job, &worker_id);
+        let result = self.execute_job_on_worker(job, worker_id);
 
         // Update worker and results
         {
             let mut workers = self.workers.lock().unwrap();
-            let worker = workers
-                .get_mut(&worker_id)
-                .ok_or_else(|| format!("Worker {} not found", worker_id))?;
+            let mut assignments = self.assignments.lock().unwrap();
+            let still_assigned = assignments
+                .get(&job.id)
+                .is_some_and(|(assigned, _)| assigned == worker_id);
+            if !still_assigned {
+                return None;
+            }
+            assignments.remove(&job.id);
+
+            let mut status = self.job_status.lock().unwrap();
 
             match &result {
                 Ok(job_result) => {
-                    worker.complete_job(job_result.duration);
-                    let mut status = self.job_status.lock().unwrap();
+                    if let Some(worker) = workers.get_mut(worker_id) {
+                        worker.complete_job(job_result.duration);
+                    }
                     status.insert(
                         job.id.clone(),
                         JobStatus::Completed {
-                            worker_id: worker_id.clone(),
+                            worker_id: worker_id.to_string(),
                             duration: job_result.duration,
                         },
                     );
+                    self.results.lock().unwrap().push(job_result.clone());
                 }
                 Err(error) => {
-                    worker.fail_job();
-                    let mut status = self.job_status.lock().unwrap();
+                    if let Some(worker) = workers.get_mut(worker_id) {
+                        worker.fail_job();
+                    }
                     status.insert(
                         job.id.clone(),
                         JobStatus::Failed {
-                            worker_id: worker_id.clone(),
+                            worker_id: worker_id.to_string(),
                             error: error.clone(),
-                            retry_count: 0,
+                            retry_count: attempt.retry_count,
                         },
                     );
                 }
             }
         }
 
-        // Store result
-        if let Ok(job_result) = result {
-            let mut results = self.results.lock().unwrap();
-            results.push(job_result);
+        on_event(match &result {
+            Ok(job_result) => JobEvent::JobCompleted {
+                job_id: job.id.clone(),
+                worker_id: worker_id.to_string(),
+                duration: job_result.duration,
+            },
+            Err(error) => JobEvent::JobFailed {
+                job_id: job.id.clone(),
+                worker_id: worker_id.to_string(),
+                error: error.clone(),
+            },
+        });
+        Some(result)
+    }
+
+    /// Next attempt for a failed job, or `None` if it succeeded or is out of retries
+    ///
+    /// A job out of retries is moved to the dead-letter queue.
+    fn retry_after(
+        &self,
+        mut attempt: PendingAttempt,
+        worker_id: String,
+        result: &Result<JobResult>,
+    ) -> Option<PendingAttempt> {
+        let Err(error) = result else {
+            return None;
+        };
+        if attempt.retry_count >= self.max_retries {
+            self.dead_letters
+                .lock()
+                .unwrap()
+                .push((attempt.job, error.clone()));
+            return None;
         }
+        // Fail over to a worker that hasn't tried this job yet
+        attempt.tried_workers.push(worker_id);
+        attempt.retry_count += 1;
+        Some(attempt)
+    }
 
-        Ok(())
+    /// Select a worker, preferring ones not in `exclude`
+    ///
+    /// Falls back to the full worker set when every eligible worker has been
+    /// excluded, so a job can still be retried on a single-worker cluster.
+    fn select_worker(&self, job: &DistributedJob, exclude: &[String]) -> Result<String> {
+        if !exclude.is_empty() {
+            if let Ok(worker_id) = self.pick_worker(job, exclude) {
+                return Ok(worker_id);
+            }
+        }
+        self.pick_worker(job, &[])
     }
 
-    fn select_worker(&self, job: &DistributedJob) -> Result<String> {
+    fn pick_worker(&self, job: &DistributedJob, exclude: &[String]) -> Result<String> {
         let workers = self.workers.lock().unwrap();
 
         if workers.is_empty() {
             return Err("No workers available".to_string());
         }
 
+        let mut candidates: Vec<&WorkerNode> = workers
+            .values()
+            .filter(|w| !exclude.contains(&w.id))
+            .collect();
+        // Stable rotation order for the round-robin strategies
+        candidates.sort_by(|a, b| a.id.cmp(&b.id));
+
         match self.strategy {
             LoadBalancingStrategy::RoundRobin => {
-                let worker_ids: Vec<String> = workers.keys().cloned().collect();
+                let available: Vec<&&WorkerNode> =
+                    candidates.iter().filter(|w| w.is_available()).collect();
+                if available.is_empty() {
+                    return Err("No available workers".to_string());
+                }
                 let mut index = self.next_worker_index.lock().unwrap();
-                let worker_id = worker_ids[*index % worker_ids.len()].clone();
+                let worker_id = available[*index % available.len()].id.clone();
                 *index += 1;
                 Ok(worker_id)
             }
-            LoadBalancingStrategy::LeastLoaded => workers
-                .values()
+            LoadBalancingStrategy::WeightedRoundRobin => {
+                // Smooth weighted round-robin: every available worker gains its
+                // capacity, the highest is picked and pays back the total
+                let available: Vec<&&WorkerNode> =
+                    candidates.iter().filter(|w| w.is_available()).collect();
+                let mut weights = self.round_robin_weights.lock().unwrap();
+                let mut total = 0;
+                let mut selected: Option<(&str, i64)> = None;
+                for worker in &available {
+                    let weight = i64::try_from(worker.capacity).unwrap_or(i64::MAX);
+                    let current = weights.entry(worker.id.clone()).or_insert(0);
+                    *current += weight;
+                    total += weight;
+                    if selected.is_none_or(|(_, best)| *current > best) {
+                        selected = Some((&worker.id, *current));
+                    }
+                }
+                let (worker_id, _) = selected.ok_or_else(|| "No available workers".to_string())?;
+                if let Some(current) = weights.get_mut(worker_id) {
+                    *current -= total;
+                }
+                Ok(worker_id.to_string())
+            }
+            LoadBalancingStrategy::LeastLoaded => candidates
+                .iter()
                 .filter(|w| w.is_available())
                 .min_by_key(|w| w.current_load)
                 .map(|w| w.id.clone())
                 .ok_or_else(|| "No available workers".to_string()),
             LoadBalancingStrategy::CapacityBased => {
-                workers
-                    .values()
+                candidates
+                    .iter()
                     .filter(|w| w.is_available() && w.available_capacity() >= job.files.len())
                     .max_by_key(|w| w.available_capacity())
                     .map(|w| w.id.clone())
                     .or_else(|| {
                         // Fallback to any available worker
-                        workers
-                            .values()
+                        candidates
+                            .iter()
                             .filter(|w| w.is_available())
                             .max_by_key(|w| w.available_capacity())
                             .map(|w| w.id.clone())
@@ -365,12 +771,24 @@ impl DistributedCoordinator {
     fn execute_job_on_worker(&self, job: &DistributedJob, worker_id: &str) -> Result<JobResult> {
         let start = Instant::now();
 
-        // Simulate transpilation work
+        let faulty = {
+            let workers = self.workers.lock().unwrap();
+            workers.get(worker_id).is_some_and(|w| w.faulty)
+        };
+
+        // Simulate transpilation work, abandoning it once the job times out
         let processing_time = Duration::from_millis(job.files.len() as u64 * 10);
+        if processing_time > job.timeout {
+            std::thread::sleep(job.timeout);
+            return Err(format!(
+                "Job {} timeout after {:?} on worker {}",
+                job.id, job.timeout, worker_id
+            ));
+        }
         std::thread::sleep(processing_time);
 
         // Simulate occasional failures
-        let success = job.priority != JobPriority::Low || job.files.len() < 100;
+        let success = !faulty && (job.priority != JobPriority::Low || job.files.len() < 100);
 
         let duration = start.elapsed();
 
@@ -393,9 +811,214 @@ impl DistributedCoordinator {
         workers.values().cloned().collect()
     }
 
+    /// Jobs that failed more than `max_retries` times, with their last error
+    ///
+    /// # Panics
+    ///
+    /// Panics if a state mutex is poisoned.
+    #[must_use]
+    pub fn dead_letters(&self) -> Vec<(DistributedJob, String)> {
+        self.dead_letters.lock().unwrap().clone()
+    }
+
+    /// Move a dead-lettered job back onto the queue as `Pending`
+    ///
+    /// The job is resubmitted with its original priority and a fresh retry
+    /// budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no dead-lettered job has id `job_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a state mutex is poisoned.
+    pub fn requeue_dead_letter(&self, job_id: &str) -> Result<()> {
+        let job = {
+            let mut dead_letters = self.dead_letters.lock().unwrap();
+            let index = dead_letters
+                .iter()
+                .position(|(job, _)| job.id == job_id)
+                .ok_or_else(|| format!("Job {job_id} is not in the dead-letter queue"))?;
+            dead_letters.remove(index).0
+        };
+        self.submit_job(job)
+    }
+
+    /// Suggest whether to add or remove workers
+    ///
+    /// Jobs that don't fit in the free capacity are a backlog. A backlog the
+    /// cluster can't drain within `SCALING_DRAIN_TARGET` at its recent
+    /// throughput (or at all, before any job has completed) asks for enough
+    /// average-sized workers to absorb it; with no healthy workers, one is
+    /// suggested. An empty queue with an idle worker to spare suggests scaling
+    /// down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a state mutex is poisoned.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn scaling_recommendation(&self) -> ScalingAdvice {
+        let (healthy, total_capacity, available_capacity, idle) = {
+            let workers = self.workers.lock().unwrap();
+            let healthy: Vec<&WorkerNode> = workers
+                .values()
+                .filter(|w| matches!(w.status, WorkerStatus::Idle | WorkerStatus::Busy))
+                .collect();
+            (
+                healthy.len(),
+                healthy.iter().map(|w| w.capacity).sum::<usize>(),
+                healthy
+                    .iter()
+                    .map(|w| w.available_capacity())
+                    .sum::<usize>(),
+                healthy.iter().filter(|w| w.current_load == 0).count(),
+            )
+        };
+        let queue_depth = self.job_queue.lock().unwrap().len();
+
+        let recent_throughput = {
+            let results = self.results.lock().unwrap();
+            let recent = &results[results.len().saturating_sub(SCALING_WINDOW)..];
+            let busy_time: Duration = recent.iter().map(|r| r.duration).sum();
+            if busy_time.is_zero() {
+                0.0
+            } else {
+                // Each slot finishes one job per average job duration
+                recent.len() as f64 / busy_time.as_secs_f64() * total_capacity as f64
+            }
+        };
+
+        let backlog = queue_depth.saturating_sub(available_capacity);
+        let drains_in_time = recent_throughput > 0.0
+            && queue_depth as f64 / recent_throughput <= SCALING_DRAIN_TARGET.as_secs_f64();
+
+        let action = if backlog > 0 && !drains_in_time {
+            let worker_capacity = total_capacity.checked_div(healthy).unwrap_or(backlog);
+            ScalingAction::ScaleUp {
+                workers: backlog.div_ceil(worker_capacity.max(1)),
+            }
+        } else if queue_depth == 0 && idle > 0 && healthy > 1 {
+            ScalingAction::ScaleDown
+        } else {
+            ScalingAction::Steady
+        };
+
+        ScalingAdvice {
+            action,
+            queue_depth,
+            available_capacity,
+            total_capacity,
+            recent_throughput,
+        }
+    }
+
     pub fn get_job_status(&self, job_id: &str) -> Option<JobStatus> {
+        let children = self.job_children.lock().unwrap();
         let status = self.job_status.lock().unwrap();
-        status.get(job_id).cloned()
+
+        match children.get(job_id) {
+            Some(child_ids) => {
+                let child_statuses: Vec<JobStatus> = child_ids
+                    .iter()
+                    .filter_map(|id| status.get(id).cloned())
+                    .collect();
+                Some(Self::aggregate_status(&child_statuses))
+            }
+            None => status.get(job_id).cloned(),
+        }
+    }
+
+    /// Combine chunk statuses into the status of their parent job
+    ///
+    /// Completed only when every chunk completed (duration is the summed
+    /// chunk time); in progress while any chunk runs; failed once a chunk
+    /// failed and nothing is left to run; pending otherwise.
+    fn aggregate_status(children: &[JobStatus]) -> JobStatus {
+        if !children.is_empty()
+            && children
+                .iter()
+                .all(|s| matches!(s, JobStatus::Completed { .. }))
+        {
+            let mut worker_ids: Vec<&str> = Vec::new();
+            let mut duration = Duration::ZERO;
+            for child in children {
+                if let JobStatus::Completed {
+                    worker_id,
+                    duration: child_duration,
+                } = child
+                {
+                    if !worker_ids.contains(&worker_id.as_str()) {
+                        worker_ids.push(worker_id);
+                    }
+                    duration += *child_duration;
+                }
+            }
+            worker_ids.sort_unstable();
+            return JobStatus::Completed {
+                worker_id: worker_ids.join(","),
+                duration,
+            };
+        }
+
+        let in_progress = children
+            .iter()
+            .filter(|s| matches!(s, JobStatus::InProgress { .. }))
+            .min_by_key(|s| match s {
+                JobStatus::InProgress { started_at, .. } => Some(*started_at),
+                _ => None,
+            });
+        if let Some(status) = in_progress {
+            return status.clone();
+        }
+
+        let pending = children.contains(&JobStatus::Pending);
+        match children
+            .iter()
+            .find(|s| matches!(s, JobStatus::Failed { .. }))
+        {
+            Some(failed) if !pending => failed.clone(),
+            _ => JobStatus::Pending,
+        }
+    }
+
+    /// Mark stale workers unhealthy and requeue the jobs assigned to them
+    ///
+    /// Requeued jobs go back to `Pending` at the front of the queue and the
+    /// reaped worker's load is released, so a late result from it is ignored.
+    /// Returns the ids of the requeued jobs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a state mutex is poisoned.
+    pub fn reap_unhealthy_workers(&self, timeout: Duration) -> Vec<String> {
+        let unhealthy = self.health_check(timeout);
+
+        let mut workers = self.workers.lock().unwrap();
+        let mut assignments = self.assignments.lock().unwrap();
+        // Queue before status, in the same order as `submit_job`
+        let mut queue = self.job_queue.lock().unwrap();
+        let mut status = self.job_status.lock().unwrap();
+
+        let reaped: Vec<String> = assignments
+            .iter()
+            .filter(|(_, (worker_id, _))| unhealthy.contains(worker_id))
+            .map(|(job_id, _)| job_id.clone())
+            .collect();
+
+        for job_id in &reaped {
+            let Some((worker_id, job)) = assignments.remove(job_id) else {
+                continue;
+            };
+            if let Some(worker) = workers.get_mut(&worker_id) {
+                worker.current_load = worker.current_load.saturating_sub(1);
+            }
+            status.insert(job_id.clone(), JobStatus::Pending);
+            queue.push_front(job);
+        }
+
+        reaped
     }
 
     pub fn health_check(&self, timeout: Duration) -> Vec<String> {
@@ -426,6 +1049,16 @@ pub struct DistributedMetrics {
     pub worker_count: usize,
     pub average_job_time: Duration,
     pub throughput: f64, // files per second
+    pub per_worker: HashMap<String, WorkerMetrics>,
+}
+
+/// Per-worker share of a distributed run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkerMetrics {
+    pub jobs_handled: usize,
+    pub files_processed: usize,
+    pub busy_time: Duration,
+    pub failures: usize,
 }
 
 impl DistributedMetrics {
@@ -448,6 +1081,17 @@ impl DistributedMetrics {
             0.0
         };
 
+        let mut per_worker: HashMap<String, WorkerMetrics> = HashMap::new();
+        for result in results {
+            let entry = per_worker.entry(result.worker_id.clone()).or_default();
+            entry.jobs_handled += 1;
+            entry.files_processed += result.files_processed;
+            entry.busy_time += result.duration;
+            if !result.success {
+                entry.failures += 1;
+            }
+        }
+
         Self {
             total_jobs,
             completed_jobs,
@@ -457,7 +1101,34 @@ impl DistributedMetrics {
             worker_count,
             average_job_time,
             throughput,
+            per_worker,
+        }
+    }
+
+    /// Metrics from results plus the coordinator's worker stats
+    ///
+    /// Failed attempts don't produce a `JobResult`, so each worker's failure
+    /// count comes from its `failed_jobs` counter and is added to the jobs it
+    /// handled. Workers that handled nothing appear with zeroed metrics.
+    #[must_use]
+    pub fn from_results_with_workers(results: &[JobResult], workers: &[WorkerNode]) -> Self {
+        let mut metrics = Self::from_results(results, workers.len());
+        for worker in workers {
+            let entry = metrics.per_worker.entry(worker.id.clone()).or_default();
+            let recorded_failures = entry.failures;
+            entry.failures = recorded_failures.max(worker.failed_jobs);
+            entry.jobs_handled += entry.failures - recorded_failures;
         }
+        metrics
+    }
+
+    /// Worker with the highest busy time, i.e. the likely bottleneck
+    #[must_use]
+    pub fn bottleneck_worker(&self) -> Option<String> {
+        self.per_worker
+            .iter()
+            .max_by(|a, b| a.1.busy_time.cmp(&b.1.busy_time).then_with(|| b.0.cmp(a.0)))
+            .map(|(id, _)| id.clone())
     }
 
     pub fn success_rate(&self) -> f64 {
@@ -515,7 +1186,8 @@ fn example_basic_distributed() -> Result<()> {
     let results = coordinator.process_jobs()?;
 
     // Display metrics
-    let metrics = DistributedMetrics::from_results(&results, 3);
+    let metrics =
+        DistributedMetrics::from_results_with_workers(&results, &coordinator.get_worker_stats());
     println!("Distributed Processing Metrics:");
     println!("  Total jobs: {}", metrics.total_jobs);
     println!("  Completed: {}", metrics.completed_jobs);
@@ -524,12 +1196,30 @@ fn example_basic_distributed() -> Result<()> {
     println!("  Total files: {}", metrics.total_files);
     println!("  Throughput: {:.2} files/sec", metrics.throughput);
 
+    let mut worker_ids: Vec<&String> = metrics.per_worker.keys().collect();
+    worker_ids.sort();
+    for id in worker_ids {
+        let worker = &metrics.per_worker[id];
+        println!(
+            "  {}: {} jobs, {} files, busy {:?}",
+            id, worker.jobs_handled, worker.files_processed, worker.busy_time
+        );
+    }
+    if let Some(bottleneck) = metrics.bottleneck_worker() {
+        println!("  Bottleneck: {bottleneck}");
+    }
+    println!(
+        "  Scaling advice: {:?}",
+        coordinator.scaling_recommendation().action
+    );
+
     Ok(())
 }
 
 fn example_load_balancing() -> Result<()> {
     let strategies = [
         LoadBalancingStrategy::RoundRobin,
+        LoadBalancingStrategy::WeightedRoundRobin,
         LoadBalancingStrategy::LeastLoaded,
         LoadBalancingStrategy::CapacityBased,
     ];
@@ -583,8 +1273,9 @@ fn example_fault_tolerance() -> Result<()> {
     // Register workers
     coordinator.register_worker(WorkerNode::new("worker-1".to_string(), 15))?;
     coordinator.register_worker(WorkerNode::new("worker-2".to_string(), 15))?;
+    coordinator.register_worker(WorkerNode::new("worker-3".to_string(), 15).with_faulty(true))?;
 
-    println!("Registered 2 workers\n");
+    println!("Registered 3 workers (worker-3 fails every job)\n");
 
     // Submit jobs including some that will fail
     for i in 0..4 {
@@ -618,6 +1309,14 @@ fn example_fault_tolerance() -> Result<()> {
     );
     println!("  Unhealthy workers: {}", unhealthy.len());
 
+    // Jobs that landed on the faulty worker were retried elsewhere
+    for i in 0..4 {
+        let job_id = format!("job-{i}");
+        if let Some(status) = coordinator.get_job_status(&job_id) {
+            println!("  {job_id}: {status:?}");
+        }
+    }
+
     // Display worker health
     let worker_stats = coordinator.get_worker_stats();
     println!("\nWorker Health:");
@@ -778,8 +1477,8 @@ mod tests {
             timeout: Duration::from_secs(60),
         };
 
-        let worker1 = coordinator.select_worker(&job).unwrap();
-        let worker2 = coordinator.select_worker(&job).unwrap();
+        let worker1 = coordinator.select_worker(&job, &[]).unwrap();
+        let worker2 = coordinator.select_worker(&job, &[]).unwrap();
 
         // Round robin should alternate
         assert_ne!(worker1, worker2);
@@ -873,7 +1572,545 @@ mod tests {
             timeout: Duration::from_secs(60),
         };
 
-        let worker = coordinator.select_worker(&large_job).unwrap();
+        let worker = coordinator.select_worker(&large_job, &[]).unwrap();
         assert_eq!(worker, "large"); // Should select worker with more capacity
     }
+
+    fn small_job(id: &str) -> DistributedJob {
+        DistributedJob {
+            id: id.to_string(),
+            files: vec![PathBuf::from("file.rs")],
+            priority: JobPriority::Normal,
+            created_at: Instant::now(),
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn test_failed_job_retried_on_another_worker() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::CapacityBased);
+
+        // The faulty worker has more capacity, so it is picked first
+        coordinator
+            .register_worker(WorkerNode::new("faulty".to_string(), 20).with_faulty(true))
+            .unwrap();
+        coordinator
+            .register_worker(WorkerNode::new("healthy".to_string(), 10))
+            .unwrap();
+
+        coordinator.submit_job(small_job("job-1")).unwrap();
+        let results = coordinator.process_jobs().unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].worker_id, "healthy");
+        assert!(matches!(
+            coordinator.get_job_status("job-1"),
+            Some(JobStatus::Completed { worker_id, .. }) if worker_id == "healthy"
+        ));
+
+        let stats = coordinator.get_worker_stats();
+        let faulty = stats.iter().find(|w| w.id == "faulty").unwrap();
+        assert_eq!(faulty.failed_jobs, 1);
+        assert_eq!(faulty.status, WorkerStatus::Idle);
+    }
+
+    #[test]
+    fn test_job_fails_after_max_retries() {
+        let coordinator =
+            DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin).with_max_retries(2);
+
+        coordinator
+            .register_worker(WorkerNode::new("w1".to_string(), 10).with_faulty(true))
+            .unwrap();
+        coordinator
+            .register_worker(WorkerNode::new("w2".to_string(), 10).with_faulty(true))
+            .unwrap();
+
+        coordinator.submit_job(small_job("job-1")).unwrap();
+        let results = coordinator.process_jobs().unwrap();
+
+        assert!(results.is_empty());
+        assert!(matches!(
+            coordinator.get_job_status("job-1"),
+            Some(JobStatus::Failed { retry_count: 2, .. })
+        ));
+
+        // One initial attempt plus two retries
+        let total_failures: usize = coordinator
+            .get_worker_stats()
+            .iter()
+            .map(|w| w.failed_jobs)
+            .sum();
+        assert_eq!(total_failures, 3);
+    }
+
+    #[test]
+    fn test_concurrent_processing_faster_than_sequential() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::LeastLoaded);
+        for id in ["w1", "w2", "w3"] {
+            coordinator
+                .register_worker(WorkerNode::new(id.to_string(), 2))
+                .unwrap();
+        }
+
+        // 12 jobs x 5 files x 10ms = 600ms if run one after another
+        let job_count = 12;
+        for i in 0..job_count {
+            let mut job = small_job(&format!("job-{i}"));
+            job.files = (0..5)
+                .map(|j| PathBuf::from(format!("file-{j}.rs")))
+                .collect();
+            coordinator.submit_job(job).unwrap();
+        }
+        let sequential = Duration::from_millis(job_count * 5 * 10);
+
+        let start = Instant::now();
+        let results = coordinator.process_jobs_concurrent().unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(results.len(), 12);
+        assert!(results.iter().all(|r| r.success));
+        assert!(
+            elapsed < sequential / 2,
+            "concurrent run took {elapsed:?}, sequential sum is {sequential:?}"
+        );
+
+        // Every worker took part and was released afterwards
+        for worker in coordinator.get_worker_stats() {
+            assert!(worker.completed_jobs > 0);
+            assert_eq!(worker.current_load, 0);
+            assert_eq!(worker.status, WorkerStatus::Idle);
+        }
+    }
+
+    #[test]
+    fn test_concurrent_processing_retries_failed_jobs() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin);
+        coordinator
+            .register_worker(WorkerNode::new("faulty".to_string(), 2).with_faulty(true))
+            .unwrap();
+        coordinator
+            .register_worker(WorkerNode::new("healthy".to_string(), 2))
+            .unwrap();
+
+        for i in 0..6 {
+            coordinator
+                .submit_job(small_job(&format!("job-{i}")))
+                .unwrap();
+        }
+
+        let results = coordinator.process_jobs_concurrent().unwrap();
+
+        assert_eq!(results.len(), 6);
+        assert!(results.iter().all(|r| r.worker_id == "healthy"));
+    }
+
+    #[test]
+    fn test_job_timeout_fails_and_frees_worker() {
+        let coordinator =
+            DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin).with_max_retries(1);
+        coordinator
+            .register_worker(WorkerNode::new("w1".to_string(), 10))
+            .unwrap();
+
+        // 5 files take 50ms of simulated work, far beyond the timeout
+        let mut job = small_job("slow");
+        job.files = (0..5).map(|_| PathBuf::from("file.rs")).collect();
+        job.timeout = Duration::from_millis(1);
+        coordinator.submit_job(job).unwrap();
+
+        let start = Instant::now();
+        let results = coordinator.process_jobs().unwrap();
+
+        assert!(results.is_empty());
+        assert!(start.elapsed() < Duration::from_millis(50));
+        match coordinator.get_job_status("slow") {
+            Some(JobStatus::Failed {
+                error, retry_count, ..
+            }) => {
+                assert!(error.contains("timeout"), "unexpected error: {error}");
+                assert_eq!(retry_count, 1);
+            }
+            other => panic!("expected timeout failure, got {other:?}"),
+        }
+
+        let worker = &coordinator.get_worker_stats()[0];
+        assert_eq!(worker.status, WorkerStatus::Idle);
+        assert_eq!(worker.current_load, 0);
+        assert_eq!(worker.failed_jobs, 2);
+    }
+
+    #[test]
+    fn test_reap_unhealthy_worker_requeues_job() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::LeastLoaded);
+        coordinator
+            .register_worker(WorkerNode::new("w1".to_string(), 10))
+            .unwrap();
+        coordinator
+            .register_worker(WorkerNode::new("w2".to_string(), 10))
+            .unwrap();
+        coordinator.submit_job(small_job("job-1")).unwrap();
+
+        // Dispatch the job without running it
+        let job = coordinator.job_queue.lock().unwrap().pop_front().unwrap();
+        let attempt = PendingAttempt::new(job);
+        let stale_worker = coordinator.reserve_worker(&attempt, &|_| {}).unwrap();
+        assert!(matches!(
+            coordinator.get_job_status("job-1"),
+            Some(JobStatus::InProgress { .. })
+        ));
+
+        coordinator
+            .workers
+            .lock()
+            .unwrap()
+            .get_mut(&stale_worker)
+            .unwrap()
+            .last_heartbeat = Instant::now() - Duration::from_secs(10);
+
+        let requeued = coordinator.reap_unhealthy_workers(Duration::from_secs(5));
+        assert_eq!(requeued, vec!["job-1".to_string()]);
+        assert!(matches!(
+            coordinator.get_job_status("job-1"),
+            Some(JobStatus::Pending)
+        ));
+        assert_eq!(coordinator.job_queue.lock().unwrap()[0].id, "job-1");
+
+        // A late result from the reaped worker is discarded without touching its load
+        assert!(coordinator
+            .run_attempt(&attempt, &stale_worker, &|_| {})
+            .is_none());
+        let stats = coordinator.get_worker_stats();
+        let stale = stats.iter().find(|w| w.id == stale_worker).unwrap();
+        assert_eq!(stale.current_load, 0);
+        assert_eq!(stale.status, WorkerStatus::Unhealthy);
+        assert_eq!(stale.completed_jobs + stale.failed_jobs, 0);
+
+        // The job re-runs on the healthy worker
+        let results = coordinator.process_jobs().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_ne!(results[0].worker_id, stale_worker);
+        assert!(matches!(
+            coordinator.get_job_status("job-1"),
+            Some(JobStatus::Completed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_weighted_round_robin_proportional_to_capacity() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::WeightedRoundRobin);
+        coordinator
+            .register_worker(WorkerNode::new("small".to_string(), 5))
+            .unwrap();
+        coordinator
+            .register_worker(WorkerNode::new("large".to_string(), 20))
+            .unwrap();
+
+        let job = small_job("job");
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for _ in 0..500 {
+            let worker = coordinator.select_worker(&job, &[]).unwrap();
+            *counts.entry(worker).or_insert(0) += 1;
+        }
+
+        let ratio = counts["large"] as f64 / counts["small"] as f64;
+        assert!((3.5..=4.5).contains(&ratio), "ratio was {ratio}");
+    }
+
+    #[test]
+    fn test_round_robin_strategies_skip_full_workers() {
+        for strategy in [
+            LoadBalancingStrategy::RoundRobin,
+            LoadBalancingStrategy::WeightedRoundRobin,
+        ] {
+            let coordinator = DistributedCoordinator::new(strategy);
+            let mut full = WorkerNode::new("full".to_string(), 2);
+            full.assign_job(1).unwrap();
+            full.assign_job(1).unwrap();
+            coordinator.register_worker(full).unwrap();
+            coordinator
+                .register_worker(WorkerNode::new("open".to_string(), 2))
+                .unwrap();
+
+            let job = small_job("job");
+            for _ in 0..20 {
+                assert_eq!(coordinator.select_worker(&job, &[]).unwrap(), "open");
+            }
+        }
+    }
+
+    fn result_on(worker_id: &str, files: usize, millis: u64) -> JobResult {
+        JobResult {
+            job_id: format!("{worker_id}-{files}-{millis}"),
+            worker_id: worker_id.to_string(),
+            success: true,
+            files_processed: files,
+            duration: Duration::from_millis(millis),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_metrics_identify_bottleneck_worker() {
+        let results = vec![
+            result_on("w1", 10, 400),
+            result_on("w1", 8, 300),
+            result_on("w1", 12, 500),
+            result_on("w2", 2, 100),
+            result_on("w3", 3, 150),
+        ];
+
+        let metrics = DistributedMetrics::from_results(&results, 3);
+
+        let w1 = &metrics.per_worker["w1"];
+        assert_eq!(w1.jobs_handled, 3);
+        assert_eq!(w1.files_processed, 30);
+        assert_eq!(w1.busy_time, Duration::from_millis(1200));
+        assert_eq!(w1.failures, 0);
+        assert_eq!(metrics.bottleneck_worker(), Some("w1".to_string()));
+    }
+
+    #[test]
+    fn test_metrics_with_worker_stats() {
+        let results = vec![result_on("w1", 5, 100)];
+        let mut w1 = WorkerNode::new("w1".to_string(), 10);
+        w1.failed_jobs = 2;
+        let idle = WorkerNode::new("idle".to_string(), 10);
+
+        let metrics = DistributedMetrics::from_results_with_workers(&results, &[w1, idle]);
+
+        assert_eq!(metrics.worker_count, 2);
+        assert_eq!(metrics.per_worker["w1"].failures, 2);
+        assert_eq!(metrics.per_worker["w1"].jobs_handled, 3);
+        assert_eq!(metrics.per_worker["idle"], WorkerMetrics::default());
+        assert_eq!(metrics.bottleneck_worker(), Some("w1".to_string()));
+        assert_eq!(
+            DistributedMetrics::from_results(&[], 0).bottleneck_worker(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_submit_job_split_aggregates_children() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::LeastLoaded);
+        for id in ["w1", "w2", "w3"] {
+            coordinator
+                .register_worker(WorkerNode::new(id.to_string(), 10))
+                .unwrap();
+        }
+
+        let mut job = small_job("job-0");
+        job.files = (0..100)
+            .map(|i| PathBuf::from(format!("file-{i}.rs")))
+            .collect();
+
+        let children = coordinator.submit_job_split(job, 10).unwrap();
+        assert_eq!(children.len(), 10);
+        assert_eq!(children[2], "job-0/chunk-2");
+        assert_eq!(coordinator.job_queue.lock().unwrap().len(), 10);
+        assert!(coordinator
+            .job_queue
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|chunk| chunk.files.len() == 10));
+        assert_eq!(
+            coordinator.get_job_status("job-0"),
+            Some(JobStatus::Pending)
+        );
+
+        let results = coordinator.process_jobs().unwrap();
+        assert_eq!(results.len(), 10);
+        assert_eq!(
+            results.iter().map(|r| r.files_processed).sum::<usize>(),
+            100
+        );
+
+        assert!(matches!(
+            coordinator.get_job_status("job-0"),
+            Some(JobStatus::Completed { .. })
+        ));
+        assert!(matches!(
+            coordinator.get_job_status("job-0/chunk-9"),
+            Some(JobStatus::Completed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_submit_job_split_rejects_zero_chunk_size() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin);
+        assert!(coordinator.submit_job_split(small_job("job"), 0).is_err());
+    }
+
+    #[test]
+    fn test_submit_job_split_rejects_empty_job() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin);
+        let mut job = small_job("job");
+        job.files.clear();
+
+        assert!(coordinator.submit_job_split(job, 10).is_err());
+        assert_eq!(coordinator.get_job_status("job"), None);
+    }
+
+    #[test]
+    fn test_progress_callback_reports_each_job() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin);
+        for id in ["w1", "w2"] {
+            coordinator
+                .register_worker(WorkerNode::new(id.to_string(), 10))
+                .unwrap();
+        }
+        for i in 0..3 {
+            coordinator
+                .submit_job(small_job(&format!("job-{i}")))
+                .unwrap();
+        }
+
+        let events = Mutex::new(Vec::new());
+        coordinator
+            .process_jobs_with_callback(|event| events.lock().unwrap().push(event))
+            .unwrap();
+        let events = events.into_inner().unwrap();
+
+        assert_eq!(events.len(), 9);
+        for i in 0..3 {
+            let id = format!("job-{i}");
+            let for_job: Vec<_> = events
+                .iter()
+                .filter(|event| match event {
+                    JobEvent::WorkerAssigned { job_id, .. }
+                    | JobEvent::JobStarted { job_id, .. }
+                    | JobEvent::JobCompleted { job_id, .. }
+                    | JobEvent::JobFailed { job_id, .. } => *job_id == id,
+                })
+                .collect();
+            assert!(matches!(
+                for_job.as_slice(),
+                [
+                    JobEvent::WorkerAssigned { .. },
+                    JobEvent::JobStarted { .. },
+                    JobEvent::JobCompleted { .. },
+                ]
+            ));
+        }
+    }
+
+    #[test]
+    fn test_concurrent_progress_callback_reports_retries() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin);
+        coordinator
+            .register_worker(WorkerNode::new("faulty".to_string(), 2).with_faulty(true))
+            .unwrap();
+        coordinator
+            .register_worker(WorkerNode::new("healthy".to_string(), 2))
+            .unwrap();
+        coordinator.submit_job(small_job("job-1")).unwrap();
+
+        let events = Mutex::new(Vec::new());
+        coordinator
+            .process_jobs_concurrent_with_callback(|event| events.lock().unwrap().push(event))
+            .unwrap();
+        let events = events.into_inner().unwrap();
+
+        let started = events
+            .iter()
+            .filter(|event| matches!(event, JobEvent::JobStarted { .. }))
+            .count();
+        assert_eq!(started, 2);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            JobEvent::JobFailed { worker_id, .. } if worker_id == "faulty"
+        )));
+        assert!(matches!(
+            events.last(),
+            Some(JobEvent::JobCompleted { worker_id, .. }) if worker_id == "healthy"
+        ));
+    }
+
+    #[test]
+    fn test_exhausted_job_lands_in_dead_letter_queue() {
+        let coordinator =
+            DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin).with_max_retries(1);
+        coordinator
+            .register_worker(WorkerNode::new("w1".to_string(), 10).with_faulty(true))
+            .unwrap();
+        coordinator.submit_job(small_job("job-1")).unwrap();
+        coordinator.submit_job(small_job("job-2")).unwrap();
+        coordinator.process_jobs().unwrap();
+
+        let dead_letters = coordinator.dead_letters();
+        assert_eq!(dead_letters.len(), 2);
+        let (job, error) = &dead_letters[0];
+        assert_eq!(job.id, "job-1");
+        assert_eq!(error, "Job job-1 failed on worker w1");
+
+        coordinator.requeue_dead_letter("job-1").unwrap();
+        assert_eq!(
+            coordinator.get_job_status("job-1"),
+            Some(JobStatus::Pending)
+        );
+        assert_eq!(coordinator.dead_letters().len(), 1);
+        assert!(coordinator.requeue_dead_letter("job-1").is_err());
+
+        // The requeued job gets a fresh set of retries
+        coordinator.process_jobs().unwrap();
+        assert_eq!(coordinator.dead_letters().len(), 2);
+    }
+
+    #[test]
+    fn test_successful_jobs_skip_dead_letter_queue() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin);
+        coordinator
+            .register_worker(WorkerNode::new("faulty".to_string(), 2).with_faulty(true))
+            .unwrap();
+        coordinator
+            .register_worker(WorkerNode::new("healthy".to_string(), 2))
+            .unwrap();
+        coordinator.submit_job(small_job("job-1")).unwrap();
+        coordinator.process_jobs_concurrent().unwrap();
+
+        assert!(coordinator.dead_letters().is_empty());
+    }
+
+    #[test]
+    fn test_scaling_recommends_scale_up_for_deep_queue() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::LeastLoaded);
+        for id in ["w1", "w2"] {
+            let mut worker = WorkerNode::new(id.to_string(), 4);
+            worker.current_load = 4;
+            worker.status = WorkerStatus::Busy;
+            coordinator.register_worker(worker).unwrap();
+        }
+        for i in 0..10 {
+            coordinator
+                .submit_job(small_job(&format!("job-{i}")))
+                .unwrap();
+        }
+
+        let advice = coordinator.scaling_recommendation();
+        assert_eq!(advice.queue_depth, 10);
+        assert_eq!(advice.available_capacity, 0);
+        assert_eq!(advice.total_capacity, 8);
+        assert_eq!(advice.recent_throughput, 0.0);
+        // 10 queued jobs need three more 4-slot workers
+        assert_eq!(advice.action, ScalingAction::ScaleUp { workers: 3 });
+    }
+
+    #[test]
+    fn test_scaling_recommends_scale_down_when_idle() {
+        let coordinator = DistributedCoordinator::new(LoadBalancingStrategy::RoundRobin);
+        for id in ["w1", "w2", "w3"] {
+            coordinator
+                .register_worker(WorkerNode::new(id.to_string(), 4))
+                .unwrap();
+        }
+        coordinator.submit_job(small_job("job-1")).unwrap();
+        coordinator.process_jobs().unwrap();
+
+        let advice = coordinator.scaling_recommendation();
+        assert_eq!(advice.queue_depth, 0);
+        assert_eq!(advice.available_capacity, 12);
+        assert!(advice.recent_throughput > 0.0);
+        assert_eq!(advice.action, ScalingAction::ScaleDown);
+    }
 }
@@ -17,8 +17,13 @@
 //! Estimated Time: 52 hours
 //! Prerequisites: RECIPE-200-4 (Optimization Profiles), RECIPE-300-5 (Performance Profiling)
 
+use batuta_cookbook::optimizer::{resolve_conflicts, OptimizationStrategy};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 type Result<T> = std::result::Result<T, String>;
 
@@ -27,7 +32,7 @@ type Result<T> = std::result::Result<T, String>;
 // ============================================================================
 
 /// Code features extracted for ML model
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeFeatures {
     pub lines_of_code: usize,
     pub cyclomatic_complexity: usize,
@@ -37,23 +42,44 @@ pub struct CodeFeatures {
     pub memory_allocations: usize,
     pub io_operations: usize,
     pub dependencies_count: usize,
+    pub logical_operators: usize,
 }
 
-/// Optimization strategy that can be applied
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum OptimizationStrategy {
-    LoopUnrolling,
-    Inlining,
-    ConstantFolding,
-    DeadCodeElimination,
-    MemoryPooling,
-    Parallelization,
-    Vectorization,
-    CacheOptimization,
+impl CodeFeatures {
+    /// Field names, in declaration order
+    pub const FIELD_NAMES: [&'static str; 9] = [
+        "lines_of_code",
+        "cyclomatic_complexity",
+        "function_count",
+        "loop_count",
+        "recursion_depth",
+        "memory_allocations",
+        "io_operations",
+        "dependencies_count",
+        "logical_operators",
+    ];
+
+    /// A copy with the named field set to zero (unknown names change nothing)
+    fn without(&self, field: &str) -> Self {
+        let mut features = self.clone();
+        match field {
+            "lines_of_code" => features.lines_of_code = 0,
+            "cyclomatic_complexity" => features.cyclomatic_complexity = 0,
+            "function_count" => features.function_count = 0,
+            "loop_count" => features.loop_count = 0,
+            "recursion_depth" => features.recursion_depth = 0,
+            "memory_allocations" => features.memory_allocations = 0,
+            "io_operations" => features.io_operations = 0,
+            "dependencies_count" => features.dependencies_count = 0,
+            "logical_operators" => features.logical_operators = 0,
+            _ => {}
+        }
+        features
+    }
 }
 
 /// Historical data point for training
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrainingExample {
     pub features: CodeFeatures,
     pub strategy: OptimizationStrategy,
@@ -65,6 +91,8 @@ pub struct TrainingExample {
 #[derive(Debug, Clone)]
 pub struct OptimizationPrediction {
     pub strategy: OptimizationStrategy,
+    /// Softmax of the raw strategy scores over every learned strategy, so
+    /// confidences lie in [0, 1] and sum to 1 across a full `predict`
     pub confidence: f64,
     pub estimated_speedup: f64,
     pub reasoning: Vec<String>,
@@ -79,6 +107,43 @@ pub struct PerformanceResult {
     pub memory_saved: usize,
 }
 
+impl PerformanceResult {
+    /// Time `baseline` and `optimized` over `iterations` runs each
+    ///
+    /// Times are totals across all iterations. Durations are floored at 1ns
+    /// so a zero-duration run yields a finite speedup (1.0 when both are zero).
+    /// Memory is not measured, so `memory_saved` is 0.
+    pub fn measure<F: Fn(), G: Fn()>(
+        baseline: F,
+        optimized: G,
+        iterations: usize,
+    ) -> PerformanceResult {
+        let time = |f: &dyn Fn()| {
+            if iterations == 0 {
+                return Duration::ZERO;
+            }
+            let start = Instant::now();
+            for _ in 0..iterations {
+                f();
+            }
+            start.elapsed()
+        };
+
+        let baseline_time = time(&baseline);
+        let optimized_time = time(&optimized);
+        let floor = Duration::from_nanos(1);
+        let actual_speedup =
+            baseline_time.max(floor).as_secs_f64() / optimized_time.max(floor).as_secs_f64();
+
+        PerformanceResult {
+            baseline_time,
+            optimized_time,
+            actual_speedup,
+            memory_saved: 0,
+        }
+    }
+}
+
 // ============================================================================
 // Feature Extraction
 // ============================================================================
@@ -96,13 +161,17 @@ impl FeatureExtractor {
             + code.matches(".to_string()").count();
         let io_operations = code.matches("read").count() + code.matches("write").count();
         let dependencies_count = code.matches("use ").count();
+        let logical_operators = code.matches("&&").count() + code.matches("||").count();
 
-        // Simplified cyclomatic complexity: 1 + number of decision points
+        // Simplified cyclomatic complexity: 1 + number of decision points.
+        // Short-circuit operators and `?` early returns each add a branch.
         let complexity = 1
             + code.matches("if ").count()
             + code.matches("match ").count()
             + code.matches("while ").count()
-            + code.matches("for ").count();
+            + code.matches("for ").count()
+            + logical_operators
+            + code.matches('?').count();
 
         CodeFeatures {
             lines_of_code,
@@ -113,17 +182,62 @@ impl FeatureExtractor {
             memory_allocations,
             io_operations,
             dependencies_count,
+            logical_operators,
         }
     }
 
+    /// Most self-calls made by any single function in `code`
+    ///
+    /// Each `fn name` body (found by brace matching) is scanned for calls to
+    /// `name(`, including `self.name(` and `Self::name(`.
     fn estimate_recursion_depth(code: &str) -> usize {
-        // Simple heuristic: count recursive function calls
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
         let mut max_depth = 0;
-        for line in code.lines() {
-            if line.contains("fn ") && line.contains("self.") {
-                max_depth += 1;
+
+        for (fn_pos, _) in code.match_indices("fn ") {
+            if code[..fn_pos].chars().next_back().is_some_and(is_ident) {
+                continue;
             }
+            let after = &code[fn_pos + 3..];
+            let name_len = after.find(|c: char| !is_ident(c)).unwrap_or(after.len());
+            let name = &after[..name_len];
+            let Some(open) = after.find('{') else {
+                continue;
+            };
+            if name.is_empty() || after[..open].contains(';') {
+                // Trait method declarations have no body
+                continue;
+            }
+
+            let mut depth = 0;
+            let mut close = after.len();
+            for (i, c) in after[open..].char_indices() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            close = open + i;
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let body = &after[open..close];
+
+            let calls = body
+                .match_indices(name)
+                .filter(|&(pos, _)| {
+                    let before = &body[..pos];
+                    !before.chars().next_back().is_some_and(is_ident)
+                        && !before.ends_with("fn ")
+                        && body[pos + name.len()..].starts_with('(')
+                })
+                .count();
+            max_depth = max_depth.max(calls);
         }
+
         max_depth
     }
 }
@@ -138,7 +252,7 @@ pub struct MlOptimizer {
     feature_weights: FeatureWeights,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureWeights {
     pub complexity_weight: f64,
     pub loop_weight: f64,
@@ -157,6 +271,18 @@ impl Default for FeatureWeights {
     }
 }
 
+/// Current on-disk model format version
+const MODEL_FORMAT_VERSION: u32 = 1;
+
+/// Versioned envelope for a persisted model
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedModel {
+    version: u32,
+    strategy_scores: HashMap<OptimizationStrategy, f64>,
+    feature_weights: FeatureWeights,
+    training_data: Vec<TrainingExample>,
+}
+
 impl MlOptimizer {
     pub fn new() -> Self {
         Self {
@@ -186,6 +312,10 @@ impl MlOptimizer {
 
         // Update strategy scores based on success rate and average speedup
         for (strategy, (successes, total, speedup_sum)) in strategy_stats {
+            // A strategy with no examples has no evidence; dividing would yield NaN
+            if total == 0 {
+                continue;
+            }
             let success_rate = successes as f64 / total as f64;
             let avg_speedup = speedup_sum / total as f64;
             self.strategy_scores
@@ -202,33 +332,188 @@ impl MlOptimizer {
         })
     }
 
+    /// Feed a measured outcome back into the model
+    ///
+    /// The result becomes a `TrainingExample` (success = speedup > 1.0) and
+    /// only `strategy`'s score is recomputed; feature weights are left for
+    /// the next full `train`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn record_outcome(
+        &mut self,
+        features: CodeFeatures,
+        strategy: OptimizationStrategy,
+        result: &PerformanceResult,
+    ) {
+        self.training_data.push(TrainingExample {
+            features,
+            strategy,
+            speedup: result.actual_speedup,
+            success: result.actual_speedup > 1.0,
+        });
+
+        let (successes, total, speedup_sum) = self
+            .training_data
+            .iter()
+            .filter(|e| e.strategy == strategy)
+            .fold((0usize, 0usize, 0.0), |(successes, total, sum), e| {
+                (
+                    successes + usize::from(e.success),
+                    total + 1,
+                    sum + e.speedup,
+                )
+            });
+        let success_rate = successes as f64 / total as f64;
+        let avg_speedup = speedup_sum / total as f64;
+        self.strategy_scores
+            .insert(strategy, success_rate * avg_speedup);
+    }
+
+    /// Mean absolute difference between predicted and recorded speedups
+    /// across the training data (0.0 when there is none)
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn calibration_error(&self) -> f64 {
+        if self.training_data.is_empty() {
+            return 0.0;
+        }
+
+        let total: f64 = self
+            .training_data
+            .iter()
+            .map(|e| (self.estimate_speedup(&e.features, e.strategy) - e.speedup).abs())
+            .sum();
+        total / self.training_data.len() as f64
+    }
+
+    /// Save the trained model to a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model cannot be serialized or written.
+    pub fn save_model(&self, path: &Path) -> Result<()> {
+        let saved = SavedModel {
+            version: MODEL_FORMAT_VERSION,
+            strategy_scores: self.strategy_scores.clone(),
+            feature_weights: self.feature_weights.clone(),
+            training_data: self.training_data.clone(),
+        };
+
+        let json = serde_json::to_string_pretty(&saved)
+            .map_err(|e| format!("Failed to serialize model: {e}"))?;
+
+        fs::write(path, json).map_err(|e| format!("Failed to write model file: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Load a model previously written by [`MlOptimizer::save_model`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or if it was
+    /// written with an unsupported format version.
+    pub fn load_model(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read model file: {e}"))?;
+
+        let saved: SavedModel = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to deserialize model: {e}"))?;
+
+        if saved.version != MODEL_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported model format version {} (expected {MODEL_FORMAT_VERSION})",
+                saved.version
+            ));
+        }
+
+        Ok(Self {
+            training_data: saved.training_data,
+            strategy_scores: saved.strategy_scores,
+            feature_weights: saved.feature_weights,
+        })
+    }
+
     pub fn predict(&self, features: &CodeFeatures) -> Vec<OptimizationPrediction> {
-        let mut predictions = Vec::new();
+        self.predict_top_n(features, usize::MAX)
+    }
+
+    /// Return at most `n` predictions, best first
+    #[must_use]
+    pub fn predict_top_n(&self, features: &CodeFeatures, n: usize) -> Vec<OptimizationPrediction> {
+        if n == 0 {
+            return Vec::new();
+        }
 
         // Score each strategy based on code features
-        for (&strategy, &base_score) in &self.strategy_scores {
-            let feature_score = self.calculate_feature_score(features, strategy);
-            let confidence = (base_score * feature_score).min(1.0);
-            let estimated_speedup = self.estimate_speedup(features, strategy);
+        let mut raw_scores: Vec<(OptimizationStrategy, f64)> = self
+            .strategy_scores
+            .iter()
+            .map(|(&strategy, &base_score)| {
+                (
+                    strategy,
+                    base_score * self.calculate_feature_score(features, strategy),
+                )
+            })
+            .collect();
+
+        // Sum in a fixed order so confidences don't vary with map iteration
+        raw_scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        // Raw scores are unbounded, so clamping them would tie every strong
+        // strategy at 1.0; a softmax keeps them distinct and in order
+        let max_score = raw_scores
+            .iter()
+            .map(|&(_, score)| score)
+            .filter(|score| score.is_finite())
+            .fold(f64::NEG_INFINITY, f64::max);
+        let exp_score = |score: f64| {
+            if score.is_finite() {
+                (score - max_score).exp()
+            } else {
+                0.0
+            }
+        };
+        let total: f64 = raw_scores.iter().map(|&(_, score)| exp_score(score)).sum();
 
-            predictions.push(OptimizationPrediction {
+        let mut predictions: Vec<OptimizationPrediction> = raw_scores
+            .into_iter()
+            .map(|(strategy, score)| OptimizationPrediction {
                 strategy,
-                confidence,
-                estimated_speedup,
+                confidence: if total > 0.0 {
+                    exp_score(score) / total
+                } else {
+                    0.0
+                },
+                estimated_speedup: self.estimate_speedup(features, strategy),
                 reasoning: self.generate_reasoning(features, strategy),
-            });
-        }
+            })
+            .collect();
 
-        // Sort by confidence and estimated speedup
-        predictions.sort_by(|a, b| {
-            let score_a = a.confidence * a.estimated_speedup;
-            let score_b = b.confidence * b.estimated_speedup;
-            score_b.partial_cmp(&score_a).unwrap()
-        });
+        // Sort by confidence and estimated speedup.
+        // Only the top `n` need ordering: partition them to the front first
+        if n < predictions.len() {
+            predictions.select_nth_unstable_by(n - 1, Self::compare_predictions);
+            predictions.truncate(n);
+        }
+        predictions.sort_by(Self::compare_predictions);
 
         predictions
     }
 
+    /// Order predictions best-first by `confidence * estimated_speedup`,
+    /// treating NaN scores as the lowest so sorting never panics
+    fn compare_predictions(a: &OptimizationPrediction, b: &OptimizationPrediction) -> Ordering {
+        let score_a = a.confidence * a.estimated_speedup;
+        let score_b = b.confidence * b.estimated_speedup;
+
+        match (score_a.is_nan(), score_b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => score_b.total_cmp(&score_a),
+        }
+    }
+
     pub fn recommend(&self, features: &CodeFeatures) -> OptimizationPrediction {
         let predictions = self.predict(features);
         predictions
@@ -242,6 +527,41 @@ impl MlOptimizer {
             })
     }
 
+    /// How much each `CodeFeatures` field drives `strategy`'s feature score
+    ///
+    /// A field's importance is the mean absolute change in the score when
+    /// that field is zeroed, over the training data, under the current
+    /// feature weights. Results are sorted most important first (ties keep
+    /// field order); every importance is 0.0 for an untrained model.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn feature_importance(&self, strategy: OptimizationStrategy) -> Vec<(String, f64)> {
+        let mut importance: Vec<(String, f64)> = CodeFeatures::FIELD_NAMES
+            .iter()
+            .map(|&field| {
+                let total: f64 = self
+                    .training_data
+                    .iter()
+                    .map(|example| {
+                        let score = self.calculate_feature_score(&example.features, strategy);
+                        let ablated = self
+                            .calculate_feature_score(&example.features.without(field), strategy);
+                        (score - ablated).abs()
+                    })
+                    .sum();
+                let mean = if self.training_data.is_empty() {
+                    0.0
+                } else {
+                    total / self.training_data.len() as f64
+                };
+                (field.to_string(), mean)
+            })
+            .collect();
+
+        importance.sort_by(|a, b| b.1.total_cmp(&a.1));
+        importance
+    }
+
     fn calculate_feature_score(
         &self,
         features: &CodeFeatures,
@@ -411,6 +731,7 @@ impl MlOptimizer {
         let mut correct_predictions = 0;
         let mut total_predictions = 0;
         let mut speedup_errors = Vec::new();
+        let mut confusion = HashMap::new();
 
         for example in test_data {
             total_predictions += 1;
@@ -419,6 +740,9 @@ impl MlOptimizer {
             if prediction.strategy == example.strategy {
                 correct_predictions += 1;
             }
+            *confusion
+                .entry((example.strategy, prediction.strategy))
+                .or_insert(0) += 1;
 
             let error = (prediction.estimated_speedup - example.speedup).abs();
             speedup_errors.push(error);
@@ -441,8 +765,47 @@ impl MlOptimizer {
             correct_predictions,
             total_predictions,
             mean_absolute_error: mae,
+            confusion,
         }
     }
+
+    /// Estimate held-out performance with k-fold cross-validation
+    ///
+    /// Example `i` is held out in fold `i % k`; each fold is evaluated by a
+    /// fresh model trained on the other folds. `k` is capped at the number of
+    /// examples (leave-one-out); with fewer than two folds nothing can be
+    /// held out, so the result has no folds and zeroed metrics.
+    #[must_use]
+    pub fn cross_validate(examples: Vec<TrainingExample>, k: usize) -> CrossValidationMetrics {
+        let k = k.min(examples.len());
+        if k < 2 {
+            return CrossValidationMetrics::from_folds(Vec::new());
+        }
+
+        let indexed: Vec<(usize, TrainingExample)> = examples.into_iter().enumerate().collect();
+        let folds = (0..k)
+            .filter_map(|fold| {
+                let (test, train): (Vec<_>, Vec<_>) =
+                    indexed.iter().cloned().partition(|(i, _)| i % k == fold);
+
+                let mut model = Self::new();
+                model
+                    .train(train.into_iter().map(|(_, example)| example).collect())
+                    .ok()?;
+
+                let (test_indices, test_data): (Vec<usize>, Vec<TrainingExample>) =
+                    test.into_iter().unzip();
+                let metrics = model.evaluate(&test_data);
+                Some(FoldMetrics {
+                    test_indices,
+                    accuracy: metrics.accuracy,
+                    mean_absolute_error: metrics.mean_absolute_error,
+                })
+            })
+            .collect();
+
+        CrossValidationMetrics::from_folds(folds)
+    }
 }
 
 // ============================================================================
@@ -462,6 +825,78 @@ pub struct EvaluationMetrics {
     pub correct_predictions: usize,
     pub total_predictions: usize,
     pub mean_absolute_error: f64,
+    /// Counts of `(actual, predicted)` strategy pairs
+    pub confusion: HashMap<(OptimizationStrategy, OptimizationStrategy), usize>,
+}
+
+impl EvaluationMetrics {
+    /// Fraction of predictions of each strategy that matched the actual label
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn per_strategy_precision(&self) -> HashMap<OptimizationStrategy, f64> {
+        let mut totals: HashMap<OptimizationStrategy, (usize, usize)> = HashMap::new();
+
+        for (&(actual, predicted), &count) in &self.confusion {
+            let (correct, total) = totals.entry(predicted).or_insert((0, 0));
+            *total += count;
+            if actual == predicted {
+                *correct += count;
+            }
+        }
+
+        totals
+            .into_iter()
+            .map(|(strategy, (correct, total))| (strategy, correct as f64 / total as f64))
+            .collect()
+    }
+}
+
+/// Held-out performance of one cross-validation fold
+#[derive(Debug, Clone)]
+pub struct FoldMetrics {
+    /// Indices (into the cross-validated examples) held out for this fold
+    pub test_indices: Vec<usize>,
+    pub accuracy: f64,
+    pub mean_absolute_error: f64,
+}
+
+/// Per-fold results of `MlOptimizer::cross_validate` and their spread
+#[derive(Debug, Clone)]
+pub struct CrossValidationMetrics {
+    pub folds: Vec<FoldMetrics>,
+    pub mean_accuracy: f64,
+    pub accuracy_std_dev: f64,
+    pub mean_absolute_error: f64,
+    pub mean_absolute_error_std_dev: f64,
+}
+
+impl CrossValidationMetrics {
+    fn from_folds(folds: Vec<FoldMetrics>) -> Self {
+        let accuracies: Vec<f64> = folds.iter().map(|f| f.accuracy).collect();
+        let errors: Vec<f64> = folds.iter().map(|f| f.mean_absolute_error).collect();
+        let (mean_accuracy, accuracy_std_dev) = mean_and_std_dev(&accuracies);
+        let (mean_absolute_error, mean_absolute_error_std_dev) = mean_and_std_dev(&errors);
+
+        Self {
+            folds,
+            mean_accuracy,
+            accuracy_std_dev,
+            mean_absolute_error,
+            mean_absolute_error_std_dev,
+        }
+    }
+}
+
+/// Mean and population standard deviation (both 0.0 for no values)
+#[allow(clippy::cast_precision_loss)]
+fn mean_and_std_dev(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
 }
 
 // ============================================================================
@@ -471,6 +906,7 @@ pub struct EvaluationMetrics {
 pub struct TransferLearner {
     source_model: MlOptimizer,
     target_domain: String,
+    adaptation_rate: f64,
 }
 
 impl TransferLearner {
@@ -478,15 +914,41 @@ impl TransferLearner {
         Self {
             source_model,
             target_domain,
+            adaptation_rate: 1.0,
         }
     }
 
+    /// Set how strongly target evidence overrides the source scores
+    /// (0.0 = keep source scores, 1.0 = fully retrain). Clamped to `[0, 1]`.
+    #[must_use]
+    pub fn with_adaptation_rate(mut self, rate: f64) -> Self {
+        self.adaptation_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
     pub fn adapt(&mut self, target_examples: Vec<TrainingExample>) -> Result<AdaptationMetrics> {
         // Fine-tune the model with target domain data
         let initial_accuracy = self.source_model.calculate_accuracy();
+        let source_scores = self.source_model.strategy_scores.clone();
 
         self.source_model.train(target_examples.clone())?;
 
+        // Blend retrained scores with the frozen source scores per strategy
+        let rate = self.adaptation_rate;
+        let target_scores = std::mem::take(&mut self.source_model.strategy_scores);
+        self.source_model.strategy_scores = target_scores
+            .into_iter()
+            .filter_map(
+                |(strategy, target_score)| match source_scores.get(&strategy) {
+                    Some(&source_score) => {
+                        Some((strategy, (1.0 - rate) * source_score + rate * target_score))
+                    }
+                    None if rate > 0.0 => Some((strategy, rate * target_score)),
+                    None => None,
+                },
+            )
+            .collect();
+
         let final_accuracy = self.source_model.calculate_accuracy();
         let improvement = final_accuracy - initial_accuracy;
 
@@ -563,6 +1025,7 @@ fn process_data(input: Vec<i32>) -> Vec<i32> {
     );
     println!("  Function Count: {}", features.function_count);
     println!("  Loop Count: {}", features.loop_count);
+    println!("  Logical Operators: {}", features.logical_operators);
     println!("  Memory Allocations: {}", features.memory_allocations);
 
     // Create training data
@@ -582,13 +1045,20 @@ fn process_data(input: Vec<i32>) -> Vec<i32> {
     ];
 
     let mut optimizer = MlOptimizer::new();
-    let metrics = optimizer.train(training_examples)?;
+    let metrics = optimizer.train(training_examples.clone())?;
 
     println!("\nTraining Metrics:");
     println!("  Examples Processed: {}", metrics.examples_processed);
     println!("  Strategies Learned: {}", metrics.strategies_learned);
     println!("  Average Accuracy: {:.1}%", metrics.average_accuracy);
 
+    // Training-set accuracy is optimistic; held-out folds give a fairer estimate
+    let cv = MlOptimizer::cross_validate(training_examples, 2);
+    println!(
+        "  Cross-Validated Accuracy: {:.1}% (± {:.1})",
+        cv.mean_accuracy, cv.accuracy_std_dev
+    );
+
     Ok(())
 }
 
@@ -607,6 +1077,7 @@ fn example_optimization_prediction() -> Result<()> {
                 memory_allocations: 2,
                 io_operations: 0,
                 dependencies_count: 5,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::LoopUnrolling,
             speedup: 1.9,
@@ -622,6 +1093,7 @@ fn example_optimization_prediction() -> Result<()> {
                 memory_allocations: 15,
                 io_operations: 3,
                 dependencies_count: 10,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::MemoryPooling,
             speedup: 2.3,
@@ -637,6 +1109,7 @@ fn example_optimization_prediction() -> Result<()> {
                 memory_allocations: 5,
                 io_operations: 1,
                 dependencies_count: 8,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::Parallelization,
             speedup: 3.2,
@@ -656,12 +1129,13 @@ fn example_optimization_prediction() -> Result<()> {
         memory_allocations: 3,
         io_operations: 0,
         dependencies_count: 6,
+        logical_operators: 0,
     };
 
-    let predictions = optimizer.predict(&test_features);
+    let predictions = optimizer.predict_top_n(&test_features, 3);
 
     println!("Top 3 Optimization Recommendations:");
-    for (i, pred) in predictions.iter().take(3).enumerate() {
+    for (i, pred) in predictions.iter().enumerate() {
         println!("\n{}. {:?}", i + 1, pred.strategy);
         println!("   Confidence: {:.1}%", pred.confidence * 100.0);
         println!("   Estimated Speedup: {:.2}x", pred.estimated_speedup);
@@ -669,8 +1143,35 @@ fn example_optimization_prediction() -> Result<()> {
         for reason in &pred.reasoning {
             println!("     - {}", reason);
         }
+        if let Some((feature, weight)) = optimizer.feature_importance(pred.strategy).first() {
+            println!("   Top feature: {feature} ({weight:.2})");
+        }
     }
 
+    // Validate a prediction against a real measurement
+    let data: Vec<u64> = (0..10_000).collect();
+    let result = PerformanceResult::measure(
+        || {
+            // Copies the input before summing it
+            let copy = data.clone();
+            std::hint::black_box(copy.iter().sum::<u64>());
+        },
+        || {
+            std::hint::black_box(data.iter().sum::<u64>());
+        },
+        100,
+    );
+    println!(
+        "\nMeasured copy-then-sum vs in-place sum: {:?} -> {:?} ({:.2}x)",
+        result.baseline_time, result.optimized_time, result.actual_speedup
+    );
+
+    let ranked: Vec<OptimizationStrategy> = predictions.iter().map(|p| p.strategy).collect();
+    println!(
+        "\nCompatible set after conflict resolution: {:?}",
+        resolve_conflicts(&ranked)
+    );
+
     Ok(())
 }
 
@@ -688,6 +1189,7 @@ fn example_transfer_learning() -> Result<()> {
             memory_allocations: 10,
             io_operations: 15,
             dependencies_count: 20,
+            logical_operators: 0,
         },
         strategy: OptimizationStrategy::CacheOptimization,
         speedup: 2.5,
@@ -711,6 +1213,7 @@ fn example_transfer_learning() -> Result<()> {
             memory_allocations: 8,
             io_operations: 2,
             dependencies_count: 12,
+            logical_operators: 0,
         },
         strategy: OptimizationStrategy::Parallelization,
         speedup: 3.8,
@@ -753,6 +1256,52 @@ mod tests {
         assert!(features.lines_of_code > 0);
     }
 
+    #[test]
+    fn test_recursion_detects_free_function() {
+        let code = "fn fib(n: u64) -> u64 {\n    if n < 2 {\n        return n;\n    }\n    fib(n - 1) + fib(n - 2)\n}";
+        let features = FeatureExtractor::extract(code);
+
+        assert!(features.recursion_depth >= 1);
+        assert_eq!(features.recursion_depth, 2);
+    }
+
+    #[test]
+    fn test_recursion_ignores_non_recursive_code() {
+        let code = "fn total(&self) -> u64 {\n    self.items.iter().map(|i| self.price(i)).sum()\n}\n\nfn price(&self, item: &Item) -> u64 {\n    item.cost\n}\n\nfn prefix_total() -> u64 { total_items() }";
+        let features = FeatureExtractor::extract(code);
+
+        assert_eq!(features.recursion_depth, 0);
+    }
+
+    #[test]
+    fn test_recursion_detects_method_self_call() {
+        let code = "impl Node {\n    fn depth(&self) -> usize {\n        self.child.as_ref().map_or(0, |c| 1 + c.depth())\n    }\n    fn walk(&self) { if let Some(c) = &self.child { Self::walk(c); self.walk(); } }\n}";
+        let features = FeatureExtractor::extract(code);
+
+        // Calls match by name regardless of receiver; walk calls itself twice
+        assert_eq!(features.recursion_depth, 2);
+    }
+
+    #[test]
+    fn test_feature_extraction_counts_logical_operators() {
+        let code = "fn check(a: bool, b: bool, c: bool) -> bool {\n    if a && b || c {\n        return true;\n    }\n    false\n}";
+        let features = FeatureExtractor::extract(code);
+
+        assert_eq!(features.logical_operators, 2);
+        // 1 (base) + 1 (if) + 2 (&&, ||)
+        assert_eq!(features.cyclomatic_complexity, 4);
+    }
+
+    #[test]
+    fn test_feature_extraction_counts_try_operator() {
+        let code = "fn load() -> Result<()> {\n    let data = read_file()?;\n    if data.is_empty() && strict || verbose {\n        parse(&data)?;\n    }\n    Ok(())\n}";
+        let features = FeatureExtractor::extract(code);
+
+        assert_eq!(features.logical_operators, 2);
+        // 1 (base) + 1 (if) + 2 (&&, ||) + 2 (?)
+        assert_eq!(features.cyclomatic_complexity, 6);
+    }
+
     #[test]
     fn test_ml_optimizer_creation() {
         let optimizer = MlOptimizer::new();
@@ -774,6 +1323,7 @@ mod tests {
                 memory_allocations: 1,
                 io_operations: 0,
                 dependencies_count: 5,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::LoopUnrolling,
             speedup: 1.5,
@@ -802,6 +1352,7 @@ mod tests {
                 memory_allocations: 3,
                 io_operations: 0,
                 dependencies_count: 8,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::LoopUnrolling,
             speedup: 2.0,
@@ -819,6 +1370,7 @@ mod tests {
             memory_allocations: 2,
             io_operations: 0,
             dependencies_count: 7,
+            logical_operators: 0,
         };
 
         let predictions = optimizer.predict(&features);
@@ -826,6 +1378,147 @@ mod tests {
         assert!(predictions[0].confidence > 0.0);
     }
 
+    fn multi_strategy_optimizer() -> MlOptimizer {
+        let mut optimizer = MlOptimizer::new();
+        let features = CodeFeatures {
+            lines_of_code: 150,
+            cyclomatic_complexity: 6,
+            function_count: 12,
+            loop_count: 4,
+            recursion_depth: 0,
+            memory_allocations: 12,
+            io_operations: 1,
+            dependencies_count: 5,
+            logical_operators: 0,
+        };
+        let training = [
+            (OptimizationStrategy::LoopUnrolling, 1.8),
+            (OptimizationStrategy::Inlining, 1.3),
+            (OptimizationStrategy::MemoryPooling, 2.2),
+            (OptimizationStrategy::Parallelization, 3.0),
+            (OptimizationStrategy::Vectorization, 2.5),
+        ]
+        .into_iter()
+        .map(|(strategy, speedup)| TrainingExample {
+            features: features.clone(),
+            strategy,
+            speedup,
+            success: true,
+        })
+        .collect();
+
+        optimizer.train(training).unwrap();
+        optimizer
+    }
+
+    #[test]
+    fn test_predict_top_n_matches_predict_prefix() {
+        let optimizer = multi_strategy_optimizer();
+        let features = FeatureExtractor::extract("fn f() { for x in xs { while y { } } }");
+
+        let all = optimizer.predict(&features);
+        let top = optimizer.predict_top_n(&features, 3);
+
+        assert_eq!(all.len(), 5);
+        assert_eq!(top.len(), 3);
+        for (a, b) in all.iter().zip(&top) {
+            assert_eq!(
+                a.confidence * a.estimated_speedup,
+                b.confidence * b.estimated_speedup
+            );
+        }
+    }
+
+    #[test]
+    fn test_predict_top_n_bounds() {
+        let optimizer = multi_strategy_optimizer();
+        let features = FeatureExtractor::extract("fn f() {}");
+
+        assert!(optimizer.predict_top_n(&features, 0).is_empty());
+        assert_eq!(optimizer.predict_top_n(&features, 1).len(), 1);
+        assert_eq!(optimizer.predict_top_n(&features, 50).len(), 5);
+    }
+
+    #[test]
+    fn test_model_save_load_roundtrip() {
+        let optimizer = multi_strategy_optimizer();
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("model.json");
+
+        optimizer.save_model(&path).unwrap();
+        let loaded = MlOptimizer::load_model(&path).unwrap();
+
+        assert_eq!(loaded.training_data.len(), optimizer.training_data.len());
+        assert_eq!(loaded.calculate_accuracy(), optimizer.calculate_accuracy());
+
+        for code in [
+            "fn f() { for x in xs { while y { } } }",
+            "fn g() { let v = Vec::new(); }",
+            "fn h() {}",
+        ] {
+            let features = FeatureExtractor::extract(code);
+            let expected = optimizer.recommend(&features);
+            let actual = loaded.recommend(&features);
+
+            assert_eq!(actual.strategy, expected.strategy);
+            assert_eq!(actual.confidence, expected.confidence);
+            assert_eq!(actual.estimated_speedup, expected.estimated_speedup);
+            assert_eq!(actual.reasoning, expected.reasoning);
+        }
+    }
+
+    #[test]
+    fn test_model_load_rejects_unknown_version() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("model.json");
+        let saved = SavedModel {
+            version: MODEL_FORMAT_VERSION + 1,
+            strategy_scores: HashMap::new(),
+            feature_weights: FeatureWeights::default(),
+            training_data: Vec::new(),
+        };
+        fs::write(&path, serde_json::to_string(&saved).unwrap()).unwrap();
+
+        let err = MlOptimizer::load_model(&path).err().unwrap();
+        assert!(err.contains("Unsupported model format version"));
+    }
+
+    #[test]
+    fn test_predict_with_nan_strategy_score() {
+        let mut optimizer = multi_strategy_optimizer();
+        optimizer
+            .strategy_scores
+            .insert(OptimizationStrategy::ConstantFolding, f64::NAN);
+
+        let features = FeatureExtractor::extract("fn f() { for x in xs { } }");
+        let predictions = optimizer.predict(&features);
+        assert_eq!(predictions.len(), 6);
+
+        let top = optimizer.predict_top_n(&features, 2);
+        assert_eq!(top.len(), 2);
+    }
+
+    #[test]
+    fn test_compare_predictions_sorts_nan_last() {
+        let prediction = |strategy, confidence| OptimizationPrediction {
+            strategy,
+            confidence,
+            estimated_speedup: 2.0,
+            reasoning: Vec::new(),
+        };
+        let mut predictions = vec![
+            prediction(OptimizationStrategy::Inlining, f64::NAN),
+            prediction(OptimizationStrategy::LoopUnrolling, 0.4),
+            prediction(OptimizationStrategy::Vectorization, 0.9),
+        ];
+
+        predictions.sort_by(MlOptimizer::compare_predictions);
+
+        assert_eq!(predictions[0].strategy, OptimizationStrategy::Vectorization);
+        assert_eq!(predictions[1].strategy, OptimizationStrategy::LoopUnrolling);
+        assert_eq!(predictions[2].strategy, OptimizationStrategy::Inlining);
+    }
+
     #[test]
     fn test_recommendation() {
         let mut optimizer = MlOptimizer::new();
@@ -840,6 +1533,7 @@ mod tests {
                 memory_allocations: 1,
                 io_operations: 0,
                 dependencies_count: 5,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::LoopUnrolling,
             speedup: 1.5,
@@ -857,6 +1551,7 @@ mod tests {
             memory_allocations: 1,
             io_operations: 0,
             dependencies_count: 4,
+            logical_operators: 0,
         };
 
         let recommendation = optimizer.recommend(&features);
@@ -878,6 +1573,7 @@ mod tests {
                 memory_allocations: 3,
                 io_operations: 0,
                 dependencies_count: 8,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::LoopUnrolling,
             speedup: 2.0,
@@ -891,6 +1587,58 @@ mod tests {
         assert_eq!(metrics.total_predictions, 1);
     }
 
+    #[test]
+    fn test_evaluation_confusion_matrix() {
+        let mut optimizer = MlOptimizer::new();
+        let loop_heavy = CodeFeatures {
+            lines_of_code: 150,
+            cyclomatic_complexity: 6,
+            function_count: 3,
+            loop_count: 4,
+            recursion_depth: 0,
+            memory_allocations: 1,
+            io_operations: 0,
+            dependencies_count: 5,
+            logical_operators: 0,
+        };
+        optimizer
+            .train(vec![TrainingExample {
+                features: loop_heavy.clone(),
+                strategy: OptimizationStrategy::Vectorization,
+                speedup: 2.5,
+                success: true,
+            }])
+            .unwrap();
+
+        // Only Vectorization was learned, so every LoopUnrolling label is confused with it
+        let test_set: Vec<TrainingExample> = [
+            OptimizationStrategy::LoopUnrolling,
+            OptimizationStrategy::LoopUnrolling,
+            OptimizationStrategy::LoopUnrolling,
+            OptimizationStrategy::Vectorization,
+        ]
+        .into_iter()
+        .map(|strategy| TrainingExample {
+            features: loop_heavy.clone(),
+            strategy,
+            speedup: 2.0,
+            success: true,
+        })
+        .collect();
+
+        let metrics = optimizer.evaluate(&test_set);
+        let key = (
+            OptimizationStrategy::LoopUnrolling,
+            OptimizationStrategy::Vectorization,
+        );
+        assert_eq!(metrics.confusion.get(&key), Some(&3));
+        assert_eq!(metrics.correct_predictions, 1);
+
+        let precision = metrics.per_strategy_precision();
+        assert_eq!(precision[&OptimizationStrategy::Vectorization], 0.25);
+        assert!(!precision.contains_key(&OptimizationStrategy::LoopUnrolling));
+    }
+
     #[test]
     fn test_feature_score_loop_unrolling() {
         let optimizer = MlOptimizer::new();
@@ -904,6 +1652,7 @@ mod tests {
             memory_allocations: 1,
             io_operations: 0,
             dependencies_count: 5,
+            logical_operators: 0,
         };
 
         let score =
@@ -924,6 +1673,7 @@ mod tests {
             memory_allocations: 5,
             io_operations: 1,
             dependencies_count: 10,
+            logical_operators: 0,
         };
 
         let score = optimizer.calculate_feature_score(&features, OptimizationStrategy::Inlining);
@@ -952,6 +1702,7 @@ mod tests {
                 memory_allocations: 5,
                 io_operations: 5,
                 dependencies_count: 10,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::CacheOptimization,
             speedup: 2.0,
@@ -972,6 +1723,7 @@ mod tests {
                 memory_allocations: 4,
                 io_operations: 2,
                 dependencies_count: 8,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::Parallelization,
             speedup: 3.0,
@@ -986,6 +1738,159 @@ mod tests {
         assert_eq!(metrics.examples_used, 1);
     }
 
+    fn transfer_examples() -> (Vec<TrainingExample>, Vec<TrainingExample>) {
+        let features = CodeFeatures {
+            lines_of_code: 120,
+            cyclomatic_complexity: 8,
+            function_count: 6,
+            loop_count: 4,
+            recursion_depth: 0,
+            memory_allocations: 6,
+            io_operations: 2,
+            dependencies_count: 8,
+            logical_operators: 0,
+        };
+        let example = |strategy, speedup, success| TrainingExample {
+            features: features.clone(),
+            strategy,
+            speedup,
+            success,
+        };
+
+        let source = vec![
+            example(OptimizationStrategy::CacheOptimization, 2.0, true),
+            example(OptimizationStrategy::Parallelization, 1.5, true),
+        ];
+        let target = vec![
+            example(OptimizationStrategy::Parallelization, 3.5, true),
+            example(OptimizationStrategy::CacheOptimization, 1.0, false),
+            example(OptimizationStrategy::Vectorization, 2.5, true),
+        ];
+        (source, target)
+    }
+
+    #[test]
+    fn test_transfer_adaptation_rate_zero_keeps_source_scores() {
+        let (source, target) = transfer_examples();
+        let mut source_model = MlOptimizer::new();
+        source_model.train(source).unwrap();
+        let source_scores = source_model.strategy_scores.clone();
+
+        let mut learner =
+            TransferLearner::new(source_model, "frozen".to_string()).with_adaptation_rate(0.0);
+        let metrics = learner.adapt(target).unwrap();
+
+        assert_eq!(learner.source_model.strategy_scores, source_scores);
+        assert_eq!(metrics.examples_used, 3);
+        assert!(metrics.final_accuracy < metrics.initial_accuracy);
+    }
+
+    #[test]
+    fn test_transfer_adaptation_rate_one_matches_full_retrain() {
+        let (source, target) = transfer_examples();
+
+        let mut retrained = MlOptimizer::new();
+        retrained.train(source.clone()).unwrap();
+        retrained.train(target.clone()).unwrap();
+
+        let mut source_model = MlOptimizer::new();
+        source_model.train(source).unwrap();
+        let mut learner =
+            TransferLearner::new(source_model, "retrain".to_string()).with_adaptation_rate(1.0);
+        learner.adapt(target).unwrap();
+
+        assert_eq!(
+            learner.source_model.strategy_scores,
+            retrained.strategy_scores
+        );
+    }
+
+    #[test]
+    fn test_transfer_adaptation_rate_blends_scores() {
+        let (source, target) = transfer_examples();
+        let mut source_model = MlOptimizer::new();
+        source_model.train(source).unwrap();
+        let source_score = source_model.strategy_scores[&OptimizationStrategy::Parallelization];
+
+        let mut learner =
+            TransferLearner::new(source_model, "blend".to_string()).with_adaptation_rate(0.5);
+        learner.adapt(target).unwrap();
+
+        // Combined data: Parallelization speedups 1.5 and 3.5, both successful
+        let blended = learner.source_model.strategy_scores[&OptimizationStrategy::Parallelization];
+        assert!((blended - (0.5 * source_score + 0.5 * 2.5)).abs() < 1e-12);
+        assert!(learner
+            .source_model
+            .strategy_scores
+            .contains_key(&OptimizationStrategy::Vectorization));
+    }
+
+    #[test]
+    fn test_record_outcome_improves_calibration() {
+        let features = CodeFeatures {
+            lines_of_code: 60,
+            cyclomatic_complexity: 4,
+            function_count: 2,
+            loop_count: 3,
+            recursion_depth: 0,
+            memory_allocations: 1,
+            io_operations: 0,
+            dependencies_count: 1,
+            logical_operators: 0,
+        };
+        let strategy = OptimizationStrategy::Parallelization;
+
+        // An over-optimistic prior
+        let mut optimizer = MlOptimizer::new();
+        optimizer
+            .train(vec![TrainingExample {
+                features: features.clone(),
+                strategy,
+                speedup: 5.0,
+                success: true,
+            }])
+            .unwrap();
+        let mut errors = vec![optimizer.calibration_error()];
+
+        let measured = PerformanceResult {
+            baseline_time: Duration::from_millis(20),
+            optimized_time: Duration::from_millis(10),
+            actual_speedup: 2.0,
+            memory_saved: 0,
+        };
+        for _ in 0..4 {
+            optimizer.record_outcome(features.clone(), strategy, &measured);
+            errors.push(optimizer.calibration_error());
+        }
+
+        assert_eq!(optimizer.training_data.len(), 5);
+        assert!(optimizer.training_data.last().unwrap().success);
+        assert!(
+            errors.windows(2).all(|w| w[1] < w[0]),
+            "calibration error should shrink: {errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_measure_detects_speedup() {
+        let result = PerformanceResult::measure(
+            || std::thread::sleep(Duration::from_millis(4)),
+            || std::thread::sleep(Duration::from_millis(1)),
+            3,
+        );
+
+        assert!(result.baseline_time >= Duration::from_millis(12));
+        assert!(result.actual_speedup > 1.0);
+    }
+
+    #[test]
+    fn test_measure_zero_duration() {
+        let result = PerformanceResult::measure(|| {}, || {}, 0);
+
+        assert_eq!(result.baseline_time, Duration::ZERO);
+        assert_eq!(result.actual_speedup, 1.0);
+    }
+
     #[test]
     fn test_speedup_estimation() {
         let mut optimizer = MlOptimizer::new();
@@ -1003,6 +1908,7 @@ mod tests {
             memory_allocations: 1,
             io_operations: 0,
             dependencies_count: 5,
+            logical_operators: 0,
         };
 
         let speedup = optimizer.estimate_speedup(&features, OptimizationStrategy::LoopUnrolling);
@@ -1023,6 +1929,7 @@ mod tests {
             memory_allocations: 1,
             io_operations: 0,
             dependencies_count: 5,
+            logical_operators: 0,
         };
 
         let reasoning =
@@ -1045,6 +1952,7 @@ mod tests {
                 memory_allocations: 1,
                 io_operations: 0,
                 dependencies_count: 5,
+                logical_operators: 0,
             },
             strategy: OptimizationStrategy::LoopUnrolling,
             speedup: 1.5,
@@ -1062,4 +1970,124 @@ mod tests {
         assert_eq!(weights.memory_weight, 1.2);
         assert_eq!(weights.io_weight, 0.8);
     }
+
+    #[test]
+    fn test_feature_importance_ranks_loop_count_for_unrolling() {
+        let optimizer = multi_strategy_optimizer();
+
+        let importance = optimizer.feature_importance(OptimizationStrategy::LoopUnrolling);
+
+        assert_eq!(importance.len(), CodeFeatures::FIELD_NAMES.len());
+        assert_eq!(importance[0].0, "loop_count");
+        assert!(importance[0].1 > 0.0);
+        assert!(importance.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(MlOptimizer::new()
+            .feature_importance(OptimizationStrategy::LoopUnrolling)
+            .iter()
+            .all(|(_, weight)| *weight == 0.0));
+    }
+
+    #[test]
+    fn test_cross_validate_two_folds() {
+        let examples: Vec<TrainingExample> = [
+            (OptimizationStrategy::LoopUnrolling, 4, 1.8),
+            (OptimizationStrategy::MemoryPooling, 0, 2.2),
+            (OptimizationStrategy::LoopUnrolling, 6, 2.0),
+            (OptimizationStrategy::MemoryPooling, 1, 1.9),
+            (OptimizationStrategy::LoopUnrolling, 3, 1.7),
+        ]
+        .into_iter()
+        .map(|(strategy, loop_count, speedup)| TrainingExample {
+            features: CodeFeatures {
+                lines_of_code: 120,
+                cyclomatic_complexity: 6,
+                function_count: 4,
+                loop_count,
+                recursion_depth: 0,
+                memory_allocations: 15,
+                io_operations: 1,
+                dependencies_count: 5,
+                logical_operators: 0,
+            },
+            strategy,
+            speedup,
+            success: true,
+        })
+        .collect();
+
+        let cv = MlOptimizer::cross_validate(examples.clone(), 2);
+
+        assert_eq!(cv.folds.len(), 2);
+        let mut held_out: Vec<usize> = cv
+            .folds
+            .iter()
+            .flat_map(|fold| fold.test_indices.iter().copied())
+            .collect();
+        held_out.sort_unstable();
+        assert_eq!(held_out, (0..examples.len()).collect::<Vec<_>>());
+
+        assert!((0.0..=100.0).contains(&cv.mean_accuracy));
+        assert!(cv.accuracy_std_dev >= 0.0 && cv.accuracy_std_dev <= 100.0);
+        assert!(cv.mean_absolute_error >= 0.0);
+        assert!(cv.mean_absolute_error_std_dev >= 0.0);
+        for fold in &cv.folds {
+            assert!((0.0..=100.0).contains(&fold.accuracy));
+        }
+    }
+
+    #[test]
+    fn test_cross_validate_caps_k() {
+        let example = TrainingExample {
+            features: FeatureExtractor::extract("fn f() { for x in xs { } }"),
+            strategy: OptimizationStrategy::LoopUnrolling,
+            speedup: 1.5,
+            success: true,
+        };
+
+        let cv = MlOptimizer::cross_validate(vec![example.clone(), example.clone()], 10);
+        assert_eq!(cv.folds.len(), 2);
+
+        let cv = MlOptimizer::cross_validate(vec![example], 10);
+        assert!(cv.folds.is_empty());
+        assert_eq!(cv.mean_accuracy, 0.0);
+    }
+
+    #[test]
+    fn test_confidences_are_normalized_and_distinct() {
+        let optimizer = multi_strategy_optimizer();
+        let features = CodeFeatures {
+            lines_of_code: 150,
+            cyclomatic_complexity: 6,
+            function_count: 12,
+            loop_count: 4,
+            recursion_depth: 0,
+            memory_allocations: 12,
+            io_operations: 1,
+            dependencies_count: 5,
+            logical_operators: 0,
+        };
+        let raw_score = |strategy| {
+            optimizer.strategy_scores[&strategy]
+                * optimizer.calculate_feature_score(&features, strategy)
+        };
+
+        let predictions = optimizer.predict(&features);
+
+        // Clamping these would have tied every strategy at 1.0
+        assert!(predictions.iter().all(|p| raw_score(p.strategy) > 1.0));
+        for prediction in &predictions {
+            assert!((0.0..=1.0).contains(&prediction.confidence));
+        }
+        for (i, a) in predictions.iter().enumerate() {
+            for b in &predictions[i + 1..] {
+                assert_ne!(a.confidence, b.confidence);
+                assert_eq!(
+                    a.confidence > b.confidence,
+                    raw_score(a.strategy) > raw_score(b.strategy)
+                );
+            }
+        }
+        let total: f64 = predictions.iter().map(|p| p.confidence).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
 }
@@ -1,13 +1,84 @@
 //! Project analysis and TDG scoring
 
 use crate::types::{Error, Grade, Language, Result, TdgScore};
-use std::collections::HashMap;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories skipped by default when scanning a project
+const DEFAULT_EXCLUDE_DIRS: [&str; 3] = ["target", ".git", "node_modules"];
 
 /// Project analyzer for language detection and quality scoring
 pub struct Analyzer {
     /// Path to project directory
     path: String,
+    /// Directory names skipped while scanning
+    exclude_dirs: Vec<String>,
+    /// Maximum directory depth below the root (None = unlimited)
+    max_depth: Option<usize>,
+    /// Compute a TDG score as part of `analyze()`
+    include_tdg: bool,
+    /// Languages to include (None = every known language)
+    languages: Option<HashSet<Language>>,
+}
+
+/// Builder for a configured `Analyzer`
+///
+/// # Examples
+///
+/// ```
+/// use batuta_cookbook::types::Language;
+/// use batuta_cookbook::Analyzer;
+///
+/// let analyzer = Analyzer::builder("./examples/data/sample_project")
+///     .languages([Language::Python, Language::Rust])
+///     .max_depth(3)
+///     .include_tdg(true)
+///     .build();
+/// ```
+pub struct AnalyzerBuilder {
+    analyzer: Analyzer,
+}
+
+impl AnalyzerBuilder {
+    /// Directory names to skip (replaces the defaults: target, .git, `node_modules`)
+    #[must_use]
+    pub fn exclude_dirs<I, S>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.analyzer.exclude_dirs = dirs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only descend `depth` directories below the root (0 = root files only)
+    #[must_use]
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.analyzer.max_depth = Some(depth);
+        self
+    }
+
+    /// Compute a TDG score as part of `analyze()`
+    #[must_use]
+    pub fn include_tdg(mut self, include: bool) -> Self {
+        self.analyzer.include_tdg = include;
+        self
+    }
+
+    /// Restrict analysis to these languages
+    #[must_use]
+    pub fn languages<I: IntoIterator<Item = Language>>(mut self, languages: I) -> Self {
+        self.analyzer.languages = Some(languages.into_iter().collect());
+        self
+    }
+
+    /// Finish building the analyzer
+    #[must_use]
+    pub fn build(self) -> Analyzer {
+        self.analyzer
+    }
 }
 
 impl Analyzer {
@@ -23,33 +94,204 @@ impl Analyzer {
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
         Self {
             path: path.as_ref().to_string_lossy().to_string(),
+            exclude_dirs: DEFAULT_EXCLUDE_DIRS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            max_depth: None,
+            include_tdg: false,
+            languages: None,
+        }
+    }
+
+    /// Start configuring an analyzer for the given path
+    pub fn builder<P: AsRef<Path>>(path: P) -> AnalyzerBuilder {
+        AnalyzerBuilder {
+            analyzer: Self::new(path),
         }
     }
 
     /// Analyze the project and return a report
     ///
+    /// Counts lines per language for every recognized source file, honoring
+    /// the configured excluded directories, depth limit and language set.
+    ///
     /// # Errors
     ///
     /// Returns `Error::InvalidPath` if path doesn't exist
-    /// Returns `Error::NoFilesFound` if directory is empty
+    /// Returns `Error::NoFilesFound` if no matching source files are found
     pub fn analyze(&self) -> Result<AnalysisReport> {
-        // Stub implementation for now
-        // TODO: Implement actual file scanning and analysis
+        let files = self.source_files()?;
+
+        let stats: Vec<FileStats> = files
+            .iter()
+            .filter_map(|(file, language)| {
+                // Unreadable or non-UTF-8 files are skipped
+                let content = fs::read_to_string(file).ok()?;
+                Some(FileStats::new(*language, &content))
+            })
+            .collect();
+
+        self.build_report(&stats, None)
+    }
+
+    /// Analyze the project, reusing per-file stats from a JSON cache
+    ///
+    /// Files whose content hash matches the cached entry are not re-counted.
+    /// The cache at `cache_path` is created if missing and rewritten with the
+    /// current files afterwards; hit/miss counts land in `report.cache_stats`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `analyze()`, plus `Error::Io` / `Error::Other` if the cache
+    /// cannot be read, parsed or written
+    pub fn analyze_incremental<P: AsRef<Path>>(&self, cache_path: P) -> Result<AnalysisReport> {
+        let cache_path = cache_path.as_ref();
+        let files = self.source_files()?;
+
+        let cache = if cache_path.exists() {
+            AnalysisCache::load_from_file(cache_path)?
+        } else {
+            AnalysisCache::default()
+        };
+
+        let mut updated = AnalysisCache::default();
+        let mut cache_stats = AnalysisCacheStats::default();
+        let mut stats = Vec::with_capacity(files.len());
+        for (file, language) in files {
+            let Ok(content) = fs::read_to_string(&file) else {
+                continue;
+            };
+            let content_hash = content_hash(&content);
+            let file_stats = match cache.entries.get(&file) {
+                Some(entry) if entry.content_hash == content_hash => {
+                    cache_stats.hits += 1;
+                    entry.stats
+                }
+                _ => {
+                    cache_stats.misses += 1;
+                    FileStats::new(language, &content)
+                }
+            };
+            stats.push(file_stats);
+            updated.entries.insert(
+                file,
+                CachedFile {
+                    content_hash,
+                    stats: file_stats,
+                },
+            );
+        }
+
+        updated.save_to_file(cache_path)?;
+        self.build_report(&stats, Some(cache_stats))
+    }
+
+    /// Collect every matching source file under the analyzer's path
+    fn source_files(&self) -> Result<Vec<(PathBuf, Language)>> {
         let path = Path::new(&self.path);
 
         if !path.exists() {
             return Err(Error::InvalidPath(self.path.clone()));
         }
 
-        // For now, return a stub report
-        Ok(AnalysisReport {
+        let mut files = Vec::new();
+        self.scan(path, 0, &mut files);
+        Ok(files)
+    }
+
+    /// Aggregate per-file stats into a report
+    fn build_report(
+        &self,
+        stats: &[FileStats],
+        cache_stats: Option<AnalysisCacheStats>,
+    ) -> Result<AnalysisReport> {
+        if stats.is_empty() {
+            return Err(Error::NoFilesFound(self.path.clone()));
+        }
+
+        let mut languages: HashMap<Language, usize> = HashMap::new();
+        for file in stats {
+            *languages.entry(file.language).or_insert(0) += file.lines;
+        }
+
+        let primary_language = languages
+            .iter()
+            .max_by(|a, b| {
+                a.1.cmp(b.1)
+                    .then_with(|| b.0.to_string().cmp(&a.0.to_string()))
+            })
+            .map_or(Language::Unknown, |(language, _)| *language);
+        let total_lines = languages.values().sum();
+
+        let mut report = AnalysisReport {
             path: self.path.clone(),
-            primary_language: Language::Python,
-            languages: HashMap::from([(Language::Python, 1000)]),
-            file_count: 10,
-            total_lines: 1000,
+            primary_language,
+            languages,
+            file_count: stats.len(),
+            total_lines,
             tdg_score: None,
-        })
+            cache_stats,
+        };
+        if self.include_tdg {
+            report.tdg_score = Some(Self::calculate_tdg(&report));
+        }
+        Ok(report)
+    }
+
+    /// Collect every matching source file under `path` into `files`
+    fn scan(&self, path: &Path, depth: usize, files: &mut Vec<(PathBuf, Language)>) {
+        if path.is_file() {
+            let language = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(Language::from_extension);
+            if let Some(language) = language.filter(|l| self.includes_language(*l)) {
+                files.push((path.to_path_buf(), language));
+            }
+            return;
+        }
+
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                // Symlinked directories are skipped: one pointing at an
+                // ancestor would recurse forever
+                if entry.file_type().is_ok_and(|t| t.is_symlink()) {
+                    continue;
+                }
+                let name = entry.file_name();
+                let excluded = self
+                    .exclude_dirs
+                    .iter()
+                    .any(|dir| name.to_str() == Some(dir.as_str()));
+                let too_deep = self.max_depth.is_some_and(|max| depth >= max);
+                if excluded || too_deep {
+                    continue;
+                }
+                self.scan(&entry_path, depth + 1, files);
+            } else {
+                self.scan(&entry_path, depth, files);
+            }
+        }
+    }
+
+    fn includes_language(&self, language: Language) -> bool {
+        self.languages
+            .as_ref()
+            .is_none_or(|languages| languages.contains(&language))
+    }
+
+    fn calculate_tdg(_report: &AnalysisReport) -> TdgScore {
+        // TODO: Implement actual TDG calculation based on metrics
+        let score = 85.0; // Stub value
+        TdgScore {
+            score,
+            grade: Grade::from_score(score),
+        }
     }
 
     /// Analyze with TDG scoring
@@ -59,15 +301,7 @@ impl Analyzer {
     /// Same as `analyze()`
     pub fn analyze_with_tdg(&self) -> Result<AnalysisReport> {
         let mut report = self.analyze()?;
-
-        // Calculate TDG score
-        // TODO: Implement actual TDG calculation based on metrics
-        let score = 85.0; // Stub value
-        report.tdg_score = Some(TdgScore {
-            score,
-            grade: Grade::from_score(score),
-        });
-
+        report.tdg_score = Some(Self::calculate_tdg(&report));
         Ok(report)
     }
 }
@@ -87,6 +321,8 @@ pub struct AnalysisReport {
     pub total_lines: usize,
     /// Technical Debt Grade (if calculated)
     pub tdg_score: Option<TdgScore>,
+    /// Cache hit/miss counts (only set by `analyze_incremental()`)
+    pub cache_stats: Option<AnalysisCacheStats>,
 }
 
 impl AnalysisReport {
@@ -100,6 +336,72 @@ impl AnalysisReport {
     }
 }
 
+/// Per-file analysis result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStats {
+    /// Detected language
+    pub language: Language,
+    /// Line count
+    pub lines: usize,
+}
+
+impl FileStats {
+    fn new(language: Language, content: &str) -> Self {
+        Self {
+            language,
+            lines: content.lines().count(),
+        }
+    }
+}
+
+/// Cache hit/miss counts for an incremental analysis run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnalysisCacheStats {
+    /// Files whose cached stats were reused
+    pub hits: usize,
+    /// Files that were new or changed and had to be re-analyzed
+    pub misses: usize,
+}
+
+/// Cached stats for one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFile {
+    /// SHA-256 of the file content the stats were computed from
+    content_hash: String,
+    stats: FileStats,
+}
+
+/// On-disk analysis cache keyed by file path
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AnalysisCache {
+    entries: HashMap<PathBuf, CachedFile>,
+}
+
+impl AnalysisCache {
+    fn save_to_file(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize analysis cache: {e}")))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    fn load_from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| Error::Other(format!("Failed to deserialize analysis cache: {e}")))
+    }
+}
+
+/// Hex-encoded SHA-256 of file content, for change detection
+///
+/// A stable cryptographic digest, so a collision can't serve stale stats and
+/// the cache stays valid across Rust releases.
+fn content_hash(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +431,115 @@ mod tests {
         assert!(report.total_lines > 0);
     }
 
+    fn sample_project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("main.py"), "import os\nprint(os.name)\n").unwrap();
+        fs::write(dir.path().join("lib.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.path().join("README.md"), "# Sample\n").unwrap();
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        fs::write(dir.path().join("src/util.py"), "x = 1\n").unwrap();
+        fs::write(dir.path().join("src/nested/deep.go"), "package main\n").unwrap();
+        fs::create_dir_all(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("target/build.rs"), "fn b() {}\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_analyze_counts_languages() {
+        let dir = sample_project();
+        let report = Analyzer::new(dir.path()).analyze().unwrap();
+
+        assert_eq!(report.file_count, 4); // target/ excluded by default
+        assert_eq!(report.languages[&Language::Python], 3);
+        assert_eq!(report.languages[&Language::Rust], 1);
+        assert_eq!(report.languages[&Language::Go], 1);
+        assert_eq!(report.primary_language, Language::Python);
+        assert_eq!(report.total_lines, 5);
+        assert!(report.tdg_score.is_none());
+    }
+
+    #[test]
+    fn test_builder_restricts_languages() {
+        let dir = sample_project();
+        let report = Analyzer::builder(dir.path())
+            .languages([Language::Rust])
+            .build()
+            .analyze()
+            .unwrap();
+
+        assert_eq!(report.file_count, 1);
+        assert_eq!(report.primary_language, Language::Rust);
+        assert!(!report.languages.contains_key(&Language::Python));
+        assert!(!report.languages.contains_key(&Language::Go));
+
+        let none = Analyzer::builder(dir.path())
+            .languages([Language::Java])
+            .build()
+            .analyze();
+        assert!(matches!(none, Err(Error::NoFilesFound(_))));
+    }
+
+    #[test]
+    fn test_builder_depth_excludes_and_tdg() {
+        let dir = sample_project();
+        let report = Analyzer::builder(dir.path())
+            .max_depth(1)
+            .exclude_dirs(Vec::<String>::new())
+            .include_tdg(true)
+            .build()
+            .analyze()
+            .unwrap();
+
+        // target/ is scanned once the default excludes are replaced;
+        // src/nested is beyond the depth limit
+        assert_eq!(report.languages[&Language::Rust], 2);
+        assert!(!report.languages.contains_key(&Language::Go));
+        assert!(report.tdg_score.is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_skips_symlink_cycles() {
+        let dir = sample_project();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("src").join("loop")).unwrap();
+
+        let with_cycle = Analyzer::new(dir.path()).analyze().unwrap();
+        fs::remove_file(dir.path().join("src").join("loop")).unwrap();
+        let without = Analyzer::new(dir.path()).analyze().unwrap();
+
+        assert_eq!(with_cycle.languages, without.languages);
+    }
+
+    #[test]
+    fn test_analyze_incremental_uses_cache() {
+        let dir = sample_project();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache_path = cache_dir.path().join("analysis-cache.json");
+        let analyzer = Analyzer::new(dir.path());
+
+        let first = analyzer.analyze_incremental(&cache_path).unwrap();
+        assert_eq!(
+            first.cache_stats,
+            Some(AnalysisCacheStats { hits: 0, misses: 4 })
+        );
+        assert!(cache_path.exists());
+
+        let second = analyzer.analyze_incremental(&cache_path).unwrap();
+        assert_eq!(
+            second.cache_stats,
+            Some(AnalysisCacheStats { hits: 4, misses: 0 })
+        );
+        assert_eq!(second.languages, first.languages);
+
+        fs::write(dir.path().join("lib.rs"), "fn main() {}\nfn other() {}\n").unwrap();
+        let third = analyzer.analyze_incremental(&cache_path).unwrap();
+        assert_eq!(
+            third.cache_stats,
+            Some(AnalysisCacheStats { hits: 3, misses: 1 })
+        );
+        assert_eq!(third.languages[&Language::Rust], 2);
+    }
+
     #[test]
     fn test_tdg_score_bounds() {
         let analyzer = Analyzer::new(".");
@@ -45,7 +45,7 @@ pub mod types;
 pub mod validator;
 
 // Re-export commonly used types
-pub use analyzer::{AnalysisReport, Analyzer};
+pub use analyzer::{AnalysisCacheStats, AnalysisReport, Analyzer, AnalyzerBuilder, FileStats};
 pub use types::{Error, Result};
 
 /// Library version
@@ -4,6 +4,69 @@
 //! utilities once Trueno integration is complete.
 
 use crate::types::Result;
+use serde::{Deserialize, Serialize};
+
+/// Optimization strategy that can be applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OptimizationStrategy {
+    /// Unroll loop bodies
+    LoopUnrolling,
+    /// Inline function calls
+    Inlining,
+    /// Evaluate constant expressions at compile time
+    ConstantFolding,
+    /// Remove unreachable code
+    DeadCodeElimination,
+    /// Reuse allocations from a shared pool
+    MemoryPooling,
+    /// Spread work across threads
+    Parallelization,
+    /// Use SIMD instructions
+    Vectorization,
+    /// Improve data locality
+    CacheOptimization,
+}
+
+impl OptimizationStrategy {
+    /// Whether applying both strategies together is likely to backfire
+    ///
+    /// The relation is symmetric:
+    /// - `Inlining` + `LoopUnrolling`: compounding code-size growth
+    /// - `Parallelization` + `MemoryPooling`: threads contend on the shared pool
+    /// - `LoopUnrolling` + `Vectorization`: manual unrolling hides loops from the vectorizer
+    #[must_use]
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        use OptimizationStrategy::{
+            Inlining, LoopUnrolling, MemoryPooling, Parallelization, Vectorization,
+        };
+
+        matches!(
+            (self, other),
+            (Inlining | Vectorization, LoopUnrolling)
+                | (LoopUnrolling, Inlining | Vectorization)
+                | (Parallelization, MemoryPooling)
+                | (MemoryPooling, Parallelization)
+        )
+    }
+}
+
+/// Drop strategies that conflict with a higher-priority one
+///
+/// `strategies` is ordered by priority, highest first (e.g. predictions
+/// sorted by confidence). Duplicates are removed; survivors keep their order.
+#[must_use]
+pub fn resolve_conflicts(strategies: &[OptimizationStrategy]) -> Vec<OptimizationStrategy> {
+    let mut kept: Vec<OptimizationStrategy> = Vec::with_capacity(strategies.len());
+    for strategy in strategies {
+        if !kept
+            .iter()
+            .any(|k| k == strategy || k.conflicts_with(strategy))
+        {
+            kept.push(*strategy);
+        }
+    }
+    kept
+}
 
 /// Optimization profile
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -64,6 +127,38 @@ mod tests {
         assert!(!optimizer.gpu_enabled);
     }
 
+    #[test]
+    fn test_resolve_conflicts_keeps_higher_priority() {
+        use OptimizationStrategy::{Inlining, LoopUnrolling};
+
+        assert!(Inlining.conflicts_with(&LoopUnrolling));
+        assert!(LoopUnrolling.conflicts_with(&Inlining));
+        assert_eq!(
+            resolve_conflicts(&[Inlining, LoopUnrolling]),
+            vec![Inlining]
+        );
+        assert_eq!(
+            resolve_conflicts(&[LoopUnrolling, Inlining]),
+            vec![LoopUnrolling]
+        );
+    }
+
+    #[test]
+    fn test_resolve_conflicts_keeps_compatible() {
+        use OptimizationStrategy::{
+            CacheOptimization, ConstantFolding, DeadCodeElimination, Inlining,
+        };
+
+        let strategies = [
+            Inlining,
+            ConstantFolding,
+            DeadCodeElimination,
+            CacheOptimization,
+        ];
+        assert_eq!(resolve_conflicts(&strategies), strategies.to_vec());
+        assert_eq!(resolve_conflicts(&[Inlining, Inlining]), vec![Inlining]);
+    }
+
     #[test]
     fn test_optimizer_with_gpu() {
         let optimizer = Optimizer::new(OptimizationProfile::Aggressive).with_gpu(true);
@@ -1,6 +1,357 @@
 //! Code transpilation utilities
 
 use crate::types::{Error, Language, Result};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// A transpilation backend for one (source, target) language pair
+pub trait Transpiler: Send + Sync {
+    /// Language this backend reads
+    fn source_language(&self) -> Language;
+
+    /// Language this backend emits
+    fn target_language(&self) -> Language;
+
+    /// Transpile `src` into the target language
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TranspilationError` if the source cannot be converted
+    fn transpile(&self, src: &str) -> Result<String>;
+}
+
+/// Heuristic Python→Rust backend
+///
+/// Emits a Rust signature for each `def` (parameters and simple type hints
+/// carried over), turns docstrings into `///` comments, and keeps the
+/// original body in a block comment under a `// TODO: port body` marker.
+/// Comments and any other top-level statements are kept as `//` comments.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimplePythonToRust;
+
+impl Transpiler for SimplePythonToRust {
+    fn source_language(&self) -> Language {
+        Language::Python
+    }
+
+    fn target_language(&self) -> Language {
+        Language::Rust
+    }
+
+    fn transpile(&self, src: &str) -> Result<String> {
+        let lines: Vec<&str> = src.lines().collect();
+        let mut rust_code = String::from("// Transpiled from Python\n\n");
+
+        let mut i = 0;
+        while i < lines.len() {
+            let trimmed = lines[i].trim();
+
+            if trimmed.starts_with("def ") {
+                i = transpile_function(&lines, i, &mut rust_code);
+                continue;
+            }
+
+            if let Some(comment) = trimmed.strip_prefix('#') {
+                let _ = writeln!(rust_code, "// {}", comment.trim());
+            } else if !trimmed.is_empty() {
+                let _ = writeln!(rust_code, "// TODO: port: {trimmed}");
+            }
+            i += 1;
+        }
+
+        Ok(rust_code)
+    }
+}
+
+/// Leading whitespace width of a line
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Map a Python type hint onto a Rust type (`_` when unknown)
+fn rust_type(hint: &str) -> &'static str {
+    match hint.trim() {
+        "int" => "i64",
+        "float" => "f64",
+        "str" => "String",
+        "bool" => "bool",
+        "None" => "()",
+        _ => "_",
+    }
+}
+
+/// Convert a Python parameter list into Rust parameters
+fn rust_params(params: &str) -> String {
+    split_top_level(params)
+        .into_iter()
+        .map(|param| param.split('=').next().unwrap_or("").trim())
+        .filter(|param| !param.is_empty() && !matches!(*param, "self" | "*" | "/"))
+        .map(|param| {
+            let (name, hint) = param.split_once(':').unwrap_or((param, ""));
+            let name = name.trim().trim_start_matches('*');
+            format!("{name}: {}", rust_type(hint))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Split on the commas not nested in `()`, `[]` or `{}`
+///
+/// Keeps subscripted hints (`Dict[str, int]`) and tuple defaults
+/// (`x=(1, 2)`) in one piece.
+fn split_top_level(params: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in params.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&params[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&params[start..]);
+    parts
+}
+
+/// Docstring starting at `lines[start]`: its lines and the index after it
+fn docstring(lines: &[&str], start: usize) -> Option<(Vec<String>, usize)> {
+    let first = lines.get(start)?.trim();
+    let quote = ["\"\"\"", "'''"]
+        .into_iter()
+        .find(|q| first.starts_with(q))?;
+    let rest = &first[quote.len()..];
+
+    if let Some(end) = rest.find(quote) {
+        return Some((vec![rest[..end].trim().to_string()], start + 1));
+    }
+
+    let mut doc = vec![rest.trim().to_string()];
+    for (offset, line) in lines[start + 1..].iter().enumerate() {
+        let line = line.trim();
+        if let Some(end) = line.find(quote) {
+            doc.push(line[..end].trim().to_string());
+            while doc.last().is_some_and(String::is_empty) {
+                doc.pop();
+            }
+            if doc.first().is_some_and(String::is_empty) {
+                doc.remove(0);
+            }
+            return Some((doc, start + offset + 2));
+        }
+        doc.push(line.to_string());
+    }
+    // Unterminated docstring: leave it in the body
+    None
+}
+
+/// Transpile the function defined at `lines[start]`, returning the index of
+/// the first line after its body
+fn transpile_function(lines: &[&str], start: usize, out: &mut String) -> usize {
+    let def_line = lines[start];
+    let def_indent = indent_of(def_line);
+    let signature = def_line.trim().trim_start_matches("def ");
+
+    let Some(header) = parse_def_header(signature) else {
+        // Multi-line or malformed signature; keep it verbatim
+        let _ = writeln!(out, "// TODO: port: {}", def_line.trim());
+        return start + 1;
+    };
+
+    let mut end = start + 1;
+    while end < lines.len() {
+        let line = lines[end];
+        if !line.trim().is_empty() && indent_of(line) <= def_indent {
+            break;
+        }
+        end += 1;
+    }
+
+    let params = rust_params(header.params);
+    let returns = match header.returns.map(rust_type) {
+        Some("()") | None => String::new(),
+        Some(ty) => format!(" -> {ty}"),
+    };
+
+    let mut body_start = start + 1;
+    while body_start < end && lines[body_start].trim().is_empty() {
+        body_start += 1;
+    }
+    // A docstring can only open an indented body, not follow a one-liner
+    let doc = if header.inline_body.is_none() {
+        docstring(&lines[..end], body_start)
+    } else {
+        None
+    };
+    if let Some((doc, after)) = doc {
+        for line in doc {
+            if line.is_empty() {
+                out.push_str("///\n");
+            } else {
+                let _ = writeln!(out, "/// {line}");
+            }
+        }
+        body_start = after;
+    }
+    if let Some(comment) = header.comment {
+        let _ = writeln!(out, "// {comment}");
+    }
+
+    let _ = writeln!(out, "pub fn {}({params}){returns} {{", header.name);
+    out.push_str("    // TODO: port body\n");
+    let body: Vec<&str> = header
+        .inline_body
+        .into_iter()
+        .chain(
+            lines[body_start..end]
+                .iter()
+                .map(|line| line.get(def_indent..).unwrap_or(line.trim_start())),
+        )
+        .collect();
+    let first = body.iter().position(|line| !line.trim().is_empty());
+    let last = body.iter().rposition(|line| !line.trim().is_empty());
+    if let (Some(first), Some(last)) = (first, last) {
+        out.push_str("    /*\n");
+        for (index, line) in body[first..=last].iter().enumerate() {
+            // A one-liner's body sits on the `def` line; indent it like a block
+            let indent = if index == 0 && header.inline_body.is_some() {
+                "        "
+            } else {
+                "    "
+            };
+            // A literal `*/` would close the block comment early
+            let _ = writeln!(out, "{indent}{}", line.replace("*/", "* /").trim_end());
+        }
+        out.push_str("    */\n");
+    }
+    out.push_str("}\n\n");
+
+    end
+}
+
+/// Parts of a `def` line after the `def ` keyword
+struct DefHeader<'a> {
+    name: &'a str,
+    params: &'a str,
+    /// Return type hint, without the `->`
+    returns: Option<&'a str>,
+    /// Trailing `#` comment on the header
+    comment: Option<&'a str>,
+    /// Statement following the header `:` on the same line
+    inline_body: Option<&'a str>,
+}
+
+/// Split a single-line `def` signature into its parts
+///
+/// Returns `None` when the signature continues on another line or is
+/// malformed (no identifier name, unbalanced parentheses, or text other than
+/// a return annotation between the parameters and the `:`).
+fn parse_def_header(signature: &str) -> Option<DefHeader<'_>> {
+    let open = signature.find('(')?;
+    let name = signature[..open].trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    let close = signature[open..].char_indices().find_map(|(offset, c)| {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + offset);
+                }
+            }
+            _ => {}
+        }
+        None
+    })?;
+
+    let tail = &signature[close + 1..];
+    let (annotation, rest) = tail.split_once(':')?;
+    let annotation = annotation.trim();
+    let returns = if annotation.is_empty() {
+        None
+    } else {
+        Some(annotation.strip_prefix("->")?.trim())
+    };
+
+    let rest = rest.trim();
+    let (comment, inline_body) = match rest.strip_prefix('#') {
+        Some(comment) => (Some(comment.trim()).filter(|c| !c.is_empty()), None),
+        None => (None, Some(rest).filter(|body| !body.is_empty())),
+    };
+
+    Some(DefHeader {
+        name,
+        params: &signature[open + 1..close],
+        returns,
+        comment,
+        inline_body,
+    })
+}
+
+/// Transpiler backends keyed by (source, target) language pair
+pub struct TranspilerRegistry {
+    backends: HashMap<(Language, Language), Box<dyn Transpiler>>,
+}
+
+impl TranspilerRegistry {
+    /// Create an empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            backends: HashMap::new(),
+        }
+    }
+
+    /// Create a registry with the built-in backends (`SimplePythonToRust`)
+    #[must_use]
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(SimplePythonToRust);
+        registry
+    }
+
+    /// Register a backend, replacing any existing one for the same pair
+    pub fn register<T: Transpiler + 'static>(&mut self, backend: T) {
+        let key = (backend.source_language(), backend.target_language());
+        self.backends.insert(key, Box::new(backend));
+    }
+
+    /// Backend for the given language pair, if registered
+    #[must_use]
+    pub fn get(&self, source: Language, target: Language) -> Option<&dyn Transpiler> {
+        self.backends.get(&(source, target)).map(AsRef::as_ref)
+    }
+
+    /// Transpile `src` with the backend registered for (source, target)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnsupportedLanguage` if no backend handles the pair,
+    /// or the backend's error if transpilation fails
+    pub fn transpile(&self, source: Language, target: Language, src: &str) -> Result<String> {
+        self.get(source, target)
+            .ok_or_else(|| {
+                Error::UnsupportedLanguage(format!(
+                    "No transpiler registered for {source} -> {target}"
+                ))
+            })?
+            .transpile(src)
+    }
+}
+
+impl Default for TranspilerRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
 
 /// Transpiler configuration
 #[derive(Debug, Clone)]
@@ -79,12 +430,12 @@ impl ConfigBuilder {
     }
 }
 
-/// Transpiler for converting source code
-pub struct Transpiler {
+/// Transpiler driven by a `TranspilerConfig`
+pub struct ConfiguredTranspiler {
     config: TranspilerConfig,
 }
 
-impl Transpiler {
+impl ConfiguredTranspiler {
     /// Create a new transpiler with the given configuration
     #[must_use]
     pub fn new(config: TranspilerConfig) -> Self {
@@ -111,6 +462,20 @@ impl Transpiler {
     }
 }
 
+impl Transpiler for ConfiguredTranspiler {
+    fn source_language(&self) -> Language {
+        self.config.source_lang
+    }
+
+    fn target_language(&self) -> Language {
+        self.config.target_lang
+    }
+
+    fn transpile(&self, src: &str) -> Result<String> {
+        ConfiguredTranspiler::transpile(self, src)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,14 +497,14 @@ mod tests {
     #[test]
     fn test_transpiler_creation() {
         let config = TranspilerConfig::default();
-        let transpiler = Transpiler::new(config);
+        let transpiler = ConfiguredTranspiler::new(config);
         assert_eq!(transpiler.config.source_lang, Language::Python);
     }
 
     #[test]
     fn test_transpile_empty_fails() {
         let config = TranspilerConfig::default();
-        let transpiler = Transpiler::new(config);
+        let transpiler = ConfiguredTranspiler::new(config);
         let result = transpiler.transpile("");
         assert!(result.is_err());
     }
@@ -147,9 +512,121 @@ mod tests {
     #[test]
     fn test_transpile_basic() {
         let config = TranspilerConfig::default();
-        let transpiler = Transpiler::new(config);
+        let transpiler = ConfiguredTranspiler::new(config);
         let result = transpiler.transpile("print('hello')");
         assert!(result.is_ok());
         assert!(result.unwrap().contains("fn main"));
     }
+
+    struct Shout;
+
+    impl Transpiler for Shout {
+        fn source_language(&self) -> Language {
+            Language::Ruby
+        }
+
+        fn target_language(&self) -> Language {
+            Language::Go
+        }
+
+        fn transpile(&self, src: &str) -> Result<String> {
+            Ok(src.to_uppercase())
+        }
+    }
+
+    #[test]
+    fn test_registry_dispatches_by_pair() {
+        let mut registry = TranspilerRegistry::new();
+        registry.register(Shout);
+
+        let out = registry
+            .transpile(Language::Ruby, Language::Go, "puts 1")
+            .unwrap();
+        assert_eq!(out, "PUTS 1");
+
+        let err = registry
+            .transpile(Language::Go, Language::Ruby, "puts 1")
+            .unwrap_err();
+        assert!(matches!(err, Error::UnsupportedLanguage(_)));
+        assert!(registry.get(Language::Python, Language::Rust).is_none());
+    }
+
+    #[test]
+    fn test_default_registry_has_python_to_rust() {
+        let registry = TranspilerRegistry::default();
+        let out = registry
+            .transpile(
+                Language::Python,
+                Language::Rust,
+                "# greet\ndef hello(name):\n    pass\n",
+            )
+            .unwrap();
+        assert!(out.contains("// greet"));
+        assert!(out.contains("pub fn hello(name: _) {"));
+    }
+
+    #[test]
+    fn test_python_signature_and_docstring() {
+        let src = r#"def add(a, b):
+    """Add two numbers."""
+    return a + b
+
+def scale(x: float, factor: int = 2) -> float:
+    """
+    Scale a value.
+
+    Multiplies x by factor.
+    """
+    # keep the comment
+    return x * factor
+"#;
+        let out = SimplePythonToRust.transpile(src).unwrap();
+
+        assert!(out.contains("/// Add two numbers.\npub fn add(a: _, b: _) {"));
+        assert!(out.contains("    // TODO: port body\n    /*\n        return a + b\n    */\n}"));
+        assert!(out.contains(
+            "/// Scale a value.\n///\n/// Multiplies x by factor.\n\
+             pub fn scale(x: f64, factor: i64) -> f64 {"
+        ));
+        assert!(out.contains("        # keep the comment\n        return x * factor\n"));
+    }
+
+    #[test]
+    fn test_python_unported_code_is_kept() {
+        let src = "class Point:\n    pass\n\ndef noop():\n    pass  # nothing */ here\n";
+        let out = SimplePythonToRust.transpile(src).unwrap();
+
+        assert!(out.contains("// TODO: port: class Point:"));
+        assert!(out.contains("        pass  # nothing * / here\n    */\n}"));
+    }
+
+    #[test]
+    fn test_python_one_liner_keeps_body() {
+        let out = SimplePythonToRust
+            .transpile("def f(x): return (x)\n")
+            .unwrap();
+
+        assert!(out.contains("pub fn f(x: _) {"));
+        assert!(out.contains("    /*\n        return (x)\n    */\n}"));
+    }
+
+    #[test]
+    fn test_python_malformed_signature_is_kept() {
+        let out = SimplePythonToRust
+            .transpile("def oops) (:\n    pass\n")
+            .unwrap();
+
+        assert!(out.contains("// TODO: port: def oops) (:"));
+        assert!(out.contains("// TODO: port: pass"));
+        assert!(!out.contains("pub fn"));
+    }
+
+    #[test]
+    fn test_python_params_with_nested_commas() {
+        let out = SimplePythonToRust
+            .transpile("def f(d: Dict[str, int], x=(1, 2), *args, y: int = 3):\n    pass\n")
+            .unwrap();
+
+        assert!(out.contains("pub fn f(d: _, x: _, args: _, y: i64) {"));
+    }
 }
@@ -1,8 +1,13 @@
 //! Common types used across the cookbook
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
 /// Cookbook-specific error type
+///
+/// Implements `Display` and `std::error::Error` via `thiserror`, so it
+/// converts into `Box<dyn std::error::Error>` with `?`.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Invalid path or file not found
@@ -29,6 +34,10 @@ pub enum Error {
     #[error("Analysis failed: {0}")]
     Analysis(String),
 
+    /// I/O error, keeping the original `std::io::Error` as the source
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Generic error
     #[error("Error: {0}")]
     Other(String),
@@ -38,7 +47,7 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Programming language
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Language {
     /// Python
     Python,
@@ -52,24 +61,78 @@ pub enum Language {
     Shell,
     /// JavaScript
     JavaScript,
+    /// TypeScript
+    TypeScript,
+    /// Go
+    Go,
+    /// Java
+    Java,
+    /// Ruby
+    Ruby,
     /// Unknown language
     Unknown,
 }
 
 impl Language {
+    /// Every language, including `Unknown`
+    pub const ALL: [Language; 11] = [
+        Self::Python,
+        Self::C,
+        Self::Cpp,
+        Self::Rust,
+        Self::Shell,
+        Self::JavaScript,
+        Self::TypeScript,
+        Self::Go,
+        Self::Java,
+        Self::Ruby,
+        Self::Unknown,
+    ];
+
     /// Get file extensions for this language
     #[must_use]
     pub fn extensions(self) -> &'static [&'static str] {
+        self.canonical_extensions()
+    }
+
+    /// Canonical file extensions (without the dot) for this language
+    #[must_use]
+    pub fn canonical_extensions(&self) -> &'static [&'static str] {
         match self {
             Self::Python => &["py", "pyw"],
             Self::C => &["c", "h"],
             Self::Cpp => &["cpp", "cc", "cxx", "hpp", "hxx"],
             Self::Rust => &["rs"],
             Self::Shell => &["sh", "bash"],
-            Self::JavaScript => &["js", "jsx", "ts", "tsx"],
+            Self::JavaScript => &["js", "jsx"],
+            Self::TypeScript => &["ts", "tsx"],
+            Self::Go => &["go"],
+            Self::Java => &["java"],
+            Self::Ruby => &["rb"],
             Self::Unknown => &[],
         }
     }
+
+    /// Language owning the file extension `ext` (without the dot, any case)
+    #[must_use]
+    pub fn from_extension(ext: &str) -> Option<Language> {
+        let ext = ext.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.canonical_extensions().contains(&ext.as_str()))
+    }
+}
+
+impl FromStr for Language {
+    type Err = Error;
+
+    /// Parse a display name such as `"Rust"` or `"C++"`, ignoring case
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| Error::UnsupportedLanguage(s.to_string()))
+    }
 }
 
 impl fmt::Display for Language {
@@ -81,6 +144,10 @@ impl fmt::Display for Language {
             Self::Rust => write!(f, "Rust"),
             Self::Shell => write!(f, "Shell"),
             Self::JavaScript => write!(f, "JavaScript"),
+            Self::TypeScript => write!(f, "TypeScript"),
+            Self::Go => write!(f, "Go"),
+            Self::Java => write!(f, "Java"),
+            Self::Ruby => write!(f, "Ruby"),
             Self::Unknown => write!(f, "Unknown"),
         }
     }
@@ -95,6 +162,35 @@ pub struct TdgScore {
     pub grade: Grade,
 }
 
+impl TdgScore {
+    /// Weighted average of `(score, weight)` pairs, with the grade recomputed
+    ///
+    /// Returns a zero score (grade F) if the weights sum to zero or less.
+    #[must_use]
+    pub fn weighted_average(scores: &[(TdgScore, f64)]) -> TdgScore {
+        let total_weight: f64 = scores.iter().map(|(_, weight)| weight).sum();
+        if total_weight <= 0.0 {
+            return Self::from_score(0.0);
+        }
+
+        let weighted: f64 = scores.iter().map(|(tdg, weight)| tdg.score * weight).sum();
+        Self::from_score(weighted / total_weight)
+    }
+
+    /// Subtract `points`, clamp to 0-100, and recompute the grade
+    #[must_use]
+    pub fn with_penalty(&self, points: f64) -> TdgScore {
+        Self::from_score((self.score - points).clamp(0.0, 100.0))
+    }
+
+    fn from_score(score: f64) -> Self {
+        Self {
+            score,
+            grade: Grade::from_score(score),
+        }
+    }
+}
+
 /// Letter grades for TDG scoring
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Grade {
@@ -134,6 +230,72 @@ impl Grade {
             _ => Self::F,
         }
     }
+
+    /// Grade point value on a 4.0 scale (A+ = 4.3)
+    #[must_use]
+    pub fn to_gpa(&self) -> f64 {
+        match self {
+            Self::APlus => 4.3,
+            Self::A => 4.0,
+            Self::AMinus => 3.7,
+            Self::BPlus => 3.3,
+            Self::B => 3.0,
+            Self::BMinus => 2.7,
+            Self::C => 2.0,
+            Self::D => 1.0,
+            Self::F => 0.0,
+        }
+    }
+
+    /// Grade whose GPA is closest to the mean GPA of `grades`
+    ///
+    /// Ties round up to the higher grade. An empty slice averages to `F`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average(grades: &[Grade]) -> Grade {
+        if grades.is_empty() {
+            return Self::F;
+        }
+
+        let mean = grades.iter().map(Grade::to_gpa).sum::<f64>() / grades.len() as f64;
+        let mut best = Self::APlus;
+        for grade in Self::ALL {
+            if (grade.to_gpa() - mean).abs() < (best.to_gpa() - mean).abs() {
+                best = grade;
+            }
+        }
+        best
+    }
+
+    /// All grades from highest to lowest
+    const ALL: [Grade; 9] = [
+        Self::APlus,
+        Self::A,
+        Self::AMinus,
+        Self::BPlus,
+        Self::B,
+        Self::BMinus,
+        Self::C,
+        Self::D,
+        Self::F,
+    ];
+
+    /// Position in the score thresholds, higher is better
+    fn rank(self) -> usize {
+        Self::ALL.len() - 1 - self as usize
+    }
+}
+
+impl PartialOrd for Grade {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Grade {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
 }
 
 impl fmt::Display for Grade {
@@ -160,6 +322,96 @@ mod tests {
     fn test_language_extensions() {
         assert!(Language::Python.extensions().contains(&"py"));
         assert!(Language::Rust.extensions().contains(&"rs"));
+        assert!(Language::TypeScript.extensions().contains(&"tsx"));
+        assert!(!Language::JavaScript.extensions().contains(&"ts"));
+        assert!(Language::Go.extensions().contains(&"go"));
+    }
+
+    #[test]
+    fn test_error_display_per_variant() {
+        let cases = [
+            (Error::InvalidPath("/x".into()), "Invalid path: /x"),
+            (
+                Error::NoFilesFound("src".into()),
+                "No files found in directory: src",
+            ),
+            (
+                Error::UnsupportedLanguage("cobol".into()),
+                "Unsupported language: cobol",
+            ),
+            (
+                Error::TranspilationError("bad".into()),
+                "Transpilation failed: bad",
+            ),
+            (
+                Error::ValidationError("mismatch".into()),
+                "Validation failed: mismatch",
+            ),
+            (Error::Analysis("oops".into()), "Analysis failed: oops"),
+            (Error::Other("misc".into()), "Error: misc"),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.to_string(), expected);
+            assert!(std::error::Error::source(&error).is_none());
+        }
+    }
+
+    #[test]
+    fn test_error_into_boxed_dyn_error() {
+        fn fails() -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Err(Error::Analysis("boxed".into()))?;
+            Ok(())
+        }
+
+        let boxed = fails().unwrap_err();
+        assert_eq!(boxed.to_string(), "Analysis failed: boxed");
+        assert!(matches!(
+            boxed.downcast_ref::<Error>(),
+            Some(Error::Analysis(msg)) if msg == "boxed"
+        ));
+    }
+
+    #[test]
+    fn test_io_error_preserves_kind() {
+        fn read(path: &str) -> Result<String> {
+            Ok(std::fs::read_to_string(path)?)
+        }
+
+        let err = read("/nonexistent/batuta-cookbook/file.txt").unwrap_err();
+        match &err {
+            Error::Io(io_err) => assert_eq!(io_err.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("expected Error::Io, got {other:?}"),
+        }
+        assert!(std::error::Error::source(&err).is_some());
+        assert!(err.to_string().starts_with("I/O error"));
+    }
+
+    #[test]
+    fn test_language_from_extension() {
+        assert_eq!(Language::from_extension("rs"), Some(Language::Rust));
+        assert_eq!(Language::from_extension("PY"), Some(Language::Python));
+        assert_eq!(Language::from_extension("hpp"), Some(Language::Cpp));
+        assert_eq!(Language::from_extension("xyz"), None);
+        assert_eq!(Language::from_extension(""), None);
+    }
+
+    #[test]
+    fn test_language_from_str() {
+        assert_eq!("RUST".parse::<Language>().unwrap(), Language::Rust);
+        assert_eq!("c++".parse::<Language>().unwrap(), Language::Cpp);
+        assert_eq!(
+            "JavaScript".parse::<Language>().unwrap(),
+            Language::JavaScript
+        );
+        assert!(matches!(
+            "cobol".parse::<Language>(),
+            Err(Error::UnsupportedLanguage(name)) if name == "cobol"
+        ));
+
+        for language in Language::ALL {
+            assert_eq!(language.to_string().parse::<Language>().unwrap(), language);
+        }
     }
 
     #[test]
@@ -170,6 +422,76 @@ mod tests {
         assert_eq!(Grade::from_score(45.0), Grade::F);
     }
 
+    #[test]
+    fn test_tdg_weighted_average() {
+        let high = TdgScore {
+            score: 90.0,
+            grade: Grade::A,
+        };
+        let low = TdgScore {
+            score: 60.0,
+            grade: Grade::C,
+        };
+
+        let avg = TdgScore::weighted_average(&[(high, 3.0), (low, 1.0)]);
+        assert!((avg.score - 82.5).abs() < 1e-9);
+        assert_eq!(avg.grade, Grade::BPlus);
+
+        let empty = TdgScore::weighted_average(&[]);
+        assert_eq!(empty.grade, Grade::F);
+    }
+
+    #[test]
+    fn test_tdg_with_penalty() {
+        let tdg = TdgScore {
+            score: 91.0,
+            grade: Grade::A,
+        };
+
+        let penalized = tdg.with_penalty(3.0);
+        assert!((penalized.score - 88.0).abs() < 1e-9);
+        assert_eq!(penalized.grade, Grade::AMinus);
+
+        assert!(tdg.with_penalty(150.0).score.abs() < 1e-9);
+        assert!((tdg.with_penalty(-20.0).score - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_grade_ordering() {
+        assert!(Grade::A > Grade::F);
+        assert!(Grade::APlus > Grade::A);
+        assert!(Grade::BPlus > Grade::B);
+        assert!(Grade::D < Grade::C);
+        assert_eq!(
+            [Grade::C, Grade::APlus, Grade::F].iter().max(),
+            Some(&Grade::APlus)
+        );
+        assert!(Grade::from_score(91.0) > Grade::from_score(89.0));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_grade_to_gpa() {
+        assert_eq!(Grade::A.to_gpa(), 4.0);
+        assert_eq!(Grade::AMinus.to_gpa(), 3.7);
+        assert_eq!(Grade::BPlus.to_gpa(), 3.3);
+        assert_eq!(Grade::B.to_gpa(), 3.0);
+        assert_eq!(Grade::C.to_gpa(), 2.0);
+        assert_eq!(Grade::F.to_gpa(), 0.0);
+    }
+
+    #[test]
+    fn test_grade_average() {
+        assert_eq!(Grade::average(&[Grade::A, Grade::F]), Grade::C);
+        assert_eq!(Grade::average(&[Grade::A, Grade::A]), Grade::A);
+        assert_eq!(Grade::average(&[Grade::APlus]), Grade::APlus);
+        assert_eq!(
+            Grade::average(&[Grade::B, Grade::BPlus, Grade::BMinus]),
+            Grade::B
+        );
+        assert_eq!(Grade::average(&[]), Grade::F);
+    }
+
     #[test]
     fn test_grade_display() {
         assert_eq!(Grade::APlus.to_string(), "A+");